@@ -7,6 +7,12 @@ use std::{
     path::PathBuf,
 };
 
+/// Lets tests check that the spirv binaries are up to date with the shader sources
+#[cfg(feature = "shader-compile")]
+mod shader_source_hash {
+    include!("src/renderer/shader_interfaces/shader_source_hash.rs");
+}
+
 /// Attempts to read the source file at `shader_path` and returns its contents as a String
 #[cfg(feature = "shader-compile")]
 fn read_shader(shader_path: &PathBuf) -> std::io::Result<String> {
@@ -59,7 +65,7 @@ fn gen_shader_spirv() {
     let compiler = Compiler::new().expect("failed to initialize shaderc compiler");
 
     // iterate over files in shaders directory
-    for dir_entry in std::fs::read_dir(shader_dir).expect("invalid shader source path") {
+    for dir_entry in std::fs::read_dir(&shader_dir).expect("invalid shader source path") {
         let dir_entry = dir_entry.expect("fs::ReadDir io error during iteration");
         let shader_path = dir_entry.path();
 
@@ -167,6 +173,12 @@ fn gen_shader_spirv() {
             .write_all(spirv_bin)
             .expect("failed to write spirv data to output file");
     }
+
+    // record which sources the binaries were compiled from
+    let shader_source_hash = shader_source_hash::shader_source_hash(&shader_dir)
+        .expect("failed to hash shader sources");
+    std::fs::write(shader_source_hash::SHADER_SOURCE_HASH_PATH, shader_source_hash)
+        .expect("failed to write shader source hash");
 }
 
 fn main() {
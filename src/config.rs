@@ -4,8 +4,8 @@ use log::LevelFilter;
 pub const ENGINE_NAME: &str = "Goshenite";
 
 pub const ENGINE_VERSION_MAJOR: u8 = 0;
-pub const ENGINE_VERSION_MINOR: u8 = 2;
-pub const ENGINE_VERSION_PATCH: u8 = 2;
+pub const ENGINE_VERSION_MINOR: u8 = 3;
+pub const ENGINE_VERSION_PATCH: u8 = 0;
 
 pub const MAGIC_BYTE: u8 = 0b_1001;
 pub const PRECURSOR_BYTE_COUNT: usize = 4;
@@ -1,9 +1,48 @@
 use super::{config_engine::AABB_EDGE, object::object::ObjectId};
 use crate::renderer::shader_interfaces::vertex_inputs::BoundingBoxVertex;
-use glam::Vec3;
+use glam::{Mat3, Vec3};
 
 pub const AABB_VERTEX_COUNT: usize = 36;
 
+// ~~ Half Extents ~~
+// Distances from the center to the faces of the bounding box of a shape after applying a linear
+// `transform`. These can be added together for shapes that are sweeps of simpler shapes (e.g. a
+// torus is a sphere swept around a disc).
+
+/// Box with half lengths `half_dimensions`
+pub fn box_half_extents(half_dimensions: Vec3, transform: Mat3) -> Vec3 {
+    let abs_transform = Mat3::from_cols(
+        transform.x_axis.abs(),
+        transform.y_axis.abs(),
+        transform.z_axis.abs(),
+    );
+    abs_transform * half_dimensions
+}
+
+/// Flat disc lying in the local xy plane
+pub fn disc_half_extents(radius: f32, transform: Mat3) -> Vec3 {
+    let squared = transform.x_axis * transform.x_axis + transform.y_axis * transform.y_axis;
+    radius * sqrt_vec3(squared)
+}
+
+pub fn sphere_half_extents(radius: f32, transform: Mat3) -> Vec3 {
+    let squared = transform.x_axis * transform.x_axis
+        + transform.y_axis * transform.y_axis
+        + transform.z_axis * transform.z_axis;
+    radius * sqrt_vec3(squared)
+}
+
+/// Line segment along the local z axis from `-half_length` to `half_length`
+pub fn z_segment_half_extents(half_length: f32, transform: Mat3) -> Vec3 {
+    transform.z_axis.abs() * half_length
+}
+
+fn sqrt_vec3(v: Vec3) -> Vec3 {
+    Vec3::new(v.x.sqrt(), v.y.sqrt(), v.z.sqrt())
+}
+
+// ~~ Aabb ~~
+
 /// Axis aligned bounding box
 #[derive(Clone, PartialEq)]
 pub struct Aabb {
@@ -24,6 +63,13 @@ impl Aabb {
         Self { max, min }
     }
 
+    /// Bounding box of a box with x/y/z lengths `dimensions` transformed by `transform` (e.g.
+    /// rotation and scale) around `center`.
+    pub fn new_transformed_box(center: Vec3, dimensions: Vec3, transform: Mat3) -> Self {
+        let half_extents = box_half_extents(dimensions / 2., transform);
+        Self::new(center, half_extents * 2.)
+    }
+
     pub fn new_zero() -> Self {
         Self {
             max: Vec3::ZERO,
@@ -36,11 +82,42 @@ impl Aabb {
         self.min = self.min.min(aabb.min);
     }
 
+    /// Returns `None` if the boxes don't overlap.
+    pub fn intersection(&self, aabb: &Aabb) -> Option<Aabb> {
+        let max = self.max.min(aabb.max);
+        let min = self.min.max(aabb.min);
+        if min.cmpgt(max).any() {
+            return None;
+        }
+        Some(Self { max, min })
+    }
+
+    /// Expands each face outwards by `margin`.
+    pub fn grow(&mut self, margin: f32) {
+        self.max += margin;
+        self.min -= margin;
+    }
+
     pub fn offset(&mut self, offset: Vec3) {
         self.max += offset;
         self.min += offset;
     }
 
+    /// Distances along the ray to where it enters and exits the box, or `None` if it misses.
+    /// The enter distance is negative when `origin` is inside the box.
+    pub fn ray_intersection(&self, origin: Vec3, direction: Vec3) -> Option<(f32, f32)> {
+        // slab method. dividing by zero components gives infinities which still compare correctly
+        let inverse_direction = direction.recip();
+        let t_min = (self.min - origin) * inverse_direction;
+        let t_max = (self.max - origin) * inverse_direction;
+        let enter_dist = t_min.min(t_max).max_element();
+        let exit_dist = t_min.max(t_max).min_element();
+        if enter_dist > exit_dist || exit_dist < 0. {
+            return None;
+        }
+        Some((enter_dist, exit_dist))
+    }
+
     /// Counter-clockwise front face
     pub fn vertices(&self, object_id: ObjectId) -> [BoundingBoxVertex; AABB_VERTEX_COUNT] {
         // note that vertex generation happens far less often than other operations (e.g. union)
@@ -112,3 +189,77 @@ impl Aabb {
         ]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use std::f32::consts::FRAC_PI_4;
+
+    fn approx_eq(a: Vec3, b: Vec3) -> bool {
+        (a - b).abs().max_element() < 1e-5
+    }
+
+    #[test]
+    fn rotated_box_45_degrees() {
+        let transform = Mat3::from_rotation_z(FRAC_PI_4);
+        let half_extents = box_half_extents(Vec3::splat(0.5), transform);
+        let expected_half_xy = 0.5 * std::f32::consts::SQRT_2;
+        assert!(approx_eq(
+            half_extents,
+            Vec3::new(expected_half_xy, expected_half_xy, 0.5)
+        ));
+    }
+
+    #[test]
+    fn disc_is_flat() {
+        let half_extents = disc_half_extents(2., Mat3::IDENTITY);
+        assert!(approx_eq(half_extents, Vec3::new(2., 2., 0.)));
+    }
+
+    #[test]
+    fn scaled_sphere() {
+        let transform = Mat3::from_diagonal(Vec3::new(1., 2., 3.));
+        let half_extents = sphere_half_extents(1., transform);
+        assert!(approx_eq(half_extents, Vec3::new(1., 2., 3.)));
+    }
+
+    #[test]
+    fn intersection_disjoint() {
+        let a = Aabb::new(Vec3::ZERO, Vec3::ONE);
+        let b = Aabb::new(Vec3::new(3., 0., 0.), Vec3::ONE);
+        assert!(a.intersection(&b).is_none());
+    }
+
+    #[test]
+    fn intersection_overlapping() {
+        let a = Aabb::new(Vec3::ZERO, Vec3::splat(2.));
+        let b = Aabb::new(Vec3::ONE, Vec3::splat(2.));
+        let c = a.intersection(&b).unwrap();
+        assert_eq!(c.min, b.min);
+        assert_eq!(c.max, a.max);
+    }
+
+    #[test]
+    fn ray_intersection() {
+        let aabb = Aabb {
+            max: Vec3::ONE,
+            min: -Vec3::ONE,
+        };
+        let (enter_dist, exit_dist) = aabb
+            .ray_intersection(Vec3::new(-3., 0.5, 0.), Vec3::X)
+            .unwrap();
+        assert!((enter_dist - 2.).abs() < 1e-5);
+        assert!((exit_dist - 4.).abs() < 1e-5);
+
+        let (enter_dist, _) = aabb.ray_intersection(Vec3::ZERO, Vec3::Y).unwrap();
+        assert!(enter_dist < 0.);
+
+        assert!(aabb
+            .ray_intersection(Vec3::new(-3., 2., 0.), Vec3::X)
+            .is_none());
+        assert!(aabb
+            .ray_intersection(Vec3::new(3., 0., 0.), Vec3::X)
+            .is_none());
+    }
+}
@@ -0,0 +1,138 @@
+use super::object::{
+    object::{Object, ObjectId},
+    primitive_op::PrimitiveOp,
+};
+use serde::{Deserialize, Serialize};
+
+/// Objects or primitive ops copied to the system clipboard. Stored as JSON so that they can be
+/// pasted into another instance, or inspected and edited as text.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ClipboardContents {
+    /// An object and its descendants paired with their ids at the time of copying, see
+    /// [`ObjectCollection::push_objects`](super::object::object_collection::ObjectCollection::push_objects).
+    Objects(Vec<(ObjectId, Object)>),
+    PrimitiveOps(Vec<PrimitiveOp>),
+}
+
+impl ClipboardContents {
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Fails if the text wasn't copied from goshenite.
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+}
+
+// ~~ Tests ~~
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::{
+        object::{object_collection::ObjectCollection, operation::Operation},
+        primitives::{primitive_transform::PrimitiveTransform, sphere::Sphere},
+    };
+    use glam::Vec3;
+
+    /// Object with a sphere of radius 0.5 at its origin
+    fn new_sphere_object(name: &str, origin: Vec3) -> Object {
+        let mut object = Object::new(name.to_string(), origin);
+        object
+            .push_primitive_op(
+                Sphere::new(0.5).into(),
+                PrimitiveTransform::DEFAULT,
+                Operation::Union,
+                0.,
+                Vec3::ONE,
+                0.,
+            )
+            .unwrap();
+        object
+    }
+
+    #[test]
+    fn copy_objects_round_trips_json() {
+        let mut object_collection = ObjectCollection::new();
+        let parent_id = object_collection
+            .push_object(new_sphere_object("parent", Vec3::new(1., 0., 0.)))
+            .unwrap();
+        let child_id = object_collection
+            .push_object(new_sphere_object("child", Vec3::new(1., 1., 0.)))
+            .unwrap();
+        object_collection
+            .set_object_parent(child_id, Some(parent_id))
+            .unwrap();
+
+        let contents =
+            ClipboardContents::Objects(object_collection.object_tree(parent_id).unwrap());
+        let json = contents.to_json().unwrap();
+        // (the cached parent transforms aren't copied)
+        let pasted_contents = ClipboardContents::from_json(&json).unwrap();
+        assert_eq!(pasted_contents.to_json().unwrap(), json);
+        assert!(ClipboardContents::from_json("not goshenite").is_err());
+    }
+
+    #[test]
+    fn paste_objects_keeps_hierarchy() {
+        let mut object_collection = ObjectCollection::new();
+        let parent_id = object_collection
+            .push_object(new_sphere_object("parent", Vec3::new(1., 0., 0.)))
+            .unwrap();
+        let child_id = object_collection
+            .push_object(new_sphere_object("child", Vec3::new(1., 1., 0.)))
+            .unwrap();
+        object_collection
+            .set_object_parent(child_id, Some(parent_id))
+            .unwrap();
+
+        // pasting assigns new ids and keeps the hierarchy
+        let objects = object_collection.object_tree(parent_id).unwrap();
+        let pasted_ids = object_collection.push_objects(objects).unwrap();
+        assert_eq!(pasted_ids.len(), 2);
+        assert!(!pasted_ids.contains(&parent_id) && !pasted_ids.contains(&child_id));
+        let pasted_child = object_collection.get_object(pasted_ids[1]).unwrap();
+        assert_eq!(pasted_child.parent(), Some(pasted_ids[0]));
+        assert_eq!(pasted_child.world_transform().origin, Vec3::new(1., 1., 0.));
+    }
+
+    #[test]
+    fn duplicate_object_keeps_parent() {
+        let mut object_collection = ObjectCollection::new();
+        let parent_id = object_collection
+            .push_object(new_sphere_object("parent", Vec3::new(1., 0., 0.)))
+            .unwrap();
+        let child_id = object_collection
+            .push_object(new_sphere_object("child", Vec3::new(1., 1., 0.)))
+            .unwrap();
+        object_collection
+            .set_object_parent(child_id, Some(parent_id))
+            .unwrap();
+
+        let duplicate_id = object_collection.duplicate_object(child_id).unwrap();
+        let duplicate = object_collection.get_object(duplicate_id).unwrap();
+        assert_eq!(duplicate.parent(), Some(parent_id));
+        assert_eq!(object_collection.children(parent_id).len(), 2);
+    }
+
+    #[test]
+    fn duplicate_primitive_op_gets_new_id() {
+        let mut object_collection = ObjectCollection::new();
+        let object_id = object_collection
+            .push_object(new_sphere_object("object", Vec3::ZERO))
+            .unwrap();
+        let primitive_op_id = object_collection
+            .get_object(object_id)
+            .unwrap()
+            .primitive_ops[0]
+            .id();
+
+        let duplicate_op_id = object_collection
+            .duplicate_primitive_op_in_object(object_id, primitive_op_id)
+            .unwrap();
+        let object = object_collection.get_object(object_id).unwrap();
+        assert_eq!(object.primitive_ops[1].id(), duplicate_op_id);
+        assert_ne!(primitive_op_id, duplicate_op_id);
+    }
+}
@@ -1,10 +1,20 @@
 use crate::renderer::config_renderer::RenderOptions;
 
 use super::{
-    object::{object::ObjectId, operation::Operation, primitive_op::PrimitiveOpId},
+    group_transform::GroupTransform,
+    object::{
+        domain_modifier::DomainModifier,
+        layer::LayerId,
+        material::{Material, MaterialId},
+        object::ObjectId,
+        operation::Operation,
+        primitive_op::PrimitiveOpId,
+        primitive_op_group::PrimitiveOpGroupId,
+        surface_modifier::SurfaceModifier,
+    },
     primitives::{primitive::Primitive, primitive_transform::PrimitiveTransform},
 };
-use glam::{DVec3, Vec3};
+use glam::{DVec3, Quat, Vec3};
 
 // ~~ Commands ~~
 
@@ -28,17 +38,106 @@ pub enum Command {
     // ~~ Object ~~
     SelectObject(ObjectId),
     DeselectObject(),
+    /// Children of the removed object are moved to its parent
     RemoveObject(ObjectId),
+    RemoveObjectAndChildren(ObjectId),
+    /// Removes every selected object
     RemoveSelectedObject(),
     CreateAndSelectNewDefaultObject(),
+    /// Copies the object and its children and selects the copy
+    DuplicateObject(ObjectId),
+    /// Creates an object sharing the primitive ops of the object and selects it
+    CreateInstance(ObjectId),
+    /// Gives an instance its own copy of the primitive ops of its definition
+    MakeInstanceUnique(ObjectId),
     SetObjectOrigin {
         object_id: ObjectId,
         origin: Vec3,
     },
+    SetObjectRotation {
+        object_id: ObjectId,
+        rotation: Quat,
+    },
+    SetObjectScale {
+        object_id: ObjectId,
+        scale: f32,
+    },
     SetObjectName {
         object_id: ObjectId,
         new_name: String,
     },
+    /// `None` moves the object to the root. The object keeps its place in the world.
+    SetObjectParent {
+        object_id: ObjectId,
+        parent_id: Option<ObjectId>,
+    },
+    SetObjectVisible {
+        object_id: ObjectId,
+        visible: bool,
+    },
+    /// Locked objects reject commands that would modify them, including removing their parent.
+    /// Layer and material commands still apply: removing a layer or material clears it from
+    /// locked objects and editing a material changes how they look.
+    SetObjectLocked {
+        object_id: ObjectId,
+        locked: bool,
+    },
+    /// Non-selectable objects are ignored when clicked in the viewport
+    SetObjectSelectable {
+        object_id: ObjectId,
+        selectable: bool,
+    },
+    /// `None` removes the object from its layer
+    SetObjectLayer {
+        object_id: ObjectId,
+        layer_id: Option<LayerId>,
+    },
+    SetObjectTags {
+        object_id: ObjectId,
+        tags: Vec<String>,
+    },
+
+    // ~~ Selection ~~
+    /// Adds the object to the selection, or deselects it if it's already selected
+    ToggleObjectSelection(ObjectId),
+    /// Adds the primitive op and its object to the selection, or deselects the primitive op if
+    /// it's already selected
+    TogglePrimitiveOpSelection(TargetPrimitiveOp),
+    /// Selects every selectable object
+    SelectAll(),
+    /// Selects the selectable objects that aren't selected and deselects the rest
+    InvertSelection(),
+    /// Removes the selected primitive ops, or the selected objects if no primitive op is selected
+    RemoveSelected(),
+    /// Moves, rotates and scales the selected primitive ops together, or the selected objects if
+    /// no primitive op is selected
+    TransformSelected(GroupTransform),
+
+    // ~~ Layers ~~
+    CreateLayer(),
+    /// Objects in the layer are left without a layer
+    RemoveLayer(LayerId),
+    SetLayerName {
+        layer_id: LayerId,
+        new_name: String,
+    },
+    SetLayerVisible {
+        layer_id: LayerId,
+        visible: bool,
+    },
+    SetLayerSelectable {
+        layer_id: LayerId,
+        selectable: bool,
+    },
+
+    // ~~ Materials ~~
+    CreateMaterial(),
+    /// Primitive ops using the material keep its albedo and specular
+    RemoveMaterial(MaterialId),
+    SetMaterial {
+        material_id: MaterialId,
+        new_material: Material,
+    },
 
     // ~~ Primtive Op: Selection ~~
     SelectPrimitiveOp(TargetPrimitiveOp),
@@ -47,6 +146,10 @@ pub enum Command {
     // ~~ Primitive Op: Remove ~~
     RemovePrimitiveOp(TargetPrimitiveOp),
 
+    // ~~ Primitive Op: Duplicate ~~
+    /// Inserts a copy after the primitive op and selects it
+    DuplicatePrimitiveOp(TargetPrimitiveOp),
+
     // ~~ Primitive Op: Push ~~
     PushPrimitiveOp {
         object_id: ObjectId,
@@ -76,6 +179,8 @@ pub enum Command {
         new_blend: f32,
         new_albedo: Vec3,
         new_specular: f32,
+        new_modifiers: Vec<DomainModifier>,
+        new_surface_modifiers: Vec<SurfaceModifier>,
     },
     SetPrimitive {
         target_primitive_op: TargetPrimitiveOp,
@@ -101,11 +206,70 @@ pub enum Command {
         target_primitive_op: TargetPrimitiveOp,
         new_specular: f32,
     },
+    /// `material_override` keeps the primitive op albedo and specular while `material_id` is set
+    SetPrimitiveOpMaterial {
+        target_primitive_op: TargetPrimitiveOp,
+        material_id: Option<MaterialId>,
+        material_override: bool,
+    },
+    SetSurfaceModifiers {
+        target_primitive_op: TargetPrimitiveOp,
+        new_surface_modifiers: Vec<SurfaceModifier>,
+    },
+    SetDomainModifiers {
+        target_primitive_op: TargetPrimitiveOp,
+        new_modifiers: Vec<DomainModifier>,
+    },
     ShiftPrimitiveOps {
         object_id: ObjectId,
         source_index: usize,
         target_index: usize,
     },
+    /// Moves a primitive op into `group_id` (`None` for the object root) before
+    /// `before_primitive_op_id` (`None` for the end of the group).
+    MovePrimitiveOp {
+        object_id: ObjectId,
+        primitive_op_id: PrimitiveOpId,
+        group_id: Option<PrimitiveOpGroupId>,
+        before_primitive_op_id: Option<PrimitiveOpId>,
+    },
+
+    // ~~ Primitive Op Groups ~~
+    PushPrimitiveOpGroup {
+        object_id: ObjectId,
+        parent_group_id: Option<PrimitiveOpGroupId>,
+    },
+    RemovePrimitiveOpGroup {
+        object_id: ObjectId,
+        group_id: PrimitiveOpGroupId,
+    },
+    SetPrimitiveOpGroup {
+        object_id: ObjectId,
+        group_id: PrimitiveOpGroupId,
+        new_name: Option<String>,
+        new_operation: Option<Operation>,
+        new_blend: Option<f32>,
+    },
+    /// Moves a group into `parent_group_id` (`None` for the object root) before
+    /// `before_primitive_op_id` (`None` for the end of the parent).
+    MovePrimitiveOpGroup {
+        object_id: ObjectId,
+        group_id: PrimitiveOpGroupId,
+        parent_group_id: Option<PrimitiveOpGroupId>,
+        before_primitive_op_id: Option<PrimitiveOpId>,
+    },
+
+    // ~~ Clipboard ~~
+    /// Copies the object and its children to the system clipboard
+    CopyObject(ObjectId),
+    CopyPrimitiveOp(TargetPrimitiveOp),
+    /// Copies the active primitive op, or the active object if no primitive op is selected
+    CopySelected(),
+    /// Pastes objects to the root of the scene, or primitive ops into the active object
+    Paste(),
+    /// Duplicates the selected primitive ops, or the selected objects if no primitive op is
+    /// selected, and selects the copies
+    DuplicateSelected(),
 
     // ~~ Internal ~~
     Validate(ValidationCommand),
@@ -113,7 +277,8 @@ pub enum Command {
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ValidationCommand {
-    SelectedObject(),
+    /// Deselects objects and primitive ops that no longer exist
+    Selection(),
 }
 
 impl From<ValidationCommand> for Command {
@@ -173,13 +338,20 @@ impl CommandWithSource {
 #[derive(Debug)]
 pub enum CommandError {
     InvalidObjectId(ObjectId),
+    InvalidLayerId(LayerId),
+    InvalidMaterialId(MaterialId),
     InvalidPrimitiveOpId(ObjectId, PrimitiveOpId),
+    ObjectLocked(ObjectId),
 }
 
 impl std::fmt::Display for CommandError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::InvalidObjectId(object_id) => write!(f, "invalid object id {}", object_id),
+            Self::InvalidLayerId(layer_id) => write!(f, "invalid layer id {}", layer_id),
+            Self::InvalidMaterialId(material_id) => {
+                write!(f, "invalid material id {}", material_id)
+            }
             Self::InvalidPrimitiveOpId(object_id, primitive_op_id) => {
                 write!(
                     f,
@@ -187,6 +359,7 @@ impl std::fmt::Display for CommandError {
                     primitive_op_id, object_id
                 )
             }
+            Self::ObjectLocked(object_id) => write!(f, "object id {} is locked", object_id),
         }
     }
 }
@@ -1,19 +1,38 @@
-use glam::Vec3;
+use crate::helper::angle::Angle;
+use glam::{Vec2, Vec3};
 
 pub const JOIN_THREAD_WAIT_TIMEOUT_SECONDS: f64 = 2.;
 
 pub const DEFAULT_RADIUS: f32 = 0.5;
+pub const DEFAULT_MINOR_RADIUS: f32 = 0.15;
 pub const DEFAULT_DIMENSIONS: Vec3 = Vec3::ONE;
+pub const DEFAULT_HEIGHT: f32 = 1.;
+pub const DEFAULT_PROFILE_DIMENSIONS: Vec2 = Vec2::ONE;
+pub const DEFAULT_CORNER_RADIUS: f32 = 0.1;
+pub const DEFAULT_THICKNESS: f32 = 0.1;
+pub const DEFAULT_ARC_ANGLE: Angle = Angle::Degrees(270.);
 pub const DEFAULT_ALBEDO: Vec3 = Vec3::new(0.9, 0.8, 0.2);
 pub const DEFAULT_SPECULAR: f32 = 0.5;
 
 pub mod primitive_names {
     pub const SPHERE: &str = "Sphere";
     pub const CUBE: &str = "Cube";
+    pub const TORUS: &str = "Torus";
+    pub const CAPPED_TORUS: &str = "Capped Torus";
+    pub const BOX_FRAME: &str = "Box Frame";
+    pub const CYLINDER: &str = "Cylinder";
+    pub const CONE: &str = "Cone";
+    pub const CAPSULE: &str = "Capsule";
+    pub const EXTRUSION: &str = "Extrusion";
+    pub const REVOLUTION: &str = "Revolution";
+    pub const TUBE: &str = "Tube";
     pub const UBER_PRIMITIVE: &str = "Uber Primitive";
 }
 
 pub const AABB_EDGE: f32 = 0.05;
+/// Infinitely repeated primitives are only bounded (and therefore rendered) up to this distance
+/// from the original.
+pub const INFINITE_REPETITION_EXTENT: f32 = 100.;
 
 pub const DEFAULT_ORIGIN: Vec3 = Vec3::ZERO;
 
@@ -1,15 +1,13 @@
 use super::{
-    commands::{CommandWithSource, TargetPrimitiveOp},
+    commands::{Command, CommandWithSource, TargetPrimitiveOp},
     config_engine,
     main_thread::MainThreadChannels,
-    object::{
-        object::ObjectId, object_collection::ObjectCollection, operation::Operation,
-        primitive_op::PrimitiveOpId,
-    },
+    object::{object::ObjectId, object_collection::ObjectCollection, operation::Operation},
     primitives::{
         cube::Cube, primitive::Primitive, primitive_transform::PrimitiveTransform, sphere::Sphere,
     },
     render_thread::{start_render_thread, RenderThreadChannels, RenderThreadCommand},
+    selection::Selection,
 };
 use crate::{
     config,
@@ -64,10 +62,10 @@ pub struct EngineController {
     object_collection: ObjectCollection, // note: some engine code written on the assumtion that there is only one object collection
     main_thread_frame_number: u64,
     pending_commands: VecDeque<CommandWithSource>,
-    selected_object_id: Option<ObjectId>,
-    selected_primitive_op_id: Option<PrimitiveOpId>,
+    selection: Selection,
     render_options: RenderOptions,
     keyboard_modifier_states: KeyboardModifierStates,
+    click_modifier_states: KeyboardModifierStates, // held when the element id request was submitted
 
     // controllers
     cursor: Cursor,
@@ -126,10 +124,10 @@ impl EngineController {
             object_collection,
             main_thread_frame_number: 0,
             pending_commands: VecDeque::new(),
-            selected_object_id: None,
-            selected_primitive_op_id: None,
+            selection: Selection::new(),
             render_options: RenderOptions::default(),
             keyboard_modifier_states: KeyboardModifierStates::default(),
+            click_modifier_states: KeyboardModifierStates::default(),
 
             cursor,
             camera,
@@ -269,8 +267,7 @@ impl EngineController {
             &self.object_collection,
             &self.window,
             self.camera,
-            self.selected_object_id,
-            self.selected_primitive_op_id,
+            &self.selection,
             self.render_options,
         );
         let commands_from_gui = anyhow_unwrap(update_gui_res, "update gui");
@@ -357,6 +354,30 @@ impl EngineController {
                     self.gui.set_command_palette_visability(false);
                 }
             }
+            KeyCode::KeyC | KeyCode::KeyV | KeyCode::KeyD | KeyCode::KeyA | KeyCode::KeyI => {
+                if key_event.state != ElementState::Pressed
+                    || !self.keyboard_modifier_states.control
+                {
+                    return;
+                }
+                let command = match key_code {
+                    KeyCode::KeyC => Command::CopySelected(),
+                    KeyCode::KeyV => Command::Paste(),
+                    KeyCode::KeyD => Command::DuplicateSelected(),
+                    KeyCode::KeyA => Command::SelectAll(),
+                    _ => Command::InvertSelection(),
+                };
+                self.pending_commands
+                    .push_back(CommandWithSource::new_from_shortcut(command));
+            }
+            KeyCode::Delete => {
+                if key_event.state == ElementState::Pressed {
+                    self.pending_commands
+                        .push_back(CommandWithSource::new_from_shortcut(
+                            Command::RemoveSelected(),
+                        ));
+                }
+            }
             _ => (),
         }
     }
@@ -390,6 +411,9 @@ impl EngineController {
                 .render_thread_channels
                 .request_element_id_at_screen_coordinate(cursor_screen_coordinates);
             check_channel_updater_result(thread_send_res)?;
+
+            // the response arrives frames later, by which time the modifiers may be released
+            self.click_modifier_states = self.keyboard_modifier_states;
         }
         Ok(())
     }
@@ -400,39 +424,54 @@ impl EngineController {
             .receive_element_id_at_screen_coordinate()
         {
             debug!("element clicked = {:?}", element_at_point);
+            let shift_held = self.click_modifier_states.shift;
             match element_at_point {
-                ElementAtPoint::Background => self.background_clicked(),
+                ElementAtPoint::Background => self.background_clicked(shift_held),
                 ElementAtPoint::Object {
                     object_id,
                     primitive_op_index,
-                } => self.object_clicked(object_id, Some(primitive_op_index)),
-                ElementAtPoint::BlendArea { object_id } => self.object_clicked(object_id, None),
+                } => self.object_clicked(object_id, Some(primitive_op_index), shift_held),
+                ElementAtPoint::BlendArea { object_id } => {
+                    self.object_clicked(object_id, None, shift_held)
+                }
             }
         }
     }
 
-    fn background_clicked(&mut self) {
-        self.deselect_primitive_op();
+    fn background_clicked(&mut self, shift_held: bool) {
+        // shift-clicking the background keeps the selection
+        if !shift_held {
+            self.deselect_primitive_op();
+        }
         self.camera.unset_lock_on_target();
     }
 
-    fn object_clicked(&mut self, object_id: ObjectId, primitive_op_index: Option<usize>) {
+    /// Shift-click adds to or removes from the selection
+    fn object_clicked(
+        &mut self,
+        object_id: ObjectId,
+        primitive_op_index: Option<usize>,
+        shift_held: bool,
+    ) {
+        // ignore objects that are non-selectable or in a non-selectable layer
+        if !self.object_collection.is_object_selectable(object_id) {
+            return;
+        }
+
         if let Some(some_primitive_op_index) = primitive_op_index {
             let target_primitive_op = TargetPrimitiveOp::Index(object_id, some_primitive_op_index);
-            self.select_primitive_op_and_object(target_primitive_op, None)
+            if shift_held {
+                self.toggle_primitive_op_selection(target_primitive_op, None);
+            } else {
+                self.select_primitive_op_and_object(target_primitive_op, None);
+            }
+        } else if shift_held {
+            self.toggle_object_selection(object_id, None);
         } else {
             self.select_object(object_id, None);
         }
     }
 
-    fn is_object_id_selected(&self, compare_object_id: ObjectId) -> bool {
-        if let Some(some_selected_object_id) = self.selected_object_id {
-            some_selected_object_id == compare_object_id
-        } else {
-            false
-        }
-    }
-
     fn shut_down(&mut self) {
         self.request_render_thread_quit();
         self.wait_for_render_thread_quit();
@@ -1,23 +1,35 @@
 use super::EngineController;
 use crate::{
     engine::{
-        commands::{Command, CommandWithSource, TargetPrimitiveOp, ValidationCommand},
+        aabb::Aabb,
+        clipboard::ClipboardContents,
+        commands::{
+            Command, CommandError, CommandWithSource, TargetPrimitiveOp, ValidationCommand,
+        },
+        group_transform::{GroupTransform, Pivot},
         object::{
-            object::{Object, ObjectId},
+            domain_modifier::DomainModifier,
+            layer::LayerId,
+            material::{Material, MaterialId},
+            object::ObjectId,
+            object_collection::ObjectCollection,
+            object_transform::ObjectTransform,
             operation::Operation,
-            primitive_op::{PrimitiveOp, PrimitiveOpId},
+            primitive_op::PrimitiveOpId,
+            primitive_op_group::PrimitiveOpGroupId,
+            surface_modifier::SurfaceModifier,
         },
         primitives::{primitive::Primitive, primitive_transform::PrimitiveTransform},
         save_states::{load_objects, load_state_camera, save_all_objects, save_state_camera},
+        selection::Selection,
     },
     helper::{
         list::choose_closest_valid_index, more_errors::CollectionError,
         unique_id_gen::UniqueIdError,
     },
     renderer::config_renderer::RenderOptions,
-    user_interface::gui::Gui,
 };
-use glam::Vec3;
+use glam::{Quat, Vec3};
 #[allow(unused_imports)]
 use log::{debug, error, info, trace, warn};
 
@@ -35,6 +47,11 @@ impl EngineController {
     }
 
     pub(super) fn execute_command(&mut self, command: Command) {
+        if let Err(e) = check_command_unlocked(&self.object_collection, &self.selection, &command) {
+            command_failed_warn(command, &e.to_string());
+            return;
+        }
+
         match command {
             // ~~ Renderer ~~
             Command::SetRenderOptions(new_render_options) => {
@@ -63,17 +80,97 @@ impl EngineController {
             }
             Command::DeselectObject() => self.deselect_object(),
             Command::RemoveObject(object_id) => self.remove_object_via_command(object_id, command),
+            Command::RemoveObjectAndChildren(object_id) => {
+                self.remove_object_and_children_via_command(object_id, command)
+            }
             Command::RemoveSelectedObject() => self.remove_selected_object_via_command(command),
             Command::CreateAndSelectNewDefaultObject() => {
                 self.create_and_select_new_default_object_via_command(command)
             }
+            Command::DuplicateObject(object_id) => {
+                self.duplicate_object_via_command(object_id, command)
+            }
+            Command::CreateInstance(object_id) => {
+                self.create_instance_via_command(object_id, command)
+            }
+            Command::MakeInstanceUnique(object_id) => {
+                self.make_instance_unique_via_command(object_id, command)
+            }
             Command::SetObjectOrigin { object_id, origin } => {
                 self.set_object_origin_via_command(object_id, origin, command)
             }
+            Command::SetObjectRotation {
+                object_id,
+                rotation,
+            } => self.set_object_rotation_via_command(object_id, rotation, command),
+            Command::SetObjectScale { object_id, scale } => {
+                self.set_object_scale_via_command(object_id, scale, command)
+            }
             Command::SetObjectName {
                 object_id,
                 ref new_name,
             } => self.set_object_name_via_command(object_id, new_name.clone(), command),
+            Command::SetObjectParent {
+                object_id,
+                parent_id,
+            } => self.set_object_parent_via_command(object_id, parent_id, command),
+            Command::SetObjectVisible { object_id, visible } => {
+                self.set_object_visible_via_command(object_id, visible, command)
+            }
+            Command::SetObjectLocked { object_id, locked } => {
+                self.set_object_locked_via_command(object_id, locked, command)
+            }
+            Command::SetObjectSelectable {
+                object_id,
+                selectable,
+            } => self.set_object_selectable_via_command(object_id, selectable, command),
+            Command::SetObjectLayer {
+                object_id,
+                layer_id,
+            } => self.set_object_layer_via_command(object_id, layer_id, command),
+            Command::SetObjectTags {
+                object_id,
+                ref tags,
+            } => self.set_object_tags_via_command(object_id, tags.clone(), command),
+
+            // ~~ Selection ~~
+            Command::ToggleObjectSelection(object_id) => {
+                self.toggle_object_selection(object_id, Some(command))
+            }
+            Command::TogglePrimitiveOpSelection(target_primitive_op) => {
+                self.toggle_primitive_op_selection(target_primitive_op, Some(command))
+            }
+            Command::SelectAll() => self.select_all(),
+            Command::InvertSelection() => self.invert_selection(),
+            Command::RemoveSelected() => self.remove_selected_via_command(command),
+            Command::TransformSelected(group_transform) => {
+                self.transform_selected_via_command(group_transform, command)
+            }
+
+            // ~~ Layers ~~
+            Command::CreateLayer() => self.create_layer_via_command(command),
+            Command::RemoveLayer(layer_id) => self.remove_layer_via_command(layer_id, command),
+            Command::SetLayerName {
+                layer_id,
+                ref new_name,
+            } => self.set_layer_name_via_command(layer_id, new_name.clone(), command),
+            Command::SetLayerVisible { layer_id, visible } => {
+                self.set_layer_visible_via_command(layer_id, visible, command)
+            }
+            Command::SetLayerSelectable {
+                layer_id,
+                selectable,
+            } => self.set_layer_selectable_via_command(layer_id, selectable, command),
+
+            // ~~ Materials ~~
+            Command::CreateMaterial() => self.create_material_via_command(command),
+            Command::RemoveMaterial(material_id) => {
+                self.remove_material_via_command(material_id, command)
+            }
+            Command::SetMaterial {
+                material_id,
+                ref new_material,
+            } => self.set_material_via_command(material_id, new_material.clone(), command),
 
             // ~~ Primtive Op: Selection ~~
             Command::SelectPrimitiveOp(target_primitive_op) => {
@@ -86,10 +183,15 @@ impl EngineController {
                 self.remove_primitive_op(target_primitive_op, Some(command))
             }
 
+            // ~~ Primitive Op: Duplicate ~~
+            Command::DuplicatePrimitiveOp(target_primitive_op) => {
+                self.duplicate_primitive_op_via_command(target_primitive_op, command)
+            }
+
             // ~~ Primitive Op: Push ~~
             Command::PushPrimitiveOp {
                 object_id,
-                primitive,
+                ref primitive,
                 transform,
                 operation,
                 blend,
@@ -97,46 +199,66 @@ impl EngineController {
                 specular,
             } => {
                 _ = self.push_op_via_command(
-                    object_id, primitive, transform, operation, blend, albedo, specular, command,
+                    object_id,
+                    primitive.clone(),
+                    transform,
+                    operation,
+                    blend,
+                    albedo,
+                    specular,
+                    command,
                 )
             }
             Command::PushPrimitiveOpAndSelect {
                 object_id,
-                primitive,
+                ref primitive,
                 transform,
                 operation,
                 blend,
                 albedo,
                 specular,
             } => self.push_op_and_select_via_command(
-                object_id, primitive, transform, operation, blend, albedo, specular, command,
+                object_id,
+                primitive.clone(),
+                transform,
+                operation,
+                blend,
+                albedo,
+                specular,
+                command,
             ),
 
             // ~~ Primitive Op: Modify ~~
             Command::SetPrimitiveOp {
                 target_primitive_op,
-                new_primitive,
+                ref new_primitive,
                 new_transform,
                 new_operation,
                 new_blend,
                 new_albedo,
                 new_specular,
+                ref new_modifiers,
+                ref new_surface_modifiers,
             } => self.set_primitive_op(
                 target_primitive_op,
-                Some(new_primitive),
+                Some(new_primitive.clone()),
                 Some(new_transform),
                 Some(new_operation),
                 Some(new_blend),
                 Some(new_albedo),
                 Some(new_specular),
+                Some(new_modifiers.clone()),
+                Some(new_surface_modifiers.clone()),
                 Some(command),
             ),
             Command::SetPrimitive {
                 target_primitive_op,
-                new_primitive,
+                ref new_primitive,
             } => self.set_primitive_op(
                 target_primitive_op,
-                Some(new_primitive),
+                Some(new_primitive.clone()),
+                None,
+                None,
                 None,
                 None,
                 None,
@@ -155,6 +277,8 @@ impl EngineController {
                 None,
                 None,
                 None,
+                None,
+                None,
                 Some(command),
             ),
             Command::SetOperation {
@@ -168,6 +292,8 @@ impl EngineController {
                 None,
                 None,
                 None,
+                None,
+                None,
                 Some(command),
             ),
             Command::SetBlend {
@@ -181,6 +307,8 @@ impl EngineController {
                 Some(new_blend),
                 None,
                 None,
+                None,
+                None,
                 Some(command),
             ),
             Command::SetAlbedo {
@@ -194,6 +322,8 @@ impl EngineController {
                 None,
                 Some(new_albedo),
                 None,
+                None,
+                None,
                 Some(command),
             ),
             Command::SetSpecular {
@@ -207,6 +337,48 @@ impl EngineController {
                 None,
                 None,
                 Some(new_specular),
+                None,
+                None,
+                Some(command),
+            ),
+            Command::SetPrimitiveOpMaterial {
+                target_primitive_op,
+                material_id,
+                material_override,
+            } => self.set_primitive_op_material_via_command(
+                target_primitive_op,
+                material_id,
+                material_override,
+                command,
+            ),
+            Command::SetSurfaceModifiers {
+                target_primitive_op,
+                ref new_surface_modifiers,
+            } => self.set_primitive_op(
+                target_primitive_op,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                Some(new_surface_modifiers.clone()),
+                Some(command),
+            ),
+            Command::SetDomainModifiers {
+                target_primitive_op,
+                ref new_modifiers,
+            } => self.set_primitive_op(
+                target_primitive_op,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                Some(new_modifiers.clone()),
+                None,
                 Some(command),
             ),
             Command::ShiftPrimitiveOps {
@@ -221,6 +393,63 @@ impl EngineController {
                     command,
                 );
             }
+            Command::MovePrimitiveOp {
+                object_id,
+                primitive_op_id,
+                group_id,
+                before_primitive_op_id,
+            } => self.move_primitive_op_via_command(
+                object_id,
+                primitive_op_id,
+                group_id,
+                before_primitive_op_id,
+                command,
+            ),
+
+            // ~~ Primitive Op Groups ~~
+            Command::PushPrimitiveOpGroup {
+                object_id,
+                parent_group_id,
+            } => self.push_group_via_command(object_id, parent_group_id, command),
+            Command::RemovePrimitiveOpGroup {
+                object_id,
+                group_id,
+            } => self.remove_group_via_command(object_id, group_id, command),
+            Command::SetPrimitiveOpGroup {
+                object_id,
+                group_id,
+                ref new_name,
+                new_operation,
+                new_blend,
+            } => self.set_group_via_command(
+                object_id,
+                group_id,
+                new_name.clone(),
+                new_operation,
+                new_blend,
+                command,
+            ),
+            Command::MovePrimitiveOpGroup {
+                object_id,
+                group_id,
+                parent_group_id,
+                before_primitive_op_id,
+            } => self.move_group_via_command(
+                object_id,
+                group_id,
+                parent_group_id,
+                before_primitive_op_id,
+                command,
+            ),
+
+            // ~~ Clipboard ~~
+            Command::CopyObject(object_id) => self.copy_object_via_command(object_id, command),
+            Command::CopyPrimitiveOp(target_primitive_op) => {
+                self.copy_primitive_op_via_command(target_primitive_op, command)
+            }
+            Command::CopySelected() => self.copy_selected_via_command(command),
+            Command::Paste() => self.paste_via_command(command),
+            Command::DuplicateSelected() => self.duplicate_selected_via_command(command),
 
             Command::Validate(v_command) => self.execute_validation_command(v_command),
         }
@@ -269,8 +498,8 @@ impl EngineController {
 
     fn load_objects_via_command(&mut self, command: Command) {
         let load_state_res = load_objects();
-        let loaded_objects = match load_state_res {
-            Ok(o) => o,
+        let loaded_scene = match load_state_res {
+            Ok(scene) => scene,
             Err(e) => {
                 let failed_because = format!("error while loading saved objects: {}", e);
                 command_failed_warn(command, &failed_because);
@@ -278,7 +507,11 @@ impl EngineController {
             }
         };
 
-        let insert_objects_res = self.object_collection.push_objects(loaded_objects);
+        let insert_objects_res = self.object_collection.push_scene(
+            loaded_scene.layers,
+            loaded_scene.materials,
+            loaded_scene.objects,
+        );
         if let Err(e) = insert_objects_res {
             let failed_because = format!("error while inserting loaded objects: {}", e);
             command_failed_warn(command, &failed_because);
@@ -298,17 +531,13 @@ impl EngineController {
         };
 
         self.camera
-            .set_lock_on_target_object(target_object_id, object.origin);
+            .set_lock_on_target_object(target_object_id, object.world_transform().origin);
     }
 
     // ~~ Object ~~
 
     fn deselect_object(&mut self) {
-        if self.selected_object_id.is_some() {
-            self.gui.selected_object_changed();
-        }
-        self.selected_object_id = None;
-        self.selected_primitive_op_id = None;
+        self.selection.clear();
     }
 
     pub(super) fn select_object(
@@ -330,21 +559,8 @@ impl EngineController {
     /// Doesn't check validity of `object_id`. Ideally we'd pass a reference to the object here
     /// to account for this, but the borrow checker doesn't like that...
     fn select_object_unchecked(&mut self, object_id_to_select: ObjectId) {
-        let mut selected_object_changed = true;
-        if let Some(previously_selected_object_id) = self.selected_object_id {
-            if previously_selected_object_id == object_id_to_select {
-                selected_object_changed = false;
-            }
-        }
-
-        self.selected_object_id = Some(object_id_to_select);
-
-        if selected_object_changed {
-            // if a different object is already selected, deselect the primitive op because it will
-            // no longer be valid
-            self.deselect_primitive_op();
-            self.gui.selected_object_changed();
-        }
+        // primitive ops of other objects are deselected
+        self.update_selection(|selection| selection.select_object(object_id_to_select));
     }
 
     fn remove_object_via_command(&mut self, object_id_to_remove: ObjectId, command: Command) {
@@ -353,24 +569,43 @@ impl EngineController {
             failure_warn_invalid_object_id(object_id_to_remove, Some(command));
         }
 
-        if let Some(previously_selected_object_id) = self.selected_object_id {
-            if previously_selected_object_id == object_id_to_remove {
-                self.deselect_object();
+        self.selection.remove_object(object_id_to_remove);
+    }
+
+    fn remove_object_and_children_via_command(
+        &mut self,
+        object_id_to_remove: ObjectId,
+        command: Command,
+    ) {
+        let removed_object_ids = match self
+            .object_collection
+            .remove_object_and_descendants(object_id_to_remove)
+        {
+            Ok(removed_object_ids) => removed_object_ids,
+            Err(_e) => {
+                failure_warn_invalid_object_id(object_id_to_remove, Some(command));
+                return;
             }
+        };
+
+        for removed_object_id in removed_object_ids {
+            self.selection.remove_object(removed_object_id);
         }
     }
 
     fn remove_selected_object_via_command(&mut self, command: Command) {
-        if let Some(selected_object_id) = self.selected_object_id {
+        if self.selection.is_empty() {
+            command_failed_warn(command, "no selected object");
+            return;
+        }
+
+        for selected_object_id in self.selection.objects().to_vec() {
             let res = self.object_collection.remove_object(selected_object_id);
             if let Err(_e) = res {
-                command_failed_warn(command, "selected object id invalid");
+                command_failed_warn(command.clone(), "selected object id invalid");
             }
-
-            self.deselect_object();
-        } else {
-            command_failed_warn(command, "no selected object");
         }
+        self.deselect_object();
     }
 
     fn create_and_select_new_default_object_via_command(&mut self, command: Command) {
@@ -387,6 +622,36 @@ impl EngineController {
         self.select_object_unchecked(new_object_id);
     }
 
+    fn duplicate_object_via_command(&mut self, object_id: ObjectId, command: Command) {
+        let duplicate_res = self.object_collection.duplicate_object(object_id);
+        match duplicate_res {
+            Ok(duplicate_id) => self.select_object_unchecked(duplicate_id),
+            Err(e) => {
+                let error_msg = e.to_string();
+                command_failed_warn(command, &error_msg);
+            }
+        }
+    }
+
+    fn create_instance_via_command(&mut self, object_id: ObjectId, command: Command) {
+        let instance_res = self.object_collection.new_instance(object_id);
+        match instance_res {
+            Ok(instance_id) => self.select_object_unchecked(instance_id),
+            Err(e) => {
+                let error_msg = e.to_string();
+                command_failed_warn(command, &error_msg);
+            }
+        }
+    }
+
+    fn make_instance_unique_via_command(&mut self, object_id: ObjectId, command: Command) {
+        let unique_res = self.object_collection.make_instance_unique(object_id);
+        if let Err(e) = unique_res {
+            let error_msg = e.to_string();
+            command_failed_warn(command, &error_msg);
+        }
+    }
+
     fn set_object_origin_via_command(
         &mut self,
         object_id: ObjectId,
@@ -401,6 +666,34 @@ impl EngineController {
         }
     }
 
+    fn set_object_rotation_via_command(
+        &mut self,
+        object_id: ObjectId,
+        new_rotation: Quat,
+        command: Command,
+    ) {
+        let update_res = self
+            .object_collection
+            .set_object_rotation(object_id, new_rotation);
+        if update_res.is_err() {
+            failure_warn_invalid_object_id(object_id, Some(command));
+        }
+    }
+
+    fn set_object_scale_via_command(
+        &mut self,
+        object_id: ObjectId,
+        new_scale: f32,
+        command: Command,
+    ) {
+        let update_res = self
+            .object_collection
+            .set_object_scale(object_id, new_scale);
+        if update_res.is_err() {
+            failure_warn_invalid_object_id(object_id, Some(command));
+        }
+    }
+
     fn set_object_name_via_command(
         &mut self,
         object_id: ObjectId,
@@ -413,117 +706,441 @@ impl EngineController {
         }
     }
 
-    // ~~ Primtive Op: Selection ~~
-
-    pub(super) fn select_primitive_op_and_object(
+    fn set_object_visible_via_command(
         &mut self,
-        target_primitive_op: TargetPrimitiveOp,
-        source_command: Option<Command>,
+        object_id: ObjectId,
+        visible: bool,
+        command: Command,
     ) {
-        let object_id = match target_primitive_op {
-            TargetPrimitiveOp::Id(object_id, _) => object_id,
-            TargetPrimitiveOp::Index(object_id, _) => object_id,
-            TargetPrimitiveOp::Selected => {
-                failure_warn_already_selected(source_command);
-                return;
-            }
-        };
-
-        let Some(object) = self.object_collection.get_object(object_id) else {
-            failure_warn_invalid_object_id(object_id, source_command);
-            return;
-        };
-
-        let primitive_op = match target_primitive_op {
-            TargetPrimitiveOp::Id(_, primitive_op_id) => {
-                let get_res = object.get_primitive_op(primitive_op_id);
-                match get_res {
-                    Some(primitive_op) => primitive_op.clone(),
-                    None => {
-                        failure_warn_invalid_primitive_op_id(
-                            object_id,
-                            primitive_op_id,
-                            source_command,
-                        );
-                        return;
-                    }
-                }
-            }
-            TargetPrimitiveOp::Index(_, primitive_op_index) => {
-                let get_res = object.primitive_ops.get(primitive_op_index);
-                match get_res {
-                    Some(primitive_op) => primitive_op.clone(),
-                    None => {
-                        failure_warn_invalid_primitive_op_index(
-                            object_id,
-                            primitive_op_index,
-                            source_command,
-                        );
-                        return;
-                    }
-                }
-            }
-            TargetPrimitiveOp::Selected => unreachable!("returned for this case at start of fn"),
-        };
-
-        self.select_object_unchecked(object_id);
-        self.select_primitive_op_unchecked(primitive_op);
+        let update_res = self
+            .object_collection
+            .set_object_visible(object_id, visible);
+        if update_res.is_err() {
+            failure_warn_invalid_object_id(object_id, Some(command));
+        }
     }
 
-    #[inline]
-    /// Convenience fn for `select_primitive_op_without_self``
-    fn select_primitive_op_unchecked(&mut self, primitive_op_to_select: PrimitiveOp) {
-        Self::select_primitive_op_without_self(
-            &mut self.selected_primitive_op_id,
-            &mut self.gui,
-            primitive_op_to_select,
-        );
+    fn set_object_locked_via_command(
+        &mut self,
+        object_id: ObjectId,
+        locked: bool,
+        command: Command,
+    ) {
+        let update_res = self.object_collection.set_object_locked(object_id, locked);
+        if update_res.is_err() {
+            failure_warn_invalid_object_id(object_id, Some(command));
+        }
     }
 
-    /// Because E0499 is too fucking conservative with `self`.
-    fn select_primitive_op_without_self(
-        selected_primitive_op_id: &mut Option<PrimitiveOpId>,
-        gui: &mut Gui,
-        primitive_op_to_select: PrimitiveOp,
+    fn set_object_selectable_via_command(
+        &mut self,
+        object_id: ObjectId,
+        selectable: bool,
+        command: Command,
     ) {
-        if let Some(selected_primitive_op_id) = *selected_primitive_op_id {
-            if selected_primitive_op_id == primitive_op_to_select.id() {
-                // don't want to unnecessarily reset the saved gui state
-                return;
-            }
+        let update_res = self
+            .object_collection
+            .set_object_selectable(object_id, selectable);
+        if update_res.is_err() {
+            failure_warn_invalid_object_id(object_id, Some(command));
         }
-
-        gui.primitive_op_selected(&primitive_op_to_select);
-        *selected_primitive_op_id = Some(primitive_op_to_select.id());
     }
 
-    pub(super) fn deselect_primitive_op(&mut self) {
-        Self::deselect_primitive_op_without_self(&mut self.selected_primitive_op_id);
+    fn set_object_layer_via_command(
+        &mut self,
+        object_id: ObjectId,
+        layer_id: Option<LayerId>,
+        command: Command,
+    ) {
+        let update_res = self.object_collection.set_object_layer(object_id, layer_id);
+        if let Err(e) = update_res {
+            let error_msg = e.to_string();
+            command_failed_warn(command, &error_msg);
+        }
     }
 
-    #[inline]
-    /// Because E0499 is too fucking conservative with `self`.
-    pub fn deselect_primitive_op_without_self(
-        selected_primitive_op_id: &mut Option<PrimitiveOpId>,
+    fn set_object_tags_via_command(
+        &mut self,
+        object_id: ObjectId,
+        tags: Vec<String>,
+        command: Command,
     ) {
-        *selected_primitive_op_id = None;
+        let update_res = self.object_collection.set_object_tags(object_id, tags);
+        if update_res.is_err() {
+            failure_warn_invalid_object_id(object_id, Some(command));
+        }
     }
 
-    // ~~ Primitive Op: Remove ~~
-
-    fn remove_primitive_op(
+    fn set_object_parent_via_command(
         &mut self,
-        target_primitive_op: TargetPrimitiveOp,
-        source_command: Option<Command>,
+        object_id: ObjectId,
+        new_parent_id: Option<ObjectId>,
+        command: Command,
     ) {
-        let Some(object_id) =
-            self.object_id_from_target_primitive_op(target_primitive_op, source_command.clone())
-        else {
-            failure_warn_no_selected_object(source_command);
-            return;
-        };
+        let update_res = self
+            .object_collection
+            .set_object_parent(object_id, new_parent_id);
+        if let Err(e) = update_res {
+            let error_msg = e.to_string();
+            command_failed_warn(command, &error_msg);
+        }
+    }
 
-        // check early to ensure if `remove_primitive_op_id_from_object` or `failure_warn_invalid_primitive_op_index`
+    // ~~ Selection ~~
+
+    /// Applies `update` to the selection. If the active primitive op changes, the gui starts
+    /// editing the new one.
+    fn update_selection(&mut self, update: impl FnOnce(&mut Selection)) {
+        let previous_active = (
+            self.selection.active_object_id(),
+            self.selection.active_primitive_op_id(),
+        );
+        update(&mut self.selection);
+
+        let (Some(object_id), Some(primitive_op_id)) = (
+            self.selection.active_object_id(),
+            self.selection.active_primitive_op_id(),
+        ) else {
+            return;
+        };
+        if previous_active == (Some(object_id), Some(primitive_op_id)) {
+            // don't want to unnecessarily reset the saved gui state
+            return;
+        }
+        if let Some(primitive_op) = self
+            .object_collection
+            .definition(object_id)
+            .and_then(|object| object.get_primitive_op(primitive_op_id))
+        {
+            self.gui.primitive_op_selected(primitive_op);
+        }
+    }
+
+    pub(super) fn toggle_object_selection(
+        &mut self,
+        object_id: ObjectId,
+        command: Option<Command>,
+    ) {
+        if self.object_collection.get_object(object_id).is_none() {
+            failure_warn_invalid_object_id(object_id, command);
+            return;
+        }
+        self.update_selection(|selection| selection.toggle_object(object_id));
+    }
+
+    pub(super) fn toggle_primitive_op_selection(
+        &mut self,
+        target_primitive_op: TargetPrimitiveOp,
+        command: Option<Command>,
+    ) {
+        let Some((object_id, primitive_op_id)) =
+            self.primitive_op_id_from_target(target_primitive_op, command)
+        else {
+            return;
+        };
+        self.update_selection(|selection| {
+            selection.toggle_primitive_op(object_id, primitive_op_id)
+        });
+    }
+
+    fn select_all(&mut self) {
+        let selectable_object_ids = self.selectable_object_ids();
+        self.update_selection(|selection| selection.select_all(selectable_object_ids));
+    }
+
+    fn invert_selection(&mut self) {
+        let selectable_object_ids = self.selectable_object_ids();
+        self.update_selection(|selection| selection.invert(selectable_object_ids));
+    }
+
+    fn remove_selected_via_command(&mut self, command: Command) {
+        if self.selection.primitive_ops().is_empty() {
+            self.remove_selected_object_via_command(command);
+            return;
+        }
+
+        for (_, definition_id, primitive_op_id) in self.selected_primitive_ops_by_definition() {
+            let remove_res = self
+                .object_collection
+                .remove_primitive_op_id_from_object(definition_id, primitive_op_id);
+            if remove_res.is_err() {
+                failure_warn_invalid_primitive_op_id(
+                    definition_id,
+                    primitive_op_id,
+                    Some(command.clone()),
+                );
+            }
+        }
+        self.selection.clear_primitive_ops();
+    }
+
+    fn transform_selected_via_command(
+        &mut self,
+        group_transform: GroupTransform,
+        command: Command,
+    ) {
+        if self.selection.is_empty() {
+            failure_warn_no_selected_object(Some(command));
+            return;
+        }
+
+        let pivot_point = match group_transform.pivot {
+            Pivot::BoundsCenter => {
+                let Some(bounds) = self.selection_bounds() else {
+                    command_failed_warn(command, "the selection has no bounds");
+                    return;
+                };
+                (bounds.min + bounds.max) / 2.
+            }
+            Pivot::ObjectOrigin => {
+                let Some(active_object) = self
+                    .selection
+                    .active_object_id()
+                    .and_then(|object_id| self.object_collection.get_object(object_id))
+                else {
+                    failure_warn_no_selected_object(Some(command));
+                    return;
+                };
+                active_object.world_transform().origin
+            }
+            Pivot::Point(point) => point,
+        };
+
+        if !self.selection.primitive_ops().is_empty() {
+            for (object_id, definition_id, primitive_op_id) in
+                self.selected_primitive_ops_by_definition()
+            {
+                let (Some(object), Some(definition)) = (
+                    self.object_collection.get_object(object_id),
+                    self.object_collection.get_object(definition_id),
+                ) else {
+                    failure_warn_invalid_object_id(object_id, Some(command.clone()));
+                    continue;
+                };
+                let Some(primitive_op) = definition.get_primitive_op(primitive_op_id) else {
+                    failure_warn_invalid_primitive_op_id(
+                        object_id,
+                        primitive_op_id,
+                        Some(command.clone()),
+                    );
+                    continue;
+                };
+
+                // primitive ops are placed in object space
+                let local_transform =
+                    group_transform.transform_in_space(pivot_point, &object.world_transform());
+                let new_transform = primitive_op.transform.transformed(&local_transform);
+                let set_res = self.object_collection.set_primitive_op_id_in_object(
+                    definition_id,
+                    primitive_op_id,
+                    None,
+                    Some(new_transform),
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                );
+                if let Err(e) = set_res {
+                    command_failed_warn(command.clone(), &e.to_string());
+                }
+            }
+            return;
+        }
+
+        for object_id in self.selected_root_objects() {
+            let Some(object) = self.object_collection.get_object(object_id) else {
+                failure_warn_invalid_object_id(object_id, Some(command.clone()));
+                continue;
+            };
+
+            // the object transform is relative to the parent
+            let parent_world_transform = object
+                .parent()
+                .and_then(|parent_id| self.object_collection.get_object(parent_id))
+                .map_or(ObjectTransform::IDENTITY, |parent| parent.world_transform());
+            let parent_space_transform =
+                group_transform.transform_in_space(pivot_point, &parent_world_transform);
+            let new_transform = parent_space_transform.mul_transform(&object.transform());
+            if let Err(e) = self
+                .object_collection
+                .set_object_transform(object_id, new_transform)
+            {
+                command_failed_warn(command.clone(), &e.to_string());
+            }
+        }
+    }
+
+    // ~~ Layers ~~
+
+    fn create_layer_via_command(&mut self, command: Command) {
+        let layer_name = format!("Layer {}", self.object_collection.layers().len() + 1);
+        if let Err(e) = self.object_collection.new_layer(layer_name) {
+            failure_warn_unique_id_error(Some(command), e);
+        }
+    }
+
+    fn remove_layer_via_command(&mut self, layer_id: LayerId, command: Command) {
+        if self.object_collection.remove_layer(layer_id).is_err() {
+            failure_warn_invalid_layer_id(layer_id, command);
+        }
+    }
+
+    fn set_layer_name_via_command(
+        &mut self,
+        layer_id: LayerId,
+        new_name: String,
+        command: Command,
+    ) {
+        if self
+            .object_collection
+            .set_layer_name(layer_id, new_name)
+            .is_err()
+        {
+            failure_warn_invalid_layer_id(layer_id, command);
+        }
+    }
+
+    fn set_layer_visible_via_command(
+        &mut self,
+        layer_id: LayerId,
+        visible: bool,
+        command: Command,
+    ) {
+        if self
+            .object_collection
+            .set_layer_visible(layer_id, visible)
+            .is_err()
+        {
+            failure_warn_invalid_layer_id(layer_id, command);
+        }
+    }
+
+    fn set_layer_selectable_via_command(
+        &mut self,
+        layer_id: LayerId,
+        selectable: bool,
+        command: Command,
+    ) {
+        if self
+            .object_collection
+            .set_layer_selectable(layer_id, selectable)
+            .is_err()
+        {
+            failure_warn_invalid_layer_id(layer_id, command);
+        }
+    }
+
+    // ~~ Materials ~~
+
+    fn create_material_via_command(&mut self, command: Command) {
+        let material_name = format!("Material {}", self.object_collection.materials().len() + 1);
+        if let Err(e) = self.object_collection.new_material(material_name) {
+            failure_warn_unique_id_error(Some(command), e);
+        }
+    }
+
+    fn remove_material_via_command(&mut self, material_id: MaterialId, command: Command) {
+        if self.object_collection.remove_material(material_id).is_err() {
+            failure_warn_invalid_material_id(material_id, command);
+        }
+    }
+
+    fn set_material_via_command(
+        &mut self,
+        material_id: MaterialId,
+        new_material: Material,
+        command: Command,
+    ) {
+        if self
+            .object_collection
+            .set_material(material_id, new_material)
+            .is_err()
+        {
+            failure_warn_invalid_material_id(material_id, command);
+        }
+    }
+
+    // ~~ Primtive Op: Selection ~~
+
+    pub(super) fn select_primitive_op_and_object(
+        &mut self,
+        target_primitive_op: TargetPrimitiveOp,
+        source_command: Option<Command>,
+    ) {
+        let object_id = match target_primitive_op {
+            TargetPrimitiveOp::Id(object_id, _) => object_id,
+            TargetPrimitiveOp::Index(object_id, _) => object_id,
+            TargetPrimitiveOp::Selected => {
+                failure_warn_already_selected(source_command);
+                return;
+            }
+        };
+
+        let Some(object) = self.object_collection.definition(object_id) else {
+            failure_warn_invalid_object_id(object_id, source_command);
+            return;
+        };
+
+        let primitive_op = match target_primitive_op {
+            TargetPrimitiveOp::Id(_, primitive_op_id) => {
+                let get_res = object.get_primitive_op(primitive_op_id);
+                match get_res {
+                    Some(primitive_op) => primitive_op.clone(),
+                    None => {
+                        failure_warn_invalid_primitive_op_id(
+                            object_id,
+                            primitive_op_id,
+                            source_command,
+                        );
+                        return;
+                    }
+                }
+            }
+            TargetPrimitiveOp::Index(_, primitive_op_index) => {
+                let get_res = object.primitive_ops.get(primitive_op_index);
+                match get_res {
+                    Some(primitive_op) => primitive_op.clone(),
+                    None => {
+                        failure_warn_invalid_primitive_op_index(
+                            object_id,
+                            primitive_op_index,
+                            source_command,
+                        );
+                        return;
+                    }
+                }
+            }
+            TargetPrimitiveOp::Selected => unreachable!("returned for this case at start of fn"),
+        };
+
+        let primitive_op_id = primitive_op.id();
+        self.update_selection(|selection| {
+            selection.select_primitive_op(object_id, primitive_op_id)
+        });
+    }
+
+    /// Deselects all primitive ops. Objects stay selected.
+    pub(super) fn deselect_primitive_op(&mut self) {
+        self.selection.clear_primitive_ops();
+    }
+
+    // ~~ Primitive Op: Remove ~~
+
+    fn remove_primitive_op(
+        &mut self,
+        target_primitive_op: TargetPrimitiveOp,
+        source_command: Option<Command>,
+    ) {
+        let Some(object_id) =
+            self.object_id_from_target_primitive_op(target_primitive_op, source_command.clone())
+        else {
+            failure_warn_no_selected_object(source_command);
+            return;
+        };
+
+        // check early to ensure if `remove_primitive_op_id_from_object` or `failure_warn_invalid_primitive_op_index`
         // fails it is because of invalid primitive op id/index
         if let None = self.object_collection.get_object(object_id) {
             failure_warn_invalid_object_id(object_id, source_command);
@@ -559,7 +1176,7 @@ impl EngineController {
                 };
                 (removed_id, primitive_op_index)
             }
-            TargetPrimitiveOp::Selected => match self.selected_primitive_op_id {
+            TargetPrimitiveOp::Selected => match self.selection.active_primitive_op_id() {
                 Some(primitive_op_id) => {
                     let remove_res = self
                         .object_collection
@@ -570,7 +1187,8 @@ impl EngineController {
                             primitive_op_id,
                             source_command,
                         );
-                        self.selected_primitive_op_id = None;
+                        self.selection
+                            .remove_primitive_op(object_id, primitive_op_id);
                         return;
                     };
                     (primitive_op_id, removed_index)
@@ -582,62 +1200,63 @@ impl EngineController {
             },
         };
 
-        if !self.is_object_id_selected(object_id) {
-            return;
-        }
-
-        // this primitive op may have been currently selected, in which case we may have
-        // to select the primitive op next to it.
-        let updated_object = self
-            .object_collection
-            .get_object(object_id)
-            .expect("checked that object id is valid at beginning of fn");
-        Self::check_and_select_closest_primitive_op(
-            &mut self.selected_primitive_op_id,
-            &mut self.gui,
-            removed_id,
-            removed_index,
-            updated_object,
-        );
-    }
-
-    /// If a removed primitive op is currently selected, select a different primitive op with the
-    /// closest index to the removed primitive op.
-    fn check_and_select_closest_primitive_op(
-        selected_primitive_op_id: &mut Option<PrimitiveOpId>,
-        gui: &mut Gui,
-        removed_primitive_op_id: PrimitiveOpId,
-        removed_primitive_op_index: usize,
-        selected_object: &Object,
-    ) {
-        if let Some(some_selected_primitive_op_id) = *selected_primitive_op_id {
-            if some_selected_primitive_op_id == removed_primitive_op_id {
-                Self::select_primitive_op_with_closest_index(
-                    selected_primitive_op_id,
-                    gui,
-                    &selected_object.primitive_ops,
-                    removed_primitive_op_index,
-                );
-            }
+        // if the removed primitive op was the active one, select the one next to it instead
+        let removed_active = self.selection.active_object_id() == Some(object_id)
+            && self.selection.active_primitive_op_id() == Some(removed_id);
+        self.selection.remove_primitive_op(object_id, removed_id);
+        if removed_active {
+            self.select_primitive_op_with_closest_index(object_id, removed_index);
         }
     }
 
-    /// Selects a primitive op in `self` from `primitive_ops` which has the closest index to
-    /// `target_prim_op_index`. If `primitive_ops` is empty, deselects primitive op in `self`.
+    /// Selects the primitive op of `object_id` which has the closest index to
+    /// `target_prim_op_index`, if there are any.
     fn select_primitive_op_with_closest_index(
-        selected_primitive_op_id: &mut Option<PrimitiveOpId>,
-        gui: &mut Gui,
-        primitive_op_list: &Vec<PrimitiveOp>,
+        &mut self,
+        object_id: ObjectId,
         target_prim_op_index: usize,
     ) {
-        if let Some(select_index) =
-            choose_closest_valid_index(primitive_op_list.len(), target_prim_op_index)
-        {
-            let primitive_op = primitive_op_list[select_index].clone();
-            Self::select_primitive_op_without_self(selected_primitive_op_id, gui, primitive_op);
-        } else {
-            Self::deselect_primitive_op_without_self(selected_primitive_op_id);
-        }
+        let Some(object) = self.object_collection.definition(object_id) else {
+            return;
+        };
+        let Some(select_index) =
+            choose_closest_valid_index(object.primitive_ops.len(), target_prim_op_index)
+        else {
+            return;
+        };
+        let primitive_op_id = object.primitive_ops[select_index].id();
+        self.update_selection(|selection| selection.add_primitive_op(object_id, primitive_op_id));
+    }
+
+    // ~~ Primitive Op: Duplicate ~~
+
+    fn duplicate_primitive_op_via_command(
+        &mut self,
+        target_primitive_op: TargetPrimitiveOp,
+        command: Command,
+    ) {
+        let Some((object_id, primitive_op_id)) =
+            self.primitive_op_id_from_target(target_primitive_op, Some(command.clone()))
+        else {
+            return;
+        };
+
+        let duplicate_res = self
+            .object_collection
+            .duplicate_primitive_op_in_object(object_id, primitive_op_id);
+        let duplicate_id = match duplicate_res {
+            Ok(duplicate_id) => duplicate_id,
+            Err(e) => {
+                let error_msg = e.to_string();
+                command_failed_warn(command, &error_msg);
+                return;
+            }
+        };
+
+        self.select_primitive_op_and_object(
+            TargetPrimitiveOp::Id(object_id, duplicate_id),
+            Some(command),
+        );
     }
 
     // ~~ Primitive Op: Push ~~
@@ -717,6 +1336,8 @@ impl EngineController {
         new_blend: Option<f32>,
         new_albedo: Option<Vec3>,
         new_specular: Option<f32>,
+        new_modifiers: Option<Vec<DomainModifier>>,
+        new_surface_modifiers: Option<Vec<SurfaceModifier>>,
         source_command: Option<Command>,
     ) {
         let Some(object_id) =
@@ -744,6 +1365,8 @@ impl EngineController {
                     new_blend,
                     new_albedo,
                     new_specular,
+                    new_modifiers,
+                    new_surface_modifiers,
                 );
                 if let Err(_) = set_res {
                     failure_warn_invalid_primitive_op_id(
@@ -763,6 +1386,8 @@ impl EngineController {
                     new_blend,
                     new_albedo,
                     new_specular,
+                    new_modifiers,
+                    new_surface_modifiers,
                 );
                 if let Err(_) = set_res {
                     failure_warn_invalid_primitive_op_index(
@@ -772,7 +1397,7 @@ impl EngineController {
                     );
                 }
             }
-            TargetPrimitiveOp::Selected => match self.selected_primitive_op_id {
+            TargetPrimitiveOp::Selected => match self.selection.active_primitive_op_id() {
                 Some(primitive_op_id) => {
                     let set_res = self.object_collection.set_primitive_op_id_in_object(
                         object_id,
@@ -783,6 +1408,8 @@ impl EngineController {
                         new_blend,
                         new_albedo,
                         new_specular,
+                        new_modifiers,
+                        new_surface_modifiers,
                     );
                     if let Err(_) = set_res {
                         failure_warn_invalid_primitive_op_id(
@@ -790,7 +1417,8 @@ impl EngineController {
                             primitive_op_id,
                             source_command,
                         );
-                        self.selected_primitive_op_id = None;
+                        self.selection
+                            .remove_primitive_op(object_id, primitive_op_id);
                     }
                 }
                 None => {
@@ -800,6 +1428,37 @@ impl EngineController {
         }
     }
 
+    fn set_primitive_op_material_via_command(
+        &mut self,
+        target_primitive_op: TargetPrimitiveOp,
+        material_id: Option<MaterialId>,
+        material_override: bool,
+        command: Command,
+    ) {
+        if let Some(material_id) = material_id {
+            if self.object_collection.get_material(material_id).is_none() {
+                failure_warn_invalid_material_id(material_id, command);
+                return;
+            }
+        }
+
+        let Some((object_id, primitive_op_id)) =
+            self.primitive_op_id_from_target(target_primitive_op, Some(command.clone()))
+        else {
+            return;
+        };
+
+        let set_res = self.object_collection.set_primitive_op_material_in_object(
+            object_id,
+            primitive_op_id,
+            material_id,
+            material_override,
+        );
+        if set_res.is_err() {
+            failure_warn_invalid_primitive_op_id(object_id, primitive_op_id, Some(command));
+        }
+    }
+
     fn shift_primitive_ops_via_command(
         &mut self,
         object_id: ObjectId,
@@ -824,25 +1483,281 @@ impl EngineController {
         }
     }
 
-    // ~~ Internal ~~
+    fn move_primitive_op_via_command(
+        &mut self,
+        object_id: ObjectId,
+        primitive_op_id: PrimitiveOpId,
+        group_id: Option<PrimitiveOpGroupId>,
+        before_primitive_op_id: Option<PrimitiveOpId>,
+        command: Command,
+    ) {
+        let move_res = self.object_collection.move_primitive_op_in_object(
+            object_id,
+            primitive_op_id,
+            group_id,
+            before_primitive_op_id,
+        );
+        if let Err(e) = move_res {
+            let error_msg = e.to_string();
+            command_failed_warn(command, &error_msg);
+        }
+    }
 
-    fn execute_validation_command(&mut self, v_command: ValidationCommand) {
-        match v_command {
-            ValidationCommand::SelectedObject() => self.validate_selected_object(),
+    // ~~ Primitive Op Groups ~~
+
+    fn push_group_via_command(
+        &mut self,
+        object_id: ObjectId,
+        parent_group_id: Option<PrimitiveOpGroupId>,
+        command: Command,
+    ) {
+        let push_res = self
+            .object_collection
+            .push_group_to_object(object_id, parent_group_id);
+        if let Err(e) = push_res {
+            let error_msg = e.to_string();
+            command_failed_warn(command, &error_msg);
         }
     }
 
-    pub(super) fn validate_selected_object(&mut self) {
-        if let Some(some_selected_object_id) = self.selected_object_id {
-            let object_exists = self
-                .object_collection
-                .get_object(some_selected_object_id)
-                .is_some();
+    fn remove_group_via_command(
+        &mut self,
+        object_id: ObjectId,
+        group_id: PrimitiveOpGroupId,
+        command: Command,
+    ) {
+        let remove_res = self
+            .object_collection
+            .remove_group_from_object(object_id, group_id);
+        if let Err(e) = remove_res {
+            let error_msg = e.to_string();
+            command_failed_warn(command, &error_msg);
+        }
+    }
+
+    fn set_group_via_command(
+        &mut self,
+        object_id: ObjectId,
+        group_id: PrimitiveOpGroupId,
+        new_name: Option<String>,
+        new_operation: Option<Operation>,
+        new_blend: Option<f32>,
+        command: Command,
+    ) {
+        let set_res = self.object_collection.set_group_in_object(
+            object_id,
+            group_id,
+            new_name,
+            new_operation,
+            new_blend,
+        );
+        if let Err(e) = set_res {
+            let error_msg = e.to_string();
+            command_failed_warn(command, &error_msg);
+        }
+    }
+
+    fn move_group_via_command(
+        &mut self,
+        object_id: ObjectId,
+        group_id: PrimitiveOpGroupId,
+        parent_group_id: Option<PrimitiveOpGroupId>,
+        before_primitive_op_id: Option<PrimitiveOpId>,
+        command: Command,
+    ) {
+        let move_res = self.object_collection.move_group_in_object(
+            object_id,
+            group_id,
+            parent_group_id,
+            before_primitive_op_id,
+        );
+        if let Err(e) = move_res {
+            let error_msg = e.to_string();
+            command_failed_warn(command, &error_msg);
+        }
+    }
+
+    // ~~ Clipboard ~~
+
+    fn copy_object_via_command(&mut self, object_id: ObjectId, command: Command) {
+        let object_tree = match self.object_collection.object_tree(object_id) {
+            Ok(object_tree) => object_tree,
+            Err(_) => {
+                failure_warn_invalid_object_id(object_id, Some(command));
+                return;
+            }
+        };
+        self.copy_to_clipboard(ClipboardContents::Objects(object_tree), command);
+    }
+
+    fn copy_primitive_op_via_command(
+        &mut self,
+        target_primitive_op: TargetPrimitiveOp,
+        command: Command,
+    ) {
+        let Some((object_id, primitive_op_id)) =
+            self.primitive_op_id_from_target(target_primitive_op, Some(command.clone()))
+        else {
+            return;
+        };
+
+        let Some(primitive_op) = self
+            .object_collection
+            .definition(object_id)
+            .and_then(|object| object.get_primitive_op(primitive_op_id))
+        else {
+            failure_warn_invalid_primitive_op_id(object_id, primitive_op_id, Some(command));
+            return;
+        };
+        let contents = ClipboardContents::PrimitiveOps(vec![primitive_op.clone()]);
+        self.copy_to_clipboard(contents, command);
+    }
+
+    fn copy_selected_via_command(&mut self, command: Command) {
+        if self.selection.active_primitive_op_id().is_some() {
+            self.copy_primitive_op_via_command(TargetPrimitiveOp::Selected, command);
+        } else if let Some(active_object_id) = self.selection.active_object_id() {
+            self.copy_object_via_command(active_object_id, command);
+        } else {
+            failure_warn_no_selected_object(Some(command));
+        }
+    }
+
+    fn duplicate_selected_via_command(&mut self, command: Command) {
+        if self.selection.is_empty() {
+            failure_warn_no_selected_object(Some(command));
+            return;
+        }
+
+        if !self.selection.primitive_ops().is_empty() {
+            let mut duplicate_primitive_ops = Vec::new();
+            for (object_id, definition_id, primitive_op_id) in
+                self.selected_primitive_ops_by_definition()
+            {
+                let duplicate_res = self
+                    .object_collection
+                    .duplicate_primitive_op_in_object(definition_id, primitive_op_id);
+                match duplicate_res {
+                    Ok(duplicate_id) => duplicate_primitive_ops.push((object_id, duplicate_id)),
+                    Err(e) => command_failed_warn(command.clone(), &e.to_string()),
+                }
+            }
+            self.update_selection(|selection| {
+                selection.clear_primitive_ops();
+                for (object_id, duplicate_id) in duplicate_primitive_ops {
+                    selection.add_primitive_op(object_id, duplicate_id);
+                }
+            });
+            return;
+        }
 
-            if !object_exists {
-                self.selected_object_id = None;
+        let mut duplicate_object_ids = Vec::new();
+        for object_id in self.selected_root_objects() {
+            match self.object_collection.duplicate_object(object_id) {
+                Ok(duplicate_id) => duplicate_object_ids.push(duplicate_id),
+                Err(e) => command_failed_warn(command.clone(), &e.to_string()),
             }
         }
+        self.update_selection(|selection| {
+            selection.clear();
+            for duplicate_id in duplicate_object_ids {
+                selection.add_object(duplicate_id);
+            }
+        });
+    }
+
+    fn paste_via_command(&mut self, command: Command) {
+        let Some(clipboard_text) = self.gui.clipboard_text() else {
+            command_failed_warn(command, "clipboard is empty");
+            return;
+        };
+        let contents = match ClipboardContents::from_json(&clipboard_text) {
+            Ok(contents) => contents,
+            Err(e) => {
+                let failed_because = format!(
+                    "clipboard doesn't contain copied objects or primitive ops: {}",
+                    e
+                );
+                command_failed_warn(command, &failed_because);
+                return;
+            }
+        };
+
+        match contents {
+            ClipboardContents::Objects(objects) => {
+                let push_res = self.object_collection.push_objects(objects);
+                match push_res {
+                    Ok(new_object_ids) => {
+                        if let Some(&pasted_object_id) = new_object_ids.first() {
+                            self.select_object_unchecked(pasted_object_id);
+                        }
+                    }
+                    Err(e) => failure_warn_unique_id_error(Some(command), e),
+                }
+            }
+            ClipboardContents::PrimitiveOps(primitive_ops) => {
+                let Some(object_id) = self.selection.active_object_id() else {
+                    failure_warn_no_selected_object(Some(command));
+                    return;
+                };
+                if let Err(e) = check_object_unlocked(&self.object_collection, object_id, true) {
+                    command_failed_warn(command, &e.to_string());
+                    return;
+                }
+
+                let mut pasted_primitive_op_id = None;
+                for primitive_op in primitive_ops {
+                    let push_res = self
+                        .object_collection
+                        .push_primitive_op_copy_to_object(object_id, primitive_op);
+                    match push_res {
+                        Ok(primitive_op_id) => pasted_primitive_op_id = Some(primitive_op_id),
+                        Err(e) => {
+                            let error_msg = e.to_string();
+                            command_failed_warn(command, &error_msg);
+                            return;
+                        }
+                    }
+                }
+
+                if let Some(primitive_op_id) = pasted_primitive_op_id {
+                    self.select_primitive_op_and_object(
+                        TargetPrimitiveOp::Id(object_id, primitive_op_id),
+                        Some(command),
+                    );
+                }
+            }
+        }
+    }
+
+    fn copy_to_clipboard(&mut self, contents: ClipboardContents, command: Command) {
+        match contents.to_json() {
+            Ok(json) => self.gui.set_clipboard_text(json),
+            Err(e) => {
+                let failed_because = format!("failed to serialize clipboard contents: {}", e);
+                command_failed_error(command, &failed_because);
+            }
+        }
+    }
+
+    // ~~ Internal ~~
+
+    fn execute_validation_command(&mut self, v_command: ValidationCommand) {
+        match v_command {
+            ValidationCommand::Selection() => self.validate_selection(),
+        }
+    }
+
+    pub(super) fn validate_selection(&mut self) {
+        let object_collection = &self.object_collection;
+        self.selection.retain(
+            |object_id| object_collection.get_object(object_id).is_some(),
+            |object_id, primitive_op_id| {
+                object_collection
+                    .definition(object_id)
+                    .is_some_and(|object| object.get_primitive_op(primitive_op_id).is_some())
+            },
+        );
     }
 
     // ~~ Misc Helper Functions ~~
@@ -855,7 +1770,7 @@ impl EngineController {
         let object_id = match target_primitive_op {
             TargetPrimitiveOp::Id(object_id, _) => object_id,
             TargetPrimitiveOp::Index(object_id, _) => object_id,
-            TargetPrimitiveOp::Selected => match self.selected_object_id {
+            TargetPrimitiveOp::Selected => match self.selection.active_object_id() {
                 Some(object_id) => object_id,
                 None => {
                     failure_warn_no_selected_object(source_command);
@@ -865,6 +1780,320 @@ impl EngineController {
         };
         Some(object_id)
     }
+
+    /// Warns and returns `None` if the target primitive op doesn't exist.
+    fn primitive_op_id_from_target(
+        &mut self,
+        target_primitive_op: TargetPrimitiveOp,
+        source_command: Option<Command>,
+    ) -> Option<(ObjectId, PrimitiveOpId)> {
+        let object_id =
+            self.object_id_from_target_primitive_op(target_primitive_op, source_command.clone())?;
+        let Some(object) = self.object_collection.definition(object_id) else {
+            failure_warn_invalid_object_id(object_id, source_command);
+            return None;
+        };
+
+        let primitive_op_id = match target_primitive_op {
+            TargetPrimitiveOp::Id(_, primitive_op_id) => primitive_op_id,
+            TargetPrimitiveOp::Index(_, primitive_op_index) => {
+                match object.primitive_ops.get(primitive_op_index) {
+                    Some(primitive_op) => primitive_op.id(),
+                    None => {
+                        failure_warn_invalid_primitive_op_index(
+                            object_id,
+                            primitive_op_index,
+                            source_command,
+                        );
+                        return None;
+                    }
+                }
+            }
+            TargetPrimitiveOp::Selected => match self.selection.active_primitive_op_id() {
+                Some(primitive_op_id) => primitive_op_id,
+                None => {
+                    failure_warn_no_selected_primitive_op(source_command);
+                    return None;
+                }
+            },
+        };
+        Some((object_id, primitive_op_id))
+    }
+
+    /// Selected objects that don't have a selected ancestor, so that operations on object trees
+    /// aren't applied twice.
+    fn selected_root_objects(&self) -> Vec<ObjectId> {
+        let has_selected_ancestor = |object_id: ObjectId| {
+            let parent_of = |id| {
+                self.object_collection
+                    .get_object(id)
+                    .and_then(|object| object.parent())
+            };
+            let mut ancestor_id = parent_of(object_id);
+            while let Some(some_ancestor_id) = ancestor_id {
+                if self.selection.contains_object(some_ancestor_id) {
+                    return true;
+                }
+                ancestor_id = parent_of(some_ancestor_id);
+            }
+            false
+        };
+
+        self.selection
+            .objects()
+            .iter()
+            .copied()
+            .filter(|&object_id| !has_selected_ancestor(object_id))
+            .collect()
+    }
+
+    /// Selected primitive ops as `(selected object id, definition id, primitive op id)`.
+    /// Primitive ops of instances belong to their definition, so a primitive op selected via
+    /// several instances is only returned once.
+    fn selected_primitive_ops_by_definition(&self) -> Vec<(ObjectId, ObjectId, PrimitiveOpId)> {
+        let mut primitive_ops = Vec::<(ObjectId, ObjectId, PrimitiveOpId)>::new();
+        for &(object_id, primitive_op_id) in self.selection.primitive_ops() {
+            let definition_id = self
+                .object_collection
+                .get_object(object_id)
+                .and_then(|object| object.instance_of())
+                .unwrap_or(object_id);
+            let already_added = primitive_ops
+                .iter()
+                .any(|&(_, added_definition_id, added_id)| {
+                    added_definition_id == definition_id && added_id == primitive_op_id
+                });
+            if !already_added {
+                primitive_ops.push((object_id, definition_id, primitive_op_id));
+            }
+        }
+        primitive_ops
+    }
+
+    /// World space bounds of the selected primitive ops, or the selected objects if no primitive
+    /// op is selected.
+    fn selection_bounds(&self) -> Option<Aabb> {
+        let mut bounds: Option<Aabb> = None;
+        let mut add_bounds = |aabb: Aabb| match bounds.as_mut() {
+            Some(some_bounds) => some_bounds.union(aabb),
+            None => bounds = Some(aabb),
+        };
+
+        if !self.selection.primitive_ops().is_empty() {
+            for (object_id, definition_id, primitive_op_id) in
+                self.selected_primitive_ops_by_definition()
+            {
+                let object = self.object_collection.get_object(object_id);
+                let primitive_op = self
+                    .object_collection
+                    .get_object(definition_id)
+                    .and_then(|definition| definition.get_primitive_op(primitive_op_id));
+                if let (Some(object), Some(primitive_op)) = (object, primitive_op) {
+                    add_bounds(
+                        object
+                            .world_transform()
+                            .transform_aabb(&primitive_op.aabb()),
+                    );
+                }
+            }
+        } else {
+            for &object_id in self.selection.objects() {
+                if let Some(object) = self.object_collection.resolved_object(object_id) {
+                    add_bounds(object.aabb());
+                }
+            }
+        }
+        bounds
+    }
+
+    /// Objects that can be selected in the viewport, see
+    /// [`ObjectCollection::is_object_selectable`](crate::engine::object::object_collection::ObjectCollection::is_object_selectable).
+    fn selectable_object_ids(&self) -> Vec<ObjectId> {
+        self.object_collection
+            .objects()
+            .keys()
+            .copied()
+            .filter(|&object_id| self.object_collection.is_object_selectable(object_id))
+            .collect()
+    }
+}
+
+// ~~ Lock Checks ~~
+
+/// Commands that would modify a locked object are rejected. Invalid ids are left for the
+/// command itself to report.
+fn check_command_unlocked(
+    object_collection: &ObjectCollection,
+    selection: &Selection,
+    command: &Command,
+) -> Result<(), CommandError> {
+    let target_object_id = |target_primitive_op: &TargetPrimitiveOp| match target_primitive_op {
+        TargetPrimitiveOp::Id(object_id, _) => Some(*object_id),
+        TargetPrimitiveOp::Index(object_id, _) => Some(*object_id),
+        TargetPrimitiveOp::Selected => selection.active_object_id(),
+    };
+
+    // the modified object and whether its primitive ops are modified
+    let (modified_object_id, modifies_primitive_ops) = match command {
+        Command::RemoveObjectAndChildren(object_id) => {
+            for descendant_id in object_collection.descendants(*object_id) {
+                check_object_unlocked(object_collection, descendant_id, false)?;
+            }
+            (Some(*object_id), false)
+        }
+        Command::RemoveObject(object_id) => {
+            check_object_removable(object_collection, *object_id)?;
+            (None, false)
+        }
+        Command::MakeInstanceUnique(object_id)
+        | Command::SetObjectOrigin { object_id, .. }
+        | Command::SetObjectRotation { object_id, .. }
+        | Command::SetObjectScale { object_id, .. }
+        | Command::SetObjectName { object_id, .. }
+        | Command::SetObjectParent { object_id, .. }
+        | Command::SetObjectLayer { object_id, .. }
+        | Command::SetObjectTags { object_id, .. } => (Some(*object_id), false),
+        Command::RemoveSelectedObject() => {
+            for &object_id in selection.objects() {
+                check_object_removable(object_collection, object_id)?;
+            }
+            (None, false)
+        }
+        // removes the selected objects when no primitive op is selected
+        Command::RemoveSelected() if selection.primitive_ops().is_empty() => {
+            for &object_id in selection.objects() {
+                check_object_removable(object_collection, object_id)?;
+            }
+            (None, false)
+        }
+        Command::RemoveSelected() | Command::TransformSelected(_) => {
+            check_selection_unlocked(object_collection, selection)?;
+            (None, false)
+        }
+        // duplicating objects doesn't modify them
+        Command::DuplicateSelected() => {
+            for &(object_id, _) in selection.primitive_ops() {
+                check_object_unlocked(object_collection, object_id, true)?;
+            }
+            (None, false)
+        }
+
+        Command::RemovePrimitiveOp(target_primitive_op)
+        | Command::DuplicatePrimitiveOp(target_primitive_op)
+        | Command::SetPrimitiveOp {
+            target_primitive_op,
+            ..
+        }
+        | Command::SetPrimitive {
+            target_primitive_op,
+            ..
+        }
+        | Command::SetPrimitiveTransform {
+            target_primitive_op,
+            ..
+        }
+        | Command::SetOperation {
+            target_primitive_op,
+            ..
+        }
+        | Command::SetBlend {
+            target_primitive_op,
+            ..
+        }
+        | Command::SetAlbedo {
+            target_primitive_op,
+            ..
+        }
+        | Command::SetSpecular {
+            target_primitive_op,
+            ..
+        }
+        | Command::SetPrimitiveOpMaterial {
+            target_primitive_op,
+            ..
+        }
+        | Command::SetSurfaceModifiers {
+            target_primitive_op,
+            ..
+        }
+        | Command::SetDomainModifiers {
+            target_primitive_op,
+            ..
+        } => (target_object_id(target_primitive_op), true),
+        Command::PushPrimitiveOp { object_id, .. }
+        | Command::PushPrimitiveOpAndSelect { object_id, .. }
+        | Command::ShiftPrimitiveOps { object_id, .. }
+        | Command::MovePrimitiveOp { object_id, .. }
+        | Command::PushPrimitiveOpGroup { object_id, .. }
+        | Command::RemovePrimitiveOpGroup { object_id, .. }
+        | Command::SetPrimitiveOpGroup { object_id, .. }
+        | Command::MovePrimitiveOpGroup { object_id, .. } => (Some(*object_id), true),
+        _ => (None, false),
+    };
+
+    match modified_object_id {
+        Some(object_id) => {
+            check_object_unlocked(object_collection, object_id, modifies_primitive_ops)
+        }
+        None => Ok(()),
+    }
+}
+
+/// Checks the objects of the selected primitive ops, or the selected objects if no primitive op
+/// is selected.
+fn check_selection_unlocked(
+    object_collection: &ObjectCollection,
+    selection: &Selection,
+) -> Result<(), CommandError> {
+    if selection.primitive_ops().is_empty() {
+        for &object_id in selection.objects() {
+            check_object_unlocked(object_collection, object_id, false)?;
+        }
+    } else {
+        for &(object_id, _) in selection.primitive_ops() {
+            check_object_unlocked(object_collection, object_id, true)?;
+        }
+    }
+    Ok(())
+}
+
+/// Returns an error if `object_id` is locked, or if `modifies_primitive_ops` is set and the
+/// object is an instance of a locked definition.
+fn check_object_unlocked(
+    object_collection: &ObjectCollection,
+    object_id: ObjectId,
+    modifies_primitive_ops: bool,
+) -> Result<(), CommandError> {
+    let Some(object) = object_collection.get_object(object_id) else {
+        return Ok(());
+    };
+    if object.locked {
+        return Err(CommandError::ObjectLocked(object_id));
+    }
+
+    if modifies_primitive_ops {
+        if let Some(definition_id) = object.instance_of() {
+            let definition_locked = object_collection
+                .get_object(definition_id)
+                .is_some_and(|definition| definition.locked);
+            if definition_locked {
+                return Err(CommandError::ObjectLocked(definition_id));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Removing an object moves its children to its parent, which modifies them too.
+fn check_object_removable(
+    object_collection: &ObjectCollection,
+    object_id: ObjectId,
+) -> Result<(), CommandError> {
+    check_object_unlocked(object_collection, object_id, false)?;
+    for child_id in object_collection.children(object_id) {
+        check_object_unlocked(object_collection, child_id, false)?;
+    }
+    Ok(())
 }
 
 // ~~ Failed Command Handling ~~
@@ -880,6 +2109,17 @@ fn command_failed_error(command: Command, failed_because: &str) {
     );
 }
 
+fn failure_warn_invalid_layer_id(layer_id: LayerId, command: Command) {
+    command_failed_warn(command, &CommandError::InvalidLayerId(layer_id).to_string());
+}
+
+fn failure_warn_invalid_material_id(material_id: MaterialId, command: Command) {
+    command_failed_warn(
+        command,
+        &CommandError::InvalidMaterialId(material_id).to_string(),
+    );
+}
+
 fn failure_warn_already_selected(source_command: Option<Command>) {
     if let Some(some_command) = source_command {
         command_failed_warn(some_command, "selecting the selected primitive op is NOP");
@@ -961,3 +2201,62 @@ fn failure_warn_unique_id_error(source_command: Option<Command>, unique_id_error
         warn!("{}", failed_because);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns `(parent, child)`
+    fn new_parent_and_child(object_collection: &mut ObjectCollection) -> (ObjectId, ObjectId) {
+        let (parent_id, _) = object_collection.new_object("parent", Vec3::ZERO).unwrap();
+        let (child_id, _) = object_collection.new_object("child", Vec3::X).unwrap();
+        object_collection
+            .set_object_parent(child_id, Some(parent_id))
+            .unwrap();
+        (parent_id, child_id)
+    }
+
+    #[test]
+    fn remove_object_rejected_with_locked_child() {
+        let mut object_collection = ObjectCollection::new();
+        let (parent_id, child_id) = new_parent_and_child(&mut object_collection);
+        let selection = Selection::new();
+        let remove_command = Command::RemoveObject(parent_id);
+        assert!(check_command_unlocked(&object_collection, &selection, &remove_command).is_ok());
+
+        object_collection.set_object_locked(child_id, true).unwrap();
+        assert!(matches!(
+            check_command_unlocked(&object_collection, &selection, &remove_command),
+            Err(CommandError::ObjectLocked(locked_id)) if locked_id == child_id
+        ));
+        // nor can the locked child itself
+        assert!(check_command_unlocked(
+            &object_collection,
+            &selection,
+            &Command::RemoveObject(child_id)
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn remove_selected_rejected_with_locked_child() {
+        let mut object_collection = ObjectCollection::new();
+        let (parent_id, child_id) = new_parent_and_child(&mut object_collection);
+        object_collection.set_object_locked(child_id, true).unwrap();
+        let mut selection = Selection::new();
+        selection.select_object(parent_id);
+        for command in [Command::RemoveSelectedObject(), Command::RemoveSelected()] {
+            assert!(check_command_unlocked(&object_collection, &selection, &command).is_err());
+        }
+        // moving the parent moves the child but doesn't modify it
+        assert!(check_command_unlocked(
+            &object_collection,
+            &selection,
+            &Command::SetObjectOrigin {
+                object_id: parent_id,
+                origin: Vec3::Y
+            }
+        )
+        .is_ok());
+    }
+}
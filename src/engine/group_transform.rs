@@ -0,0 +1,121 @@
+use super::object::object_transform::ObjectTransform;
+use glam::{Quat, Vec3};
+
+/// Point that a [`GroupTransform`] rotates and scales around
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Pivot {
+    /// Center of the world space bounds of everything being transformed
+    #[default]
+    BoundsCenter,
+    /// World space origin of the active object
+    ObjectOrigin,
+    /// User-placed world space point
+    Point(Vec3),
+}
+
+/// Moves several primitive ops or objects together in world space: uniform scale and rotation
+/// around `pivot`, then `translation`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GroupTransform {
+    pub translation: Vec3,
+    pub rotation: Quat,
+    pub scale: f32,
+    pub pivot: Pivot,
+}
+
+impl GroupTransform {
+    pub const IDENTITY: Self = Self {
+        translation: Vec3::ZERO,
+        rotation: Quat::IDENTITY,
+        scale: 1.,
+        pivot: Pivot::BoundsCenter,
+    };
+
+    /// World space transform with the pivot resolved to `pivot_point`
+    pub fn world_transform(&self, pivot_point: Vec3) -> ObjectTransform {
+        let rotation = self.rotation.normalize();
+        ObjectTransform::new(
+            pivot_point + self.translation - rotation * (self.scale * pivot_point),
+            rotation,
+            self.scale,
+        )
+    }
+
+    /// Same as [`Self::world_transform`] but acting on things placed by `space`, e.g. the
+    /// primitive ops of an object with world transform `space`.
+    pub fn transform_in_space(
+        &self,
+        pivot_point: Vec3,
+        space: &ObjectTransform,
+    ) -> ObjectTransform {
+        space
+            .inverse()
+            .mul_transform(&self.world_transform(pivot_point).mul_transform(space))
+    }
+}
+
+impl Default for GroupTransform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::primitives::primitive_transform::PrimitiveTransform;
+    use std::f32::consts::{FRAC_PI_2, PI};
+
+    fn assert_vec3_near(a: Vec3, b: Vec3) {
+        assert!(a.abs_diff_eq(b, 1e-5), "{} != {}", a, b);
+    }
+
+    #[test]
+    fn pivot_stays_in_place() {
+        let pivot_point = Vec3::new(1., 2., 3.);
+        let group_transform = GroupTransform {
+            rotation: Quat::from_rotation_y(FRAC_PI_2),
+            scale: 2.,
+            ..GroupTransform::IDENTITY
+        };
+        let world_transform = group_transform.world_transform(pivot_point);
+        assert_vec3_near(world_transform.transform_point(pivot_point), pivot_point);
+        assert_vec3_near(
+            world_transform.transform_point(pivot_point + Vec3::X),
+            pivot_point - 2. * Vec3::Z,
+        );
+    }
+
+    #[test]
+    fn primitive_ops_move_together() {
+        let object_transform = ObjectTransform::new(Vec3::X, Quat::from_rotation_z(FRAC_PI_2), 2.);
+        let primitive_transforms = [
+            PrimitiveTransform::new(Vec3::ZERO, Quat::IDENTITY),
+            PrimitiveTransform::new(Vec3::X, Quat::from_rotation_x(FRAC_PI_2)),
+        ];
+        let world_centers =
+            primitive_transforms.map(|p| object_transform.transform_point(p.center));
+        assert_vec3_near(world_centers[1], Vec3::new(1., 2., 0.));
+
+        // half turn around the midpoint of the two primitive ops, then up
+        let pivot_point = (world_centers[0] + world_centers[1]) / 2.;
+        let group_transform = GroupTransform {
+            translation: Vec3::Z,
+            rotation: Quat::from_rotation_z(PI),
+            scale: 0.5,
+            pivot: Pivot::Point(pivot_point),
+        };
+        let local_transform = group_transform.transform_in_space(pivot_point, &object_transform);
+        let new_transforms = primitive_transforms.map(|p| p.transformed(&local_transform));
+
+        let new_world_centers = new_transforms.map(|p| object_transform.transform_point(p.center));
+        assert_vec3_near(new_world_centers[0], Vec3::new(1., 1.5, 1.));
+        assert_vec3_near(new_world_centers[1], Vec3::new(1., 0.5, 1.));
+        for (old, new) in primitive_transforms.iter().zip(new_transforms.iter()) {
+            assert_vec3_near(new.scale, old.scale * 0.5);
+            assert!(new
+                .total_rotation()
+                .abs_diff_eq(Quat::from_rotation_z(PI) * old.total_rotation(), 1e-5));
+        }
+    }
+}
@@ -1,11 +1,15 @@
 pub mod aabb;
+mod clipboard;
 pub mod commands;
 pub mod config_engine;
 /// Engine entry point
 pub mod engine_controller;
+pub mod group_transform;
 pub mod main_thread;
 pub mod object;
 pub mod primitives;
 mod render_thread;
 mod save_states;
+pub mod sdf_evaluator;
+pub mod selection;
 pub mod settings;
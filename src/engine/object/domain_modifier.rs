@@ -0,0 +1,139 @@
+use crate::{
+    engine::config_engine::INFINITE_REPETITION_EXTENT,
+    renderer::shader_interfaces::primitive_op_buffer::{
+        domain_modifier_codes, PrimitiveOpBufferUnit, DOMAIN_MODIFIER_LEN,
+    },
+};
+use glam::{BVec3, UVec3, Vec3};
+use serde::{Deserialize, Serialize};
+
+/// Warps the space a primitive is evaluated in. A primitive op applies its modifiers in order
+/// to the sample position in primitive space (after the primitive transform) before evaluating
+/// the primitive's signed distance function.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum DomainModifier {
+    /// Repeats the primitive every `spacing` units along each axis. A spacing of 0 disables
+    /// repetition along that axis.
+    Repeat {
+        spacing: Vec3,
+        /// Number of copies on each side of the original. `None` repeats forever.
+        limit: Option<UVec3>,
+    },
+    /// Rotates the xy plane around the z axis by `rate` radians per unit along z.
+    Twist { rate: f32 },
+    /// Bends the x axis towards +y, curving by `rate` radians per unit along x.
+    Bend { rate: f32 },
+    /// Stretches the primitive from its center by `amount` along each axis.
+    Elongate { amount: Vec3 },
+    /// Mirrors the positive side of the primitive onto the negative side of each axis.
+    Symmetry { axes: BVec3 },
+}
+
+impl DomainModifier {
+    pub const VARIANTS: &'static [DomainModifier] = &[
+        Self::Repeat {
+            spacing: Vec3::ONE,
+            limit: None,
+        },
+        Self::Twist { rate: 1. },
+        Self::Bend { rate: 0.5 },
+        Self::Elongate {
+            amount: Vec3::new(0.5, 0., 0.),
+        },
+        Self::Symmetry { axes: BVec3::TRUE },
+    ];
+
+    pub fn variants_with_names() -> Vec<(Self, &'static str)> {
+        Self::VARIANTS
+            .iter()
+            .map(|modifier| (*modifier, modifier.name()))
+            .collect()
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Repeat { .. } => "Repeat",
+            Self::Twist { .. } => "Twist",
+            Self::Bend { .. } => "Bend",
+            Self::Elongate { .. } => "Elongate",
+            Self::Symmetry { .. } => "Symmetry",
+        }
+    }
+
+    /// Modifier code followed by its parameters as floats.
+    /// Corresponds to `apply_domain_modifiers()` in `scene_geometry.frag`.
+    pub fn encoded(&self) -> [PrimitiveOpBufferUnit; DOMAIN_MODIFIER_LEN] {
+        let (code, params): (PrimitiveOpBufferUnit, [f32; DOMAIN_MODIFIER_LEN - 1]) = match *self {
+            Self::Repeat { spacing, limit } => {
+                // negative limit = infinite repetition
+                let limit = limit.map_or(Vec3::splat(-1.), |limit| limit.as_vec3());
+                (
+                    domain_modifier_codes::REPEAT,
+                    [spacing.x, spacing.y, spacing.z, limit.x, limit.y, limit.z],
+                )
+            }
+            Self::Twist { rate } => (domain_modifier_codes::TWIST, [rate, 0., 0., 0., 0., 0.]),
+            Self::Bend { rate } => (domain_modifier_codes::BEND, [rate, 0., 0., 0., 0., 0.]),
+            Self::Elongate { amount } => {
+                let half = amount.max(Vec3::ZERO) / 2.;
+                (
+                    domain_modifier_codes::ELONGATE,
+                    [half.x, half.y, half.z, 0., 0., 0.],
+                )
+            }
+            Self::Symmetry { axes } => {
+                let axes = Vec3::select(axes, Vec3::ONE, Vec3::ZERO);
+                (
+                    domain_modifier_codes::SYMMETRY,
+                    [axes.x, axes.y, axes.z, 0., 0., 0.],
+                )
+            }
+        };
+
+        let mut encoded = [0; DOMAIN_MODIFIER_LEN];
+        encoded[0] = code;
+        for (encoded_param, param) in encoded[1..].iter_mut().zip(params) {
+            *encoded_param = param.to_bits();
+        }
+        encoded
+    }
+
+    /// Given the bounds (`min`, `max`) of a shape in modified space, returns bounds containing
+    /// every position that this modifier maps into them.
+    pub fn unmodified_bounds(&self, min: Vec3, max: Vec3) -> (Vec3, Vec3) {
+        match *self {
+            Self::Repeat { spacing, limit } => {
+                // axes with a spacing of 0 or less aren't repeated
+                let spacing = spacing.max(Vec3::ZERO);
+                let copies_extent = match limit {
+                    Some(limit) => spacing * limit.as_vec3(),
+                    None => Vec3::select(
+                        spacing.cmpgt(Vec3::ZERO),
+                        Vec3::splat(INFINITE_REPETITION_EXTENT),
+                        Vec3::ZERO,
+                    ),
+                };
+                (min - copies_extent, max + copies_extent)
+            }
+            // both rotate the xy plane around the z axis so distances from the z axis are kept
+            Self::Twist { .. } | Self::Bend { .. } => {
+                let radius = min.truncate().abs().max(max.truncate().abs()).length();
+                (
+                    Vec3::new(-radius, -radius, min.z),
+                    Vec3::new(radius, radius, max.z),
+                )
+            }
+            Self::Elongate { amount } => {
+                let half = amount.max(Vec3::ZERO) / 2.;
+                (min - half, max + half)
+            }
+            Self::Symmetry { axes } => {
+                let extent = min.abs().max(max.abs());
+                (
+                    Vec3::select(axes, -extent, min),
+                    Vec3::select(axes, extent, max),
+                )
+            }
+        }
+    }
+}
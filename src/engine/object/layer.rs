@@ -0,0 +1,48 @@
+use crate::helper::unique_id_gen::{UniqueId, UniqueIdType};
+use serde::{Deserialize, Serialize};
+
+// LAYER
+
+/// Named set of objects that can be hidden or made unselectable together. Objects that aren't
+/// assigned to a layer are always visible and selectable, unless their own flags say otherwise.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Layer {
+    pub name: String,
+    pub visible: bool,
+    pub selectable: bool,
+}
+
+impl Layer {
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            visible: true,
+            selectable: true,
+        }
+    }
+}
+
+// LAYER ID
+
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub struct LayerId(pub UniqueId);
+
+impl UniqueIdType for LayerId {
+    fn raw_id(&self) -> UniqueId {
+        self.0
+    }
+}
+
+impl From<UniqueId> for LayerId {
+    fn from(id: UniqueId) -> Self {
+        Self(id)
+    }
+}
+
+impl std::fmt::Display for LayerId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.raw_id())
+    }
+}
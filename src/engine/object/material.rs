@@ -0,0 +1,52 @@
+use crate::{
+    engine::config_engine::{DEFAULT_ALBEDO, DEFAULT_SPECULAR},
+    helper::unique_id_gen::{UniqueId, UniqueIdType},
+};
+use glam::Vec3;
+use serde::{Deserialize, Serialize};
+
+// MATERIAL
+
+/// Named surface properties that primitive ops can share, see
+/// [`PrimitiveOp::material`](super::primitive_op::PrimitiveOp::material).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Material {
+    pub name: String,
+    pub albedo: Vec3,
+    pub specular: f32,
+}
+
+impl Material {
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            albedo: DEFAULT_ALBEDO,
+            specular: DEFAULT_SPECULAR,
+        }
+    }
+}
+
+// MATERIAL ID
+
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub struct MaterialId(pub UniqueId);
+
+impl UniqueIdType for MaterialId {
+    fn raw_id(&self) -> UniqueId {
+        self.0
+    }
+}
+
+impl From<UniqueId> for MaterialId {
+    fn from(id: UniqueId) -> Self {
+        Self(id)
+    }
+}
+
+impl std::fmt::Display for MaterialId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.raw_id())
+    }
+}
@@ -1,5 +1,11 @@
+pub mod domain_modifier;
+pub mod layer;
+pub mod material;
 pub mod object;
 pub mod object_collection;
+pub mod object_transform;
 pub mod objects_delta;
 pub mod operation;
 pub mod primitive_op;
+pub mod primitive_op_group;
+pub mod surface_modifier;
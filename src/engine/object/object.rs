@@ -1,26 +1,29 @@
 use super::{
+    domain_modifier::DomainModifier,
+    layer::LayerId,
+    object_transform::ObjectTransform,
     operation::Operation,
     primitive_op::{PrimitiveOp, PrimitiveOpId},
+    primitive_op_group::{
+        evaluation_steps, group_ancestors, EvaluationStep, PrimitiveOpGroup, PrimitiveOpGroupId,
+    },
+    surface_modifier::SurfaceModifier,
 };
 use crate::{
     engine::{
         aabb::Aabb,
-        primitives::{
-            primitive::{EncodablePrimitive, Primitive},
-            primitive_transform::PrimitiveTransform,
-        },
+        primitives::{primitive::Primitive, primitive_transform::PrimitiveTransform},
     },
     helper::{
         more_errors::CollectionError,
         unique_id_gen::{UniqueId, UniqueIdError, UniqueIdGen, UniqueIdType},
     },
     renderer::shader_interfaces::primitive_op_buffer::{
-        create_primitive_op_packet, nop_primitive_op_packet, PrimitiveOpBufferUnit,
-        PrimitiveOpPacket, MAX_PRIMITIVE_OP_COUNT,
+        encode_primitive_ops, PrimitiveOpBufferUnit, MAX_GROUP_DEPTH, MAX_PRIMITIVE_OP_COUNT,
     },
 };
 use egui_dnd::utils::{shift_slice, ShiftSliceError};
-use glam::Vec3;
+use glam::{Quat, Vec3};
 use serde::{Deserialize, Serialize};
 
 // ~~ Object Id ~~
@@ -53,10 +56,36 @@ impl std::fmt::Display for ObjectId {
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Object {
     pub name: String,
+    /// Relative to the parent object if there is one
     pub origin: Vec3,
+    pub rotation: Quat,
+    /// Uniform scale applied to all primitive ops around the origin
+    pub scale: f32,
+    /// In evaluation order. Primitive ops in the same group are kept next to each other.
     pub primitive_ops: Vec<PrimitiveOp>,
+    /// Hidden objects aren't drawn
+    pub visible: bool,
+    /// Locked objects can't be modified by commands
+    pub locked: bool,
+    /// Whether the object can be selected by clicking on it in the viewport
+    pub selectable: bool,
+    /// Free-form labels for finding objects, see [`ObjectCollection::objects_with_tag`](super::object_collection::ObjectCollection::objects_with_tag)
+    pub tags: Vec<String>,
+    /// `None` if the object isn't in a layer. Set via
+    /// [`ObjectCollection::set_object_layer`](super::object_collection::ObjectCollection::set_object_layer)
+    layer: Option<LayerId>,
+    groups: Vec<PrimitiveOpGroup>,
+    /// Set via [`ObjectCollection::set_object_parent`](super::object_collection::ObjectCollection::set_object_parent)
+    parent: Option<ObjectId>,
+    /// World transform of the parent object. Kept up to date by the object collection.
+    #[serde(skip)]
+    parent_transform: ObjectTransform,
+    /// Object whose primitive ops are used in place of this object's own. Set via
+    /// [`ObjectCollection::new_instance`](super::object_collection::ObjectCollection::new_instance)
+    instance_of: Option<ObjectId>,
 
     primitive_op_id_gen: UniqueIdGen<PrimitiveOpId>,
+    primitive_op_group_id_gen: UniqueIdGen<PrimitiveOpGroupId>,
 }
 
 impl Object {
@@ -64,8 +93,20 @@ impl Object {
         Self {
             name,
             origin,
+            rotation: Quat::IDENTITY,
+            scale: 1.,
             primitive_ops: Vec::new(),
+            visible: true,
+            locked: false,
+            selectable: true,
+            tags: Vec::new(),
+            layer: None,
+            groups: Vec::new(),
+            parent: None,
+            parent_transform: ObjectTransform::IDENTITY,
+            instance_of: None,
             primitive_op_id_gen: UniqueIdGen::new(),
+            primitive_op_group_id_gen: UniqueIdGen::new(),
         }
     }
 
@@ -132,16 +173,260 @@ impl Object {
         Ok(primitive_op_id)
     }
 
+    /// Inserts a copy of a primitive op right after it in the same group. Returns the id of the
+    /// copy.
+    pub fn duplicate_primitive_op(
+        &mut self,
+        primitive_op_id: PrimitiveOpId,
+    ) -> Result<PrimitiveOpId, CollectionError> {
+        let Some((primitive_op, index)) = self.get_primitive_op_and_index(primitive_op_id) else {
+            return Err(CollectionError::InvalidId {
+                raw_id: primitive_op_id.raw_id(),
+            });
+        };
+        let mut duplicate = primitive_op.clone();
+        let duplicate_id = self.primitive_op_id_gen.new_id()?;
+        duplicate.set_id(duplicate_id);
+        self.primitive_ops.insert(index + 1, duplicate);
+        Ok(duplicate_id)
+    }
+
+    /// Appends a primitive op copied from any object to the root of this one, giving it a new id.
+    /// Returns the new id.
+    pub fn push_primitive_op_copy(
+        &mut self,
+        mut primitive_op: PrimitiveOp,
+    ) -> Result<PrimitiveOpId, UniqueIdError> {
+        let primitive_op_id = self.primitive_op_id_gen.new_id()?;
+        primitive_op.set_id(primitive_op_id);
+        primitive_op.set_group(None);
+        self.primitive_ops.push(primitive_op);
+        Ok(primitive_op_id)
+    }
+
     pub fn shift_primitive_ops(
         &mut self,
         source_index: usize,
         target_index: usize,
     ) -> Result<(), ShiftSliceError> {
-        shift_slice(source_index, target_index, &mut self.primitive_ops)
+        shift_slice(source_index, target_index, &mut self.primitive_ops)?;
+        self.sort_primitive_ops_by_group();
+        Ok(())
+    }
+
+    /// Moves a primitive op into `group` (`None` for the root of the object), placing it before
+    /// `before_primitive_op_id` or at the end of the group.
+    pub fn move_primitive_op(
+        &mut self,
+        primitive_op_id: PrimitiveOpId,
+        group: Option<PrimitiveOpGroupId>,
+        before_primitive_op_id: Option<PrimitiveOpId>,
+    ) -> Result<(), CollectionError> {
+        if let Some(group_id) = group {
+            self.check_group_id(group_id)?;
+        }
+        let Some((_, index)) = self.get_primitive_op_and_index(primitive_op_id) else {
+            return Err(CollectionError::InvalidId {
+                raw_id: primitive_op_id.raw_id(),
+            });
+        };
+
+        let mut primitive_op = self.primitive_ops.remove(index);
+        primitive_op.set_group(group);
+        let insert_index = self.insert_index_before(before_primitive_op_id);
+        self.primitive_ops.insert(insert_index, primitive_op);
+
+        self.sort_primitive_ops_by_group();
+        Ok(())
+    }
+
+    // Groups
+
+    /// Returns the id of the newly created (empty) group
+    pub fn push_group(
+        &mut self,
+        parent: Option<PrimitiveOpGroupId>,
+    ) -> Result<PrimitiveOpGroupId, CollectionError> {
+        if let Some(parent_id) = parent {
+            self.check_group_id(parent_id)?;
+        }
+        if group_ancestors(parent, &self.groups).len() >= MAX_GROUP_DEPTH {
+            return Err(CollectionError::MaxDepthReached {
+                max_depth: MAX_GROUP_DEPTH,
+            });
+        }
+
+        let group_id = self.primitive_op_group_id_gen.new_id()?;
+        let name = format!("Group {}", group_id);
+        self.groups
+            .push(PrimitiveOpGroup::new(group_id, parent, name));
+        Ok(group_id)
+    }
+
+    /// Removes the group. Its primitive ops and sub-groups are moved to the parent of the group.
+    pub fn remove_group(&mut self, group_id: PrimitiveOpGroupId) -> Result<(), CollectionError> {
+        let Some(index) = self.groups.iter().position(|group| group.id() == group_id) else {
+            return Err(CollectionError::InvalidId {
+                raw_id: group_id.raw_id(),
+            });
+        };
+        let removed_group = self.groups.remove(index);
+        _ = self.primitive_op_group_id_gen.recycle_id(group_id);
+
+        for primitive_op in &mut self.primitive_ops {
+            if primitive_op.group() == Some(group_id) {
+                primitive_op.set_group(removed_group.parent());
+            }
+        }
+        for group in &mut self.groups {
+            if group.parent() == Some(group_id) {
+                group.set_parent(removed_group.parent());
+            }
+        }
+
+        self.sort_primitive_ops_by_group();
+        Ok(())
+    }
+
+    pub fn set_group(
+        &mut self,
+        group_id: PrimitiveOpGroupId,
+        new_name: Option<String>,
+        new_operation: Option<Operation>,
+        new_blend: Option<f32>,
+    ) -> Result<(), CollectionError> {
+        let Some(group) = self.groups.iter_mut().find(|group| group.id() == group_id) else {
+            return Err(CollectionError::InvalidId {
+                raw_id: group_id.raw_id(),
+            });
+        };
+        if let Some(some_new_name) = new_name {
+            group.name = some_new_name;
+        }
+        if let Some(some_new_operation) = new_operation {
+            group.op = some_new_operation;
+        }
+        if let Some(some_new_blend) = new_blend {
+            group.blend = some_new_blend;
+        }
+        Ok(())
+    }
+
+    /// Moves a group (and everything in it) into `parent` (`None` for the root of the object),
+    /// placing it before `before_primitive_op_id` or at the end of the parent.
+    pub fn move_group(
+        &mut self,
+        group_id: PrimitiveOpGroupId,
+        parent: Option<PrimitiveOpGroupId>,
+        before_primitive_op_id: Option<PrimitiveOpId>,
+    ) -> Result<(), CollectionError> {
+        self.check_group_id(group_id)?;
+        if let Some(parent_id) = parent {
+            self.check_group_id(parent_id)?;
+        }
+
+        // a group can't be moved into itself or one of its sub-groups
+        let parent_ancestors = group_ancestors(parent, &self.groups);
+        if parent_ancestors
+            .iter()
+            .any(|ancestor| ancestor.id() == group_id)
+        {
+            return Err(CollectionError::InvalidParent {
+                raw_id: parent.map_or(0, |parent_id| parent_id.raw_id()),
+            });
+        }
+        if parent_ancestors.len() + self.group_height(group_id) > MAX_GROUP_DEPTH {
+            return Err(CollectionError::MaxDepthReached {
+                max_depth: MAX_GROUP_DEPTH,
+            });
+        }
+
+        let (moved_ops, remaining_ops): (Vec<PrimitiveOp>, Vec<PrimitiveOp>) =
+            std::mem::take(&mut self.primitive_ops)
+                .into_iter()
+                .partition(|primitive_op| self.is_in_group(primitive_op.group(), group_id));
+        self.primitive_ops = remaining_ops;
+        let insert_index = self.insert_index_before(before_primitive_op_id);
+        self.primitive_ops
+            .splice(insert_index..insert_index, moved_ops);
+
+        if let Some(group) = self.groups.iter_mut().find(|group| group.id() == group_id) {
+            group.set_parent(parent);
+        }
+
+        self.sort_primitive_ops_by_group();
+        Ok(())
+    }
+
+    pub(super) fn set_parent(&mut self, parent: Option<ObjectId>) {
+        self.parent = parent;
+    }
+
+    pub(super) fn set_layer(&mut self, layer: Option<LayerId>) {
+        self.layer = layer;
+    }
+
+    pub(super) fn set_instance_of(&mut self, instance_of: Option<ObjectId>) {
+        self.instance_of = instance_of;
+    }
+
+    /// Replaces the primitive ops and groups of this object with copies of the ones in `source`.
+    pub(super) fn copy_primitive_ops_from(&mut self, source: &Object) {
+        self.primitive_ops = source.primitive_ops.clone();
+        self.groups = source.groups.clone();
+        self.primitive_op_id_gen = source.primitive_op_id_gen.clone();
+        self.primitive_op_group_id_gen = source.primitive_op_group_id_gen.clone();
+    }
+
+    pub(super) fn set_parent_transform(&mut self, parent_transform: ObjectTransform) {
+        self.parent_transform = parent_transform;
+    }
+
+    /// Sets `origin`, `rotation` and `scale` from `transform`
+    pub fn set_transform(&mut self, transform: ObjectTransform) {
+        self.origin = transform.origin;
+        self.rotation = transform.rotation;
+        self.scale = transform.scale;
     }
 
     // Getters
 
+    /// Places the primitive ops relative to the parent object
+    pub fn transform(&self) -> ObjectTransform {
+        ObjectTransform::new(self.origin, self.rotation, self.scale)
+    }
+
+    /// Places the primitive ops in the world, taking all parent objects into account
+    pub fn world_transform(&self) -> ObjectTransform {
+        self.parent_transform.mul_transform(&self.transform())
+    }
+
+    pub fn parent(&self) -> Option<ObjectId> {
+        self.parent
+    }
+
+    pub fn layer(&self) -> Option<LayerId> {
+        self.layer
+    }
+
+    pub fn instance_of(&self) -> Option<ObjectId> {
+        self.instance_of
+    }
+
+    pub fn groups(&self) -> &[PrimitiveOpGroup] {
+        &self.groups
+    }
+
+    pub fn get_group(&self, group_id: PrimitiveOpGroupId) -> Option<&PrimitiveOpGroup> {
+        self.groups.iter().find(|group| group.id() == group_id)
+    }
+
+    /// The primitive op tree flattened into the order that `map()` in `scene_geometry.frag`
+    /// evaluates it in.
+    pub fn evaluation_steps(&self) -> Vec<EvaluationStep<'_>> {
+        evaluation_steps(&self.primitive_ops, &self.groups)
+    }
+
     pub fn get_primitive_op(&self, get_primitive_op_id: PrimitiveOpId) -> Option<&PrimitiveOp> {
         self.primitive_ops.iter().find_map(|check_primitive_op| {
             if check_primitive_op.id() == get_primitive_op_id {
@@ -192,6 +477,8 @@ impl Object {
         new_blend: Option<f32>,
         new_albedo: Option<Vec3>,
         new_specular: Option<f32>,
+        new_modifiers: Option<Vec<DomainModifier>>,
+        new_surface_modifiers: Option<Vec<SurfaceModifier>>,
     ) -> Result<(), CollectionError> {
         let primitive_op_search_res = self.get_primitive_op_mut(primitive_op_id);
         let Some(primitive_op_ref) = primitive_op_search_res else {
@@ -207,6 +494,8 @@ impl Object {
             new_blend,
             new_albedo,
             new_specular,
+            new_modifiers,
+            new_surface_modifiers,
         )
     }
 
@@ -219,6 +508,8 @@ impl Object {
         new_blend: Option<f32>,
         new_albedo: Option<Vec3>,
         new_specular: Option<f32>,
+        new_modifiers: Option<Vec<DomainModifier>>,
+        new_surface_modifiers: Option<Vec<SurfaceModifier>>,
     ) -> Result<(), CollectionError> {
         let primitive_op_search_res = self.primitive_ops.get_mut(primitive_op_index);
         let Some(primitive_op_ref) = primitive_op_search_res else {
@@ -235,6 +526,8 @@ impl Object {
             new_blend,
             new_albedo,
             new_specular,
+            new_modifiers,
+            new_surface_modifiers,
         )
     }
 
@@ -242,34 +535,227 @@ impl Object {
         // avoiding this case should be the responsibility of the functions adding to `primtive_ops`
         debug_assert!(self.primitive_ops.len() <= MAX_PRIMITIVE_OP_COUNT);
 
-        let mut encoded_primitives = Vec::<PrimitiveOpPacket>::new();
-        for primitive_op in &self.primitive_ops {
-            let packet = create_primitive_op_packet(primitive_op, self.origin);
-            encoded_primitives.push(packet);
-        }
-        if self.primitive_ops.len() == 0 {
-            // having no primitive ops would probably break something on the gpu side so lets put a NOP here...
-            let packet = nop_primitive_op_packet();
-            encoded_primitives.push(packet);
-        }
-
+        let steps = self.evaluation_steps();
         let mut encoded_object = vec![
             object_id.raw_id() as PrimitiveOpBufferUnit,
-            self.primitive_ops.len() as PrimitiveOpBufferUnit,
+            steps.len() as PrimitiveOpBufferUnit,
         ];
-        let encoded_primitives_flattened: Vec<u32> =
-            encoded_primitives.into_iter().flatten().collect();
-        encoded_object.extend_from_slice(&encoded_primitives_flattened);
+        // the object transform is applied per draw so that instances can share the buffer
+        let encoded_primitive_ops = encode_primitive_ops(&steps, &ObjectTransform::IDENTITY);
+        encoded_object.extend_from_slice(&encoded_primitive_ops);
         encoded_object
     }
 
+    /// World space bounds of the object
     pub fn aabb(&self) -> Aabb {
-        let mut aabb = Aabb::new_zero();
-        for primitive_op in &self.primitive_ops {
-            aabb.union(primitive_op.primitive.aabb(primitive_op.transform));
+        self.world_transform().transform_aabb(&self.local_aabb())
+    }
+
+    /// Bounds of the shape produced by folding the primitive ops in the same order as `map()` in
+    /// `scene_geometry.frag`, before the object transform is applied.
+    pub fn local_aabb(&self) -> Aabb {
+        // `None` represents empty space, which is what the shader starts with. each open group
+        // gets its own entry like the result stack in the shader.
+        let mut aabb_stack: Vec<Option<Aabb>> = vec![None];
+        for step in self.evaluation_steps() {
+            match step {
+                EvaluationStep::GroupBegin(_) => aabb_stack.push(None),
+                EvaluationStep::Primitive(primitive_op) => {
+                    let current_aabb = aabb_stack.last_mut().expect("root is never popped");
+                    *current_aabb = combine_aabb(
+                        current_aabb.take(),
+                        primitive_op.aabb(),
+                        primitive_op.op,
+                        primitive_op.blend,
+                    );
+                }
+                EvaluationStep::GroupEnd(group) => {
+                    let group_aabb = aabb_stack.pop().flatten();
+                    let current_aabb = aabb_stack.last_mut().expect("root is never popped");
+                    match group_aabb {
+                        Some(group_aabb) => {
+                            *current_aabb = combine_aabb(
+                                current_aabb.take(),
+                                group_aabb,
+                                group.op,
+                                group.blend,
+                            );
+                        }
+                        // an empty group only affects intersections
+                        None if group.op == Operation::Intersection => *current_aabb = None,
+                        None => (),
+                    }
+                }
+            }
+        }
+
+        aabb_stack
+            .into_iter()
+            .next()
+            .flatten()
+            .unwrap_or_else(Aabb::new_zero)
+    }
+
+    // Private
+
+    fn check_group_id(&self, group_id: PrimitiveOpGroupId) -> Result<(), CollectionError> {
+        match self.get_group(group_id) {
+            Some(_) => Ok(()),
+            None => Err(CollectionError::InvalidId {
+                raw_id: group_id.raw_id(),
+            }),
+        }
+    }
+
+    /// Index of `before_primitive_op_id` or the end of the list if `None` or invalid
+    fn insert_index_before(&self, before_primitive_op_id: Option<PrimitiveOpId>) -> usize {
+        before_primitive_op_id
+            .and_then(|before_id| self.get_primitive_op_and_index(before_id))
+            .map_or(self.primitive_ops.len(), |(_, index)| index)
+    }
+
+    /// Whether `group` is `ancestor_id` or one of its sub-groups
+    fn is_in_group(
+        &self,
+        group: Option<PrimitiveOpGroupId>,
+        ancestor_id: PrimitiveOpGroupId,
+    ) -> bool {
+        group_ancestors(group, &self.groups)
+            .iter()
+            .any(|ancestor| ancestor.id() == ancestor_id)
+    }
+
+    /// Number of group levels from `group_id` down to its deepest sub-group, including itself
+    fn group_height(&self, group_id: PrimitiveOpGroupId) -> usize {
+        self.groups
+            .iter()
+            .map(|group| {
+                let ancestors = group_ancestors(Some(group.id()), &self.groups);
+                ancestors
+                    .iter()
+                    .position(|ancestor| ancestor.id() == group_id)
+                    .map_or(0, |depth| ancestors.len() - depth)
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Reorders `primitive_ops` so that the primitive ops of each group are next to each other.
+    /// Groups and primitive ops keep their order relative to their siblings, where a group is
+    /// positioned at its first primitive op.
+    fn sort_primitive_ops_by_group(&mut self) {
+        // first index of any primitive op in each group or its sub-groups
+        let mut group_first_index = std::collections::HashMap::<PrimitiveOpGroupId, usize>::new();
+        for (index, primitive_op) in self.primitive_ops.iter().enumerate() {
+            for ancestor in group_ancestors(primitive_op.group(), &self.groups) {
+                group_first_index.entry(ancestor.id()).or_insert(index);
+            }
         }
-        aabb.offset(self.origin);
-        aabb
+
+        let mut sorted_indices = Vec::with_capacity(self.primitive_ops.len());
+        self.append_sorted_indices(None, &group_first_index, &mut sorted_indices);
+
+        let mut primitive_ops: Vec<Option<PrimitiveOp>> = std::mem::take(&mut self.primitive_ops)
+            .into_iter()
+            .map(Some)
+            .collect();
+        self.primitive_ops = sorted_indices
+            .into_iter()
+            .filter_map(|index| primitive_ops[index].take())
+            .collect();
+    }
+
+    fn append_sorted_indices(
+        &self,
+        parent: Option<PrimitiveOpGroupId>,
+        group_first_index: &std::collections::HashMap<PrimitiveOpGroupId, usize>,
+        sorted_indices: &mut Vec<usize>,
+    ) {
+        // (first index, group) where group is `None` for primitive ops
+        let mut children: Vec<(usize, Option<PrimitiveOpGroupId>)> = self
+            .primitive_ops
+            .iter()
+            .enumerate()
+            .filter(|(_, primitive_op)| self.valid_group(primitive_op.group()) == parent)
+            .map(|(index, _)| (index, None))
+            .collect();
+        children.extend(
+            self.groups
+                .iter()
+                .filter(|group| self.valid_group(group.parent()) == parent)
+                .filter_map(|group| {
+                    let first_index = *group_first_index.get(&group.id())?;
+                    Some((first_index, Some(group.id())))
+                }),
+        );
+        children.sort_by_key(|(index, _)| *index);
+
+        for (index, group) in children {
+            match group {
+                Some(group_id) => {
+                    self.append_sorted_indices(Some(group_id), group_first_index, sorted_indices)
+                }
+                None => sorted_indices.push(index),
+            }
+        }
+    }
+
+    /// `group` or `None` if it doesn't exist
+    fn valid_group(&self, group: Option<PrimitiveOpGroupId>) -> Option<PrimitiveOpGroupId> {
+        group.filter(|group_id| self.get_group(*group_id).is_some())
+    }
+}
+
+fn combine_aabb(
+    current_aabb: Option<Aabb>,
+    primitive_aabb: Aabb,
+    op: Operation,
+    blend: f32,
+) -> Option<Aabb> {
+    match op {
+        Operation::Union => {
+            let mut aabb = primitive_aabb;
+            if let Some(current_aabb) = current_aabb {
+                aabb.union(current_aabb);
+            }
+            // the polynomial smooth min in `op_union` is at most blend/4 below the regular min
+            if blend > 0. {
+                aabb.grow(blend / 4.);
+            }
+            Some(aabb)
+        }
+        // chamfers, stairs and columns only fill in corners within `blend` of both shapes
+        Operation::ChamferUnion
+        | Operation::StairsUnion { .. }
+        | Operation::ColumnsUnion { .. } => {
+            let mut aabb = primitive_aabb;
+            if let Some(current_aabb) = current_aabb {
+                aabb.union(current_aabb);
+            }
+            aabb.grow(blend.max(0.));
+            Some(aabb)
+        }
+        // morphs and xor are never inside where both shapes are outside
+        Operation::Morph { .. } | Operation::Xor => {
+            let mut aabb = primitive_aabb;
+            if let Some(current_aabb) = current_aabb {
+                aabb.union(current_aabb);
+            }
+            Some(aabb)
+        }
+        Operation::Intersection => current_aabb?.intersection(&primitive_aabb),
+        // the tongue is raised up to `blend` out of the current shape
+        Operation::Tongue { .. } => current_aabb.map(|mut aabb| {
+            aabb.grow(blend.max(0.));
+            aabb
+        }),
+        // subtracting can only remove volume from the current shape
+        Operation::Subtraction
+        | Operation::ChamferSubtraction
+        | Operation::StairsSubtraction { .. }
+        | Operation::ColumnsSubtraction { .. }
+        | Operation::Groove { .. }
+        | Operation::Nop => current_aabb,
     }
 }
 
@@ -281,6 +767,8 @@ fn set_primitive_op_internal(
     new_blend: Option<f32>,
     new_albedo: Option<Vec3>,
     new_specular: Option<f32>,
+    new_modifiers: Option<Vec<DomainModifier>>,
+    new_surface_modifiers: Option<Vec<SurfaceModifier>>,
 ) -> Result<(), CollectionError> {
     if let Some(some_new_primitive) = new_primitive {
         primitive_op_ref.primitive = some_new_primitive;
@@ -300,5 +788,11 @@ fn set_primitive_op_internal(
     if let Some(some_new_specular) = new_specular {
         primitive_op_ref.specular = some_new_specular;
     }
+    if let Some(some_new_modifiers) = new_modifiers {
+        primitive_op_ref.modifiers = some_new_modifiers;
+    }
+    if let Some(some_new_surface_modifiers) = new_surface_modifiers {
+        primitive_op_ref.surface_modifiers = some_new_surface_modifiers;
+    }
     return Ok(());
 }
@@ -1,29 +1,40 @@
 use super::{
+    domain_modifier::DomainModifier,
+    layer::{Layer, LayerId},
+    material::{Material, MaterialId},
     object::{Object, ObjectId},
+    object_transform::ObjectTransform,
     objects_delta::{push_object_delta, ObjectDeltaOperation, ObjectsDelta},
     operation::Operation,
-    primitive_op::PrimitiveOpId,
+    primitive_op::{PrimitiveOp, PrimitiveOpId},
+    primitive_op_group::PrimitiveOpGroupId,
+    surface_modifier::SurfaceModifier,
 };
 use crate::{
     engine::{
         config_engine::DEFAULT_ORIGIN,
         primitives::{primitive::Primitive, primitive_transform::PrimitiveTransform},
+        sdf_evaluator::{ObjectSdf, RayHit},
     },
     helper::{
         more_errors::CollectionError,
         unique_id_gen::{UniqueIdError, UniqueIdGen, UniqueIdType},
     },
 };
-use glam::Vec3;
+use glam::{Quat, Vec3};
 #[allow(unused_imports)]
 use log::{debug, error, info, trace, warn};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// Should only be one per engine instance.
 pub struct ObjectCollection {
     unique_id_gen: UniqueIdGen<ObjectId>,
     objects: BTreeMap<ObjectId, Object>,
     objects_delta_accumulation: ObjectsDelta,
+    layer_id_gen: UniqueIdGen<LayerId>,
+    layers: BTreeMap<LayerId, Layer>,
+    material_id_gen: UniqueIdGen<MaterialId>,
+    materials: BTreeMap<MaterialId, Material>,
 }
 
 impl ObjectCollection {
@@ -32,6 +43,10 @@ impl ObjectCollection {
             unique_id_gen: UniqueIdGen::new(),
             objects: Default::default(),
             objects_delta_accumulation: Default::default(),
+            layer_id_gen: UniqueIdGen::new(),
+            layers: Default::default(),
+            material_id_gen: UniqueIdGen::new(),
+            materials: Default::default(),
         }
     }
 
@@ -51,33 +66,140 @@ impl ObjectCollection {
         Ok(self.new_object_internal(object_id, name, origin))
     }
 
-    pub fn push_object(&mut self, new_object: Object) -> Result<ObjectId, UniqueIdError> {
+    /// Parent and instance links to objects that aren't in the collection are cleared, as are
+    /// invalid layers and materials.
+    pub fn push_object(&mut self, mut new_object: Object) -> Result<ObjectId, UniqueIdError> {
         let new_object_id = self.unique_id_gen.new_id()?;
+        self.clear_invalid_layer(&mut new_object);
+        self.clear_invalid_materials(&mut new_object.primitive_ops);
+        if let Some(parent_id) = new_object.parent() {
+            if !self.objects.contains_key(&parent_id) {
+                new_object.set_parent(None);
+            }
+        }
+        if let Some(definition_id) = new_object.instance_of() {
+            new_object.set_instance_of(self.definition_id(definition_id).ok());
+        }
         self.objects.insert(new_object_id, new_object);
-        self.mark_object_for_gpu_update(new_object_id)
+        self.update_world_transforms(new_object_id)
             .expect("new object just created");
         Ok(new_object_id)
     }
 
+    /// Pushes the layers, materials and objects of a saved scene, paired with the ids they had at
+    /// the time. New ids are assigned and links to them are remapped, see [`Self::push_objects`].
+    pub fn push_scene(
+        &mut self,
+        new_layers: impl IntoIterator<Item = (LayerId, Layer)>,
+        new_materials: impl IntoIterator<Item = (MaterialId, Material)>,
+        new_objects: impl IntoIterator<Item = (ObjectId, Object)>,
+    ) -> Result<Vec<ObjectId>, UniqueIdError> {
+        let mut new_layer_ids_by_old_id = HashMap::<LayerId, LayerId>::new();
+        for (old_layer_id, new_layer) in new_layers {
+            let new_layer_id = self.layer_id_gen.new_id()?;
+            self.layers.insert(new_layer_id, new_layer);
+            new_layer_ids_by_old_id.insert(old_layer_id, new_layer_id);
+        }
+
+        let mut new_material_ids_by_old_id = HashMap::<MaterialId, MaterialId>::new();
+        for (old_material_id, new_material) in new_materials {
+            let new_material_id = self.material_id_gen.new_id()?;
+            self.materials.insert(new_material_id, new_material);
+            new_material_ids_by_old_id.insert(old_material_id, new_material_id);
+        }
+
+        let new_objects = new_objects
+            .into_iter()
+            .map(|(old_object_id, mut new_object)| {
+                let new_layer_id = new_object
+                    .layer()
+                    .and_then(|old_layer_id| new_layer_ids_by_old_id.get(&old_layer_id));
+                new_object.set_layer(new_layer_id.copied());
+                for primitive_op in new_object.primitive_ops.iter_mut() {
+                    let new_material_id = primitive_op.material.and_then(|old_material_id| {
+                        new_material_ids_by_old_id.get(&old_material_id)
+                    });
+                    primitive_op.material = new_material_id.copied();
+                }
+                (old_object_id, new_object)
+            });
+        self.push_objects(new_objects)
+    }
+
+    /// Pushes objects that were saved or copied together, paired with the ids they had at the
+    /// time. New ids are assigned and parent and instance links between the objects are remapped
+    /// to them. Invalid layers and materials are cleared.
     pub fn push_objects(
         &mut self,
-        new_objects: impl IntoIterator<Item = Object>,
+        new_objects: impl IntoIterator<Item = (ObjectId, Object)>,
     ) -> Result<Vec<ObjectId>, UniqueIdError> {
-        let mut new_object_ids: Vec<ObjectId> = Vec::new();
-        for new_object in new_objects {
-            let new_object_id = self.push_object(new_object)?;
-            new_object_ids.push(new_object_id);
+        self.push_objects_internal(new_objects, false)
+    }
+
+    /// Copies an object and its descendants. The copy has the same parent as the original and
+    /// instances stay instances of the same definition. Returns the id of the copy.
+    pub fn duplicate_object(&mut self, object_id: ObjectId) -> Result<ObjectId, CollectionError> {
+        let object_tree = self.object_tree_internal(object_id)?;
+        let new_object_ids = self.push_objects_internal(object_tree, true)?;
+        Ok(new_object_ids[0])
+    }
+
+    /// Creates an object that shares the primitive ops of `object_id`, with the same transform
+    /// and parent. Instances of an instance share the primitive ops of the original definition.
+    /// Returns the id of the instance.
+    pub fn new_instance(&mut self, object_id: ObjectId) -> Result<ObjectId, CollectionError> {
+        let definition_id = self.definition_id(object_id)?;
+        let source_object = self
+            .get_object(object_id)
+            .ok_or(CollectionError::InvalidId {
+                raw_id: object_id.raw_id(),
+            })?;
+
+        let mut instance = Object::new(
+            format!("{} instance", source_object.name),
+            source_object.origin,
+        );
+        instance.rotation = source_object.rotation;
+        instance.scale = source_object.scale;
+        instance.set_parent(source_object.parent());
+        instance.set_instance_of(Some(definition_id));
+
+        let instance_id = self.push_object(instance)?;
+        Ok(instance_id)
+    }
+
+    /// Gives an instance its own copy of the primitive ops of its definition so that it can be
+    /// edited separately. Does nothing if the object isn't an instance.
+    pub fn make_instance_unique(&mut self, object_id: ObjectId) -> Result<(), CollectionError> {
+        let definition_id = self.definition_id(object_id)?;
+        if definition_id == object_id {
+            return Ok(());
         }
-        Ok(new_object_ids)
+
+        let definition = self
+            .get_object(definition_id)
+            .ok_or(CollectionError::InvalidId {
+                raw_id: definition_id.raw_id(),
+            })?
+            .clone();
+        let object_mut_ref = self.get_object_mut(object_id)?;
+        object_mut_ref.copy_primitive_ops_from(&definition);
+        object_mut_ref.set_instance_of(None);
+        self.push_gpu_update(object_id)
     }
 
+    /// The parent and instance definition of `new_object` are ignored, use
+    /// [`Self::set_object_parent`] and [`Self::make_instance_unique`] to change those.
     pub fn set_object(
         &mut self,
         object_id: ObjectId,
-        new_object: Object,
+        mut new_object: Object,
     ) -> Result<(), CollectionError> {
         let object_mut_ref = self.get_object_mut(object_id)?;
+        new_object.set_parent(object_mut_ref.parent());
+        new_object.set_instance_of(object_mut_ref.instance_of());
         *object_mut_ref = new_object;
+        self.update_world_transforms(object_id)?;
         self.mark_object_for_gpu_update(object_id)
     }
 
@@ -92,6 +214,68 @@ impl ObjectCollection {
         Ok(())
     }
 
+    pub fn set_object_visible(
+        &mut self,
+        object_id: ObjectId,
+        visible: bool,
+    ) -> Result<(), CollectionError> {
+        let object_mut_ref = self.get_object_mut(object_id)?;
+        object_mut_ref.visible = visible;
+        self.push_gpu_update(object_id)
+    }
+
+    pub fn set_object_locked(
+        &mut self,
+        object_id: ObjectId,
+        locked: bool,
+    ) -> Result<(), CollectionError> {
+        let object_mut_ref = self.get_object_mut(object_id)?;
+        object_mut_ref.locked = locked;
+        // don't need to mark for update becuase locking is only checked by the engine
+        Ok(())
+    }
+
+    pub fn set_object_selectable(
+        &mut self,
+        object_id: ObjectId,
+        selectable: bool,
+    ) -> Result<(), CollectionError> {
+        let object_mut_ref = self.get_object_mut(object_id)?;
+        object_mut_ref.selectable = selectable;
+        // don't need to mark for update becuase clicks are filtered by the engine
+        Ok(())
+    }
+
+    /// `None` removes the object from its layer.
+    pub fn set_object_layer(
+        &mut self,
+        object_id: ObjectId,
+        layer_id: Option<LayerId>,
+    ) -> Result<(), CollectionError> {
+        if let Some(some_layer_id) = layer_id {
+            if !self.layers.contains_key(&some_layer_id) {
+                return Err(CollectionError::InvalidId {
+                    raw_id: some_layer_id.raw_id(),
+                });
+            }
+        }
+        let object_mut_ref = self.get_object_mut(object_id)?;
+        object_mut_ref.set_layer(layer_id);
+        // layer visibility is sent to the gpu
+        self.push_gpu_update(object_id)
+    }
+
+    pub fn set_object_tags(
+        &mut self,
+        object_id: ObjectId,
+        new_tags: Vec<String>,
+    ) -> Result<(), CollectionError> {
+        let object_mut_ref = self.get_object_mut(object_id)?;
+        object_mut_ref.tags = new_tags;
+        // don't need to mark for update becuase the tags aren't sent to gpu
+        Ok(())
+    }
+
     pub fn set_object_origin(
         &mut self,
         object_id: ObjectId,
@@ -99,9 +283,80 @@ impl ObjectCollection {
     ) -> Result<(), CollectionError> {
         let object_mut_ref = self.get_object_mut(object_id)?;
         object_mut_ref.origin = new_origin;
-        self.mark_object_for_gpu_update(object_id)
+        self.update_world_transforms(object_id)
+    }
+
+    pub fn set_object_rotation(
+        &mut self,
+        object_id: ObjectId,
+        new_rotation: Quat,
+    ) -> Result<(), CollectionError> {
+        let object_mut_ref = self.get_object_mut(object_id)?;
+        object_mut_ref.rotation = new_rotation.normalize();
+        self.update_world_transforms(object_id)
+    }
+
+    pub fn set_object_scale(
+        &mut self,
+        object_id: ObjectId,
+        new_scale: f32,
+    ) -> Result<(), CollectionError> {
+        let object_mut_ref = self.get_object_mut(object_id)?;
+        object_mut_ref.scale = new_scale;
+        self.update_world_transforms(object_id)
+    }
+
+    /// Sets the origin, rotation and scale in one go.
+    pub fn set_object_transform(
+        &mut self,
+        object_id: ObjectId,
+        new_transform: ObjectTransform,
+    ) -> Result<(), CollectionError> {
+        let object_mut_ref = self.get_object_mut(object_id)?;
+        object_mut_ref.set_transform(ObjectTransform {
+            rotation: new_transform.rotation.normalize(),
+            ..new_transform
+        });
+        self.update_world_transforms(object_id)
+    }
+
+    /// Moves an object to a new parent (`None` for the root) while keeping its place in the world.
+    pub fn set_object_parent(
+        &mut self,
+        object_id: ObjectId,
+        new_parent_id: Option<ObjectId>,
+    ) -> Result<(), CollectionError> {
+        let new_parent_transform = match new_parent_id {
+            Some(new_parent_id) => {
+                if new_parent_id == object_id
+                    || self.descendants(object_id).contains(&new_parent_id)
+                {
+                    return Err(CollectionError::InvalidParent {
+                        raw_id: new_parent_id.raw_id(),
+                    });
+                }
+                let new_parent =
+                    self.get_object(new_parent_id)
+                        .ok_or(CollectionError::InvalidId {
+                            raw_id: new_parent_id.raw_id(),
+                        })?;
+                new_parent.world_transform()
+            }
+            None => ObjectTransform::IDENTITY,
+        };
+
+        let object_mut_ref = self.get_object_mut(object_id)?;
+        let new_transform = new_parent_transform
+            .inverse()
+            .mul_transform(&object_mut_ref.world_transform());
+        object_mut_ref.set_transform(new_transform);
+        object_mut_ref.set_parent(new_parent_id);
+        self.update_world_transforms(object_id)
     }
 
+    // The primitive op functions below modify the definition when given an instance, which
+    // updates all instances of it.
+
     pub fn push_op_to_object(
         &mut self,
         object_id: ObjectId,
@@ -112,10 +367,37 @@ impl ObjectCollection {
         albedo: Vec3,
         specular: f32,
     ) -> Result<PrimitiveOpId, CollectionError> {
-        let object_mut_ref = self.get_object_mut(object_id)?;
+        let definition_id = self.definition_id(object_id)?;
+        let object_mut_ref = self.get_object_mut(definition_id)?;
         let primitive_op_id =
             object_mut_ref.push_primitive_op(primitive, transform, op, blend, albedo, specular)?;
-        _ = self.mark_object_for_gpu_update(object_id);
+        _ = self.mark_object_for_gpu_update(definition_id);
+        Ok(primitive_op_id)
+    }
+
+    pub fn duplicate_primitive_op_in_object(
+        &mut self,
+        object_id: ObjectId,
+        primitive_op_id: PrimitiveOpId,
+    ) -> Result<PrimitiveOpId, CollectionError> {
+        let definition_id = self.definition_id(object_id)?;
+        let object_mut_ref = self.get_object_mut(definition_id)?;
+        let duplicate_id = object_mut_ref.duplicate_primitive_op(primitive_op_id)?;
+        _ = self.mark_object_for_gpu_update(definition_id);
+        Ok(duplicate_id)
+    }
+
+    /// The material of `primitive_op` is cleared if it isn't in the collection.
+    pub fn push_primitive_op_copy_to_object(
+        &mut self,
+        object_id: ObjectId,
+        mut primitive_op: PrimitiveOp,
+    ) -> Result<PrimitiveOpId, CollectionError> {
+        self.clear_invalid_materials(std::slice::from_mut(&mut primitive_op));
+        let definition_id = self.definition_id(object_id)?;
+        let object_mut_ref = self.get_object_mut(definition_id)?;
+        let primitive_op_id = object_mut_ref.push_primitive_op_copy(primitive_op)?;
+        _ = self.mark_object_for_gpu_update(definition_id);
         Ok(primitive_op_id)
     }
 
@@ -129,8 +411,11 @@ impl ObjectCollection {
         new_blend: Option<f32>,
         new_albedo: Option<Vec3>,
         new_specular: Option<f32>,
+        new_modifiers: Option<Vec<DomainModifier>>,
+        new_surface_modifiers: Option<Vec<SurfaceModifier>>,
     ) -> Result<(), CollectionError> {
-        let object_mut_ref = self.get_object_mut(object_id)?;
+        let definition_id = self.definition_id(object_id)?;
+        let object_mut_ref = self.get_object_mut(definition_id)?;
         object_mut_ref.set_primitive_op_id(
             primitive_op_id,
             new_primitive,
@@ -139,8 +424,10 @@ impl ObjectCollection {
             new_blend,
             new_albedo,
             new_specular,
+            new_modifiers,
+            new_surface_modifiers,
         )?;
-        self.mark_object_for_gpu_update(object_id)
+        self.mark_object_for_gpu_update(definition_id)
     }
 
     pub fn set_primitive_op_index_in_object(
@@ -153,8 +440,11 @@ impl ObjectCollection {
         new_blend: Option<f32>,
         new_albedo: Option<Vec3>,
         new_specular: Option<f32>,
+        new_modifiers: Option<Vec<DomainModifier>>,
+        new_surface_modifiers: Option<Vec<SurfaceModifier>>,
     ) -> Result<(), CollectionError> {
-        let object_mut_ref = self.get_object_mut(object_id)?;
+        let definition_id = self.definition_id(object_id)?;
+        let object_mut_ref = self.get_object_mut(definition_id)?;
         object_mut_ref.set_primitive_op_index(
             primitive_op_index,
             new_primitive,
@@ -163,8 +453,37 @@ impl ObjectCollection {
             new_blend,
             new_albedo,
             new_specular,
+            new_modifiers,
+            new_surface_modifiers,
         )?;
-        self.mark_object_for_gpu_update(object_id)
+        self.mark_object_for_gpu_update(definition_id)
+    }
+
+    /// `material_id` must be `None` or a material of this collection.
+    pub fn set_primitive_op_material_in_object(
+        &mut self,
+        object_id: ObjectId,
+        primitive_op_id: PrimitiveOpId,
+        material_id: Option<MaterialId>,
+        material_override: bool,
+    ) -> Result<(), CollectionError> {
+        if let Some(material_id) = material_id {
+            if !self.materials.contains_key(&material_id) {
+                return Err(CollectionError::InvalidId {
+                    raw_id: material_id.raw_id(),
+                });
+            }
+        }
+        let definition_id = self.definition_id(object_id)?;
+        let object_mut_ref = self.get_object_mut(definition_id)?;
+        let primitive_op_mut_ref = object_mut_ref.get_primitive_op_mut(primitive_op_id).ok_or(
+            CollectionError::InvalidId {
+                raw_id: primitive_op_id.raw_id(),
+            },
+        )?;
+        primitive_op_mut_ref.material = material_id;
+        primitive_op_mut_ref.material_override = material_override;
+        self.mark_object_for_gpu_update(definition_id)
     }
 
     pub fn shift_primitive_ops_in_object(
@@ -173,9 +492,73 @@ impl ObjectCollection {
         source_index: usize,
         target_index: usize,
     ) -> Result<(), CollectionError> {
-        let object_mut_ref = self.get_object_mut(object_id)?;
+        let definition_id = self.definition_id(object_id)?;
+        let object_mut_ref = self.get_object_mut(definition_id)?;
         object_mut_ref.shift_primitive_ops(source_index, target_index)?;
-        self.mark_object_for_gpu_update(object_id)
+        self.mark_object_for_gpu_update(definition_id)
+    }
+
+    pub fn move_primitive_op_in_object(
+        &mut self,
+        object_id: ObjectId,
+        primitive_op_id: PrimitiveOpId,
+        group_id: Option<PrimitiveOpGroupId>,
+        before_primitive_op_id: Option<PrimitiveOpId>,
+    ) -> Result<(), CollectionError> {
+        let definition_id = self.definition_id(object_id)?;
+        let object_mut_ref = self.get_object_mut(definition_id)?;
+        object_mut_ref.move_primitive_op(primitive_op_id, group_id, before_primitive_op_id)?;
+        self.mark_object_for_gpu_update(definition_id)
+    }
+
+    pub fn push_group_to_object(
+        &mut self,
+        object_id: ObjectId,
+        parent_group_id: Option<PrimitiveOpGroupId>,
+    ) -> Result<PrimitiveOpGroupId, CollectionError> {
+        let definition_id = self.definition_id(object_id)?;
+        let object_mut_ref = self.get_object_mut(definition_id)?;
+        let group_id = object_mut_ref.push_group(parent_group_id)?;
+        // empty groups don't change the shape so no gpu update needed
+        Ok(group_id)
+    }
+
+    pub fn remove_group_from_object(
+        &mut self,
+        object_id: ObjectId,
+        group_id: PrimitiveOpGroupId,
+    ) -> Result<(), CollectionError> {
+        let definition_id = self.definition_id(object_id)?;
+        let object_mut_ref = self.get_object_mut(definition_id)?;
+        object_mut_ref.remove_group(group_id)?;
+        self.mark_object_for_gpu_update(definition_id)
+    }
+
+    pub fn set_group_in_object(
+        &mut self,
+        object_id: ObjectId,
+        group_id: PrimitiveOpGroupId,
+        new_name: Option<String>,
+        new_operation: Option<Operation>,
+        new_blend: Option<f32>,
+    ) -> Result<(), CollectionError> {
+        let definition_id = self.definition_id(object_id)?;
+        let object_mut_ref = self.get_object_mut(definition_id)?;
+        object_mut_ref.set_group(group_id, new_name, new_operation, new_blend)?;
+        self.mark_object_for_gpu_update(definition_id)
+    }
+
+    pub fn move_group_in_object(
+        &mut self,
+        object_id: ObjectId,
+        group_id: PrimitiveOpGroupId,
+        parent_group_id: Option<PrimitiveOpGroupId>,
+        before_primitive_op_id: Option<PrimitiveOpId>,
+    ) -> Result<(), CollectionError> {
+        let definition_id = self.definition_id(object_id)?;
+        let object_mut_ref = self.get_object_mut(definition_id)?;
+        object_mut_ref.move_group(group_id, parent_group_id, before_primitive_op_id)?;
+        self.mark_object_for_gpu_update(definition_id)
     }
 
     pub fn remove_primitive_op_id_from_object(
@@ -183,9 +566,10 @@ impl ObjectCollection {
         object_id: ObjectId,
         remove_primitive_op_id: PrimitiveOpId,
     ) -> Result<usize, CollectionError> {
-        let object_mut_ref = self.get_object_mut(object_id)?;
+        let definition_id = self.definition_id(object_id)?;
+        let object_mut_ref = self.get_object_mut(definition_id)?;
         let index = object_mut_ref.remove_primitive_op_id(remove_primitive_op_id)?;
-        _ = self.mark_object_for_gpu_update(object_id);
+        _ = self.mark_object_for_gpu_update(definition_id);
         Ok(index)
     }
 
@@ -194,13 +578,20 @@ impl ObjectCollection {
         object_id: ObjectId,
         remove_primitive_op_index: usize,
     ) -> Result<PrimitiveOpId, CollectionError> {
-        let object_mut_ref = self.get_object_mut(object_id)?;
+        let definition_id = self.definition_id(object_id)?;
+        let object_mut_ref = self.get_object_mut(definition_id)?;
         let id = object_mut_ref.remove_primitive_op_index(remove_primitive_op_index)?;
-        _ = self.mark_object_for_gpu_update(object_id);
+        _ = self.mark_object_for_gpu_update(definition_id);
         Ok(id)
     }
 
+    /// Children of the removed object are moved to its parent, keeping their place in the world.
+    /// Instances of the removed object get their own copy of its primitive ops.
     pub fn remove_object(&mut self, object_id: ObjectId) -> Result<Object, CollectionError> {
+        for instance_id in self.instances(object_id) {
+            self.make_instance_unique(instance_id)?;
+        }
+
         let removed_object_option = self.objects.remove(&object_id);
 
         if let Some(removed_object) = removed_object_option {
@@ -209,6 +600,17 @@ impl ObjectCollection {
                 info!("{}", e);
             }
 
+            // children move to the removed object's parent while keeping their place in the world
+            let removed_transform = removed_object.transform();
+            for child_id in self.children(object_id) {
+                if let Some(child) = self.objects.get_mut(&child_id) {
+                    child.set_transform(removed_transform.mul_transform(&child.transform()));
+                    child.set_parent(removed_object.parent());
+                }
+                // the cached parent transform still includes the removed object
+                self.update_parent_transform(child_id)?;
+            }
+
             // record changed data to update the gpu
             self.push_object_delta(object_id, ObjectDeltaOperation::Remove);
 
@@ -220,6 +622,297 @@ impl ObjectCollection {
         }
     }
 
+    /// Removes an object and all of its descendants. Returns the ids of the removed objects.
+    pub fn remove_object_and_descendants(
+        &mut self,
+        object_id: ObjectId,
+    ) -> Result<Vec<ObjectId>, CollectionError> {
+        if !self.objects.contains_key(&object_id) {
+            return Err(CollectionError::InvalidId {
+                raw_id: object_id.raw_id(),
+            });
+        }
+
+        let mut removed_object_ids = vec![object_id];
+        removed_object_ids.append(&mut self.descendants(object_id));
+        // deepest first so that nothing gets reparented
+        for &removed_object_id in removed_object_ids.iter().rev() {
+            self.remove_object(removed_object_id)?;
+        }
+        Ok(removed_object_ids)
+    }
+
+    pub fn new_layer(&mut self, name: impl Into<String>) -> Result<LayerId, UniqueIdError> {
+        let layer_id = self.layer_id_gen.new_id()?;
+        self.layers.insert(layer_id, Layer::new(name.into()));
+        Ok(layer_id)
+    }
+
+    /// Objects in the removed layer are left without a layer.
+    pub fn remove_layer(&mut self, layer_id: LayerId) -> Result<Layer, CollectionError> {
+        let Some(removed_layer) = self.layers.remove(&layer_id) else {
+            return Err(CollectionError::InvalidId {
+                raw_id: layer_id.raw_id(),
+            });
+        };
+        if let Err(e) = self.layer_id_gen.recycle_id(layer_id) {
+            info!("{}", e);
+        }
+
+        for object_id in self.objects_in_layer(Some(layer_id)) {
+            self.get_object_mut(object_id)?.set_layer(None);
+            self.push_gpu_update(object_id)?;
+        }
+        Ok(removed_layer)
+    }
+
+    pub fn set_layer_name(
+        &mut self,
+        layer_id: LayerId,
+        new_name: String,
+    ) -> Result<(), CollectionError> {
+        self.get_layer_mut(layer_id)?.name = new_name;
+        Ok(())
+    }
+
+    /// Hides or shows all objects in the layer. Their own `visible` flags are left as they are.
+    pub fn set_layer_visible(
+        &mut self,
+        layer_id: LayerId,
+        visible: bool,
+    ) -> Result<(), CollectionError> {
+        self.get_layer_mut(layer_id)?.visible = visible;
+        for object_id in self.objects_in_layer(Some(layer_id)) {
+            self.push_gpu_update(object_id)?;
+        }
+        Ok(())
+    }
+
+    pub fn set_layer_selectable(
+        &mut self,
+        layer_id: LayerId,
+        selectable: bool,
+    ) -> Result<(), CollectionError> {
+        self.get_layer_mut(layer_id)?.selectable = selectable;
+        Ok(())
+    }
+
+    pub fn layers(&self) -> &BTreeMap<LayerId, Layer> {
+        &self.layers
+    }
+
+    pub fn get_layer(&self, layer_id: LayerId) -> Option<&Layer> {
+        self.layers.get(&layer_id)
+    }
+
+    /// Objects in `layer_id`, or objects without a layer for `None`
+    pub fn objects_in_layer(&self, layer_id: Option<LayerId>) -> Vec<ObjectId> {
+        self.objects
+            .iter()
+            .filter(|(_, object)| object.layer() == layer_id)
+            .map(|(&object_id, _)| object_id)
+            .collect()
+    }
+
+    pub fn objects_with_tag(&self, tag: &str) -> Vec<ObjectId> {
+        self.objects
+            .iter()
+            .filter(|(_, object)| object.tags.iter().any(|object_tag| object_tag == tag))
+            .map(|(&object_id, _)| object_id)
+            .collect()
+    }
+
+    /// Whether the object and its layer are visible. `false` if the object doesn't exist.
+    pub fn is_object_visible(&self, object_id: ObjectId) -> bool {
+        let Some(object) = self.objects.get(&object_id) else {
+            return false;
+        };
+        let layer_visible = match object
+            .layer()
+            .and_then(|layer_id| self.layers.get(&layer_id))
+        {
+            Some(layer) => layer.visible,
+            None => true,
+        };
+        object.visible && layer_visible
+    }
+
+    /// Whether the object and its layer are selectable. `false` if the object doesn't exist.
+    pub fn is_object_selectable(&self, object_id: ObjectId) -> bool {
+        let Some(object) = self.objects.get(&object_id) else {
+            return false;
+        };
+        let layer_selectable = match object
+            .layer()
+            .and_then(|layer_id| self.layers.get(&layer_id))
+        {
+            Some(layer) => layer.selectable,
+            None => true,
+        };
+        object.selectable && layer_selectable
+    }
+
+    pub fn new_material(&mut self, name: impl Into<String>) -> Result<MaterialId, UniqueIdError> {
+        let material_id = self.material_id_gen.new_id()?;
+        self.materials
+            .insert(material_id, Material::new(name.into()));
+        Ok(material_id)
+    }
+
+    /// Primitive ops using the removed material keep its albedo and specular.
+    pub fn remove_material(
+        &mut self,
+        material_id: MaterialId,
+    ) -> Result<Material, CollectionError> {
+        let Some(removed_material) = self.materials.remove(&material_id) else {
+            return Err(CollectionError::InvalidId {
+                raw_id: material_id.raw_id(),
+            });
+        };
+        if let Err(e) = self.material_id_gen.recycle_id(material_id) {
+            info!("{}", e);
+        }
+
+        for object_id in self.objects_using_material(material_id) {
+            let object_mut_ref = self.get_object_mut(object_id)?;
+            for primitive_op in object_mut_ref.primitive_ops.iter_mut() {
+                if primitive_op.material != Some(material_id) {
+                    continue;
+                }
+                if !primitive_op.material_override {
+                    primitive_op.albedo = removed_material.albedo;
+                    primitive_op.specular = removed_material.specular;
+                }
+                primitive_op.material = None;
+                primitive_op.material_override = false;
+            }
+            self.mark_object_for_gpu_update(object_id)?;
+        }
+        Ok(removed_material)
+    }
+
+    /// Updates every object that uses the material.
+    pub fn set_material(
+        &mut self,
+        material_id: MaterialId,
+        new_material: Material,
+    ) -> Result<(), CollectionError> {
+        let material_mut_ref =
+            self.materials
+                .get_mut(&material_id)
+                .ok_or(CollectionError::InvalidId {
+                    raw_id: material_id.raw_id(),
+                })?;
+        *material_mut_ref = new_material;
+
+        for object_id in self.objects_using_material(material_id) {
+            self.mark_object_for_gpu_update(object_id)?;
+        }
+        Ok(())
+    }
+
+    pub fn materials(&self) -> &BTreeMap<MaterialId, Material> {
+        &self.materials
+    }
+
+    pub fn get_material(&self, material_id: MaterialId) -> Option<&Material> {
+        self.materials.get(&material_id)
+    }
+
+    /// Objects with primitive ops that reference the material. Instances aren't included because
+    /// the primitive ops belong to their definitions.
+    pub fn objects_using_material(&self, material_id: MaterialId) -> Vec<ObjectId> {
+        self.objects
+            .iter()
+            .filter(|(_, object)| {
+                object
+                    .primitive_ops
+                    .iter()
+                    .any(|primitive_op| primitive_op.material == Some(material_id))
+            })
+            .map(|(&object_id, _)| object_id)
+            .collect()
+    }
+
+    /// Clone of the object as it's sent to the gpu. Instances get the primitive ops of their
+    /// definition, and primitive ops get the albedo and specular of their material.
+    pub fn resolved_object(&self, object_id: ObjectId) -> Option<Object> {
+        let object = self.objects.get(&object_id)?;
+        let mut resolved_object = object.clone();
+        resolved_object.visible = self.is_object_visible(object_id);
+        if let Some(definition) = object
+            .instance_of()
+            .and_then(|definition_id| self.objects.get(&definition_id))
+        {
+            resolved_object.copy_primitive_ops_from(definition);
+        }
+        for primitive_op in resolved_object.primitive_ops.iter_mut() {
+            let material = primitive_op
+                .active_material()
+                .and_then(|material_id| self.materials.get(&material_id));
+            if let Some(material) = material {
+                primitive_op.albedo = material.albedo;
+                primitive_op.specular = material.specular;
+            }
+        }
+        Some(resolved_object)
+    }
+
+    /// Sphere traces the visible objects on the cpu and returns the closest surface hit within
+    /// `max_dist` of `origin`. Unlike picking via the renderer, the result is available
+    /// immediately and for any ray.
+    pub fn ray_cast(&self, origin: Vec3, direction: Vec3, max_dist: f32) -> Option<RayHit> {
+        let direction = direction.try_normalize()?;
+
+        // cull by the bounds first, nearest to the origin first. instances are placed with
+        // their own transform but use the primitive ops of their definition.
+        let mut candidates = Vec::<(f32, f32, ObjectId, &Object, &Object)>::new();
+        for (&object_id, object) in self.objects.iter() {
+            if !self.is_object_visible(object_id) {
+                continue;
+            }
+            let Some(definition) = self.definition(object_id) else {
+                continue;
+            };
+            let aabb = object
+                .world_transform()
+                .transform_aabb(&definition.local_aabb());
+            let Some((enter_dist, exit_dist)) = aabb.ray_intersection(origin, direction) else {
+                continue;
+            };
+            if enter_dist < max_dist {
+                candidates.push((enter_dist.max(0.), exit_dist, object_id, object, definition));
+            }
+        }
+        candidates.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut closest_hit: Option<RayHit> = None;
+        for (enter_dist, exit_dist, object_id, object, definition) in candidates {
+            // the candidates are sorted, so once a box starts behind the closest hit the rest do
+            let max_dist = closest_hit.map_or(max_dist, |hit| hit.distance);
+            if enter_dist >= max_dist {
+                break;
+            }
+
+            let sdf = ObjectSdf::with_transform(definition, &object.world_transform());
+            let Some((distance, sdf_result)) =
+                sdf.ray_march(origin, direction, enter_dist, exit_dist.min(max_dist))
+            else {
+                continue;
+            };
+            let position = origin + direction * distance;
+            closest_hit = Some(RayHit {
+                object_id,
+                primitive_op_index: sdf_result.primitive_op_index(),
+                distance,
+                position,
+                normal: sdf.normal(position),
+            });
+        }
+
+        closest_hit
+    }
+
     /// Returns a description of the changes to objects since last call to this function.
     pub fn get_and_clear_objects_delta(&mut self) -> ObjectsDelta {
         std::mem::take(&mut self.objects_delta_accumulation)
@@ -233,11 +926,84 @@ impl ObjectCollection {
         self.objects.get(&object_id)
     }
 
+    /// The object whose primitive ops are used by `object_id`. That's the definition if the
+    /// object is an instance, otherwise the object itself.
+    pub fn definition(&self, object_id: ObjectId) -> Option<&Object> {
+        let object = self.objects.get(&object_id)?;
+        match object.instance_of() {
+            Some(definition_id) => self.objects.get(&definition_id),
+            None => Some(object),
+        }
+    }
+
+    /// Objects that are instances of `object_id`
+    pub fn instances(&self, object_id: ObjectId) -> Vec<ObjectId> {
+        self.objects
+            .iter()
+            .filter(|(_, object)| object.instance_of() == Some(object_id))
+            .map(|(&instance_id, _)| instance_id)
+            .collect()
+    }
+
+    /// Objects whose parent is `object_id`
+    pub fn children(&self, object_id: ObjectId) -> Vec<ObjectId> {
+        self.objects
+            .iter()
+            .filter(|(_, object)| object.parent() == Some(object_id))
+            .map(|(&child_id, _)| child_id)
+            .collect()
+    }
+
+    /// Clones of an object and its descendants paired with their ids, parents first. Can be
+    /// passed to [`Self::push_objects`] to make a copy of the hierarchy. Instances of objects
+    /// outside of the hierarchy are given a copy of the primitive ops of their definition.
+    pub fn object_tree(
+        &self,
+        object_id: ObjectId,
+    ) -> Result<Vec<(ObjectId, Object)>, CollectionError> {
+        let mut object_tree = self.object_tree_internal(object_id)?;
+        let tree_object_ids: Vec<ObjectId> = object_tree.iter().map(|(id, _)| *id).collect();
+        for (_, object) in object_tree.iter_mut() {
+            let Some(definition_id) = object.instance_of() else {
+                continue;
+            };
+            if tree_object_ids.contains(&definition_id) {
+                continue;
+            }
+            if let Some(definition) = self.get_object(definition_id) {
+                object.copy_primitive_ops_from(definition);
+            }
+            object.set_instance_of(None);
+        }
+        Ok(object_tree)
+    }
+
+    /// All objects below `object_id` in the hierarchy, parents before their children.
+    pub fn descendants(&self, object_id: ObjectId) -> Vec<ObjectId> {
+        let mut descendants = Vec::<ObjectId>::new();
+        let mut next_index = 0;
+        let mut current_id = object_id;
+        loop {
+            for child_id in self.children(current_id) {
+                if child_id != object_id && !descendants.contains(&child_id) {
+                    descendants.push(child_id);
+                }
+            }
+            let Some(&next_id) = descendants.get(next_index) else {
+                break;
+            };
+            current_id = next_id;
+            next_index += 1;
+        }
+        descendants
+    }
+
     /// Marks all objects for gpu update, regardless of wherever they've been modified since the
     /// last upload. Useful for debugging.
     pub fn force_gpu_update(&mut self) {
-        for (object_id, object) in self.objects.clone() {
-            self.push_object_delta(object_id, ObjectDeltaOperation::Update(object));
+        let object_ids: Vec<ObjectId> = self.objects.keys().copied().collect();
+        for object_id in object_ids {
+            _ = self.push_gpu_update(object_id);
         }
     }
 }
@@ -246,20 +1012,173 @@ impl ObjectCollection {
 
 impl ObjectCollection {
     /// Call this whenever an object is modified via [`get_object_mut`] so that the updated data
-    /// can be sent to the GPU.
+    /// can be sent to the GPU. Instances of the object are marked too because they share its
+    /// primitive ops.
     fn mark_object_for_gpu_update(&mut self, object_id: ObjectId) -> Result<(), CollectionError> {
-        let Some(updated_object) = self.objects.get(&object_id) else {
+        self.push_gpu_update(object_id)?;
+        for instance_id in self.instances(object_id) {
+            self.push_gpu_update(instance_id)?;
+        }
+        Ok(())
+    }
+
+    /// Marks only `object_id` for gpu update, see [`Self::resolved_object`]. Instances are sent
+    /// with a copy of the primitive ops of their definition so that the renderer can calculate
+    /// their bounds, but the renderer shares the primitive ops buffer of the definition instead of
+    /// uploading them again. Objects in hidden layers are sent as hidden.
+    fn push_gpu_update(&mut self, object_id: ObjectId) -> Result<(), CollectionError> {
+        let Some(gpu_object) = self.resolved_object(object_id) else {
             return Err(CollectionError::InvalidId {
                 raw_id: object_id.raw_id(),
             });
         };
-        self.push_object_delta(
-            object_id,
-            ObjectDeltaOperation::Update(updated_object.clone()),
-        );
+        self.push_object_delta(object_id, ObjectDeltaOperation::Update(gpu_object));
+        Ok(())
+    }
+
+    fn clear_invalid_layer(&self, object: &mut Object) {
+        if let Some(layer_id) = object.layer() {
+            if !self.layers.contains_key(&layer_id) {
+                object.set_layer(None);
+            }
+        }
+    }
+
+    /// Primitive ops keep their own albedo and specular when their material is cleared.
+    fn clear_invalid_materials(&self, primitive_ops: &mut [PrimitiveOp]) {
+        for primitive_op in primitive_ops {
+            if let Some(material_id) = primitive_op.material {
+                if !self.materials.contains_key(&material_id) {
+                    primitive_op.material = None;
+                    primitive_op.material_override = false;
+                }
+            }
+        }
+    }
+
+    fn get_layer_mut(&mut self, layer_id: LayerId) -> Result<&mut Layer, CollectionError> {
+        self.layers
+            .get_mut(&layer_id)
+            .ok_or(CollectionError::InvalidId {
+                raw_id: layer_id.raw_id(),
+            })
+    }
+
+    /// `object_id` if it isn't an instance, otherwise the id of its definition.
+    fn definition_id(&self, object_id: ObjectId) -> Result<ObjectId, CollectionError> {
+        let object = self
+            .get_object(object_id)
+            .ok_or(CollectionError::InvalidId {
+                raw_id: object_id.raw_id(),
+            })?;
+        Ok(object.instance_of().unwrap_or(object_id))
+    }
+
+    /// When `keep_outside_links` is true, parent and instance links to objects that aren't in
+    /// `new_objects` are kept if they exist in the collection. Otherwise they're cleared.
+    fn push_objects_internal(
+        &mut self,
+        new_objects: impl IntoIterator<Item = (ObjectId, Object)>,
+        keep_outside_links: bool,
+    ) -> Result<Vec<ObjectId>, UniqueIdError> {
+        let mut new_object_ids: Vec<ObjectId> = Vec::new();
+        let mut new_ids_by_old_id = HashMap::<ObjectId, ObjectId>::new();
+        let mut old_links = Vec::<(ObjectId, Option<ObjectId>, Option<ObjectId>)>::new();
+
+        for (old_object_id, mut new_object) in new_objects {
+            let new_object_id = self.unique_id_gen.new_id()?;
+            self.clear_invalid_layer(&mut new_object);
+            self.clear_invalid_materials(&mut new_object.primitive_ops);
+            old_links.push((new_object_id, new_object.parent(), new_object.instance_of()));
+            new_object.set_parent(None);
+            new_object.set_instance_of(None);
+            self.objects.insert(new_object_id, new_object);
+            new_ids_by_old_id.insert(old_object_id, new_object_id);
+            new_object_ids.push(new_object_id);
+        }
+
+        let remap_link = |old_link: Option<ObjectId>| -> Option<ObjectId> {
+            let old_id = old_link?;
+            match new_ids_by_old_id.get(&old_id) {
+                Some(&new_id) => Some(new_id),
+                None if keep_outside_links
+                    && !new_object_ids.contains(&old_id)
+                    && self.objects.contains_key(&old_id) =>
+                {
+                    Some(old_id)
+                }
+                None => None,
+            }
+        };
+        let new_links: Vec<(ObjectId, Option<ObjectId>, Option<ObjectId>)> = old_links
+            .into_iter()
+            .map(|(new_object_id, old_parent_id, old_definition_id)| {
+                (
+                    new_object_id,
+                    remap_link(old_parent_id),
+                    remap_link(old_definition_id),
+                )
+            })
+            .collect();
+
+        for (new_object_id, new_parent_id, new_definition_id) in new_links {
+            if let Some(new_object) = self.objects.get_mut(&new_object_id) {
+                new_object.set_parent(new_parent_id);
+                new_object.set_instance_of(new_definition_id);
+            }
+        }
+
+        for &new_object_id in &new_object_ids {
+            _ = self.update_world_transforms(new_object_id);
+        }
+        Ok(new_object_ids)
+    }
+
+    fn object_tree_internal(
+        &self,
+        object_id: ObjectId,
+    ) -> Result<Vec<(ObjectId, Object)>, CollectionError> {
+        let mut object_ids = vec![object_id];
+        object_ids.append(&mut self.descendants(object_id));
+        object_ids
+            .into_iter()
+            .map(|tree_object_id| {
+                let object = self
+                    .get_object(tree_object_id)
+                    .ok_or(CollectionError::InvalidId {
+                        raw_id: tree_object_id.raw_id(),
+                    })?;
+                Ok((tree_object_id, object.clone()))
+            })
+            .collect()
+    }
+
+    /// Call this whenever the transform or parent of an object changes. Updates the cached parent
+    /// transforms of the object and its descendants and marks them all for gpu update.
+    fn update_world_transforms(&mut self, object_id: ObjectId) -> Result<(), CollectionError> {
+        self.update_parent_transform(object_id)?;
+        for descendant_id in self.descendants(object_id) {
+            self.update_parent_transform(descendant_id)?;
+        }
         Ok(())
     }
 
+    fn update_parent_transform(&mut self, object_id: ObjectId) -> Result<(), CollectionError> {
+        let parent_id = self
+            .get_object(object_id)
+            .ok_or(CollectionError::InvalidId {
+                raw_id: object_id.raw_id(),
+            })?
+            .parent();
+        let parent_transform = match parent_id.and_then(|parent_id| self.get_object(parent_id)) {
+            Some(parent) => parent.world_transform(),
+            None => ObjectTransform::IDENTITY,
+        };
+        self.get_object_mut(object_id)?
+            .set_parent_transform(parent_transform);
+        self.push_gpu_update(object_id)
+    }
+
     /// Use this instead of directly inserting to perform conflict checks.
     ///
     /// Note: the reason multiple deltas for the same object are merged is so that the renderer doesn't
@@ -299,3 +1218,830 @@ impl ObjectCollection {
             })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::{primitives::sphere::Sphere, sdf_evaluator::ObjectSdf};
+    use std::f32::consts::FRAC_PI_2;
+
+    const EPSILON: f32 = 1e-4;
+
+    /// Sphere with radius 0.5 centered at `center` relative to the object origin
+    fn push_sphere(
+        object_collection: &mut ObjectCollection,
+        object_id: ObjectId,
+        center: Vec3,
+    ) -> PrimitiveOpId {
+        object_collection
+            .push_op_to_object(
+                object_id,
+                Sphere::new(0.5).into(),
+                PrimitiveTransform::new(center, Quat::IDENTITY),
+                Operation::Union,
+                0.,
+                Vec3::ONE,
+                0.5,
+            )
+            .unwrap()
+    }
+
+    fn world_origin(object_collection: &ObjectCollection, object_id: ObjectId) -> Vec3 {
+        object_collection
+            .get_object(object_id)
+            .unwrap()
+            .world_transform()
+            .origin
+    }
+
+    #[test]
+    fn set_object_parent_keeps_world_transform() {
+        let mut object_collection = ObjectCollection::new();
+        let (parent_id, _) = object_collection
+            .new_object("parent", Vec3::new(1., 0., 0.))
+            .unwrap();
+        object_collection
+            .set_object_rotation(parent_id, Quat::from_rotation_z(FRAC_PI_2))
+            .unwrap();
+        let (child_id, _) = object_collection
+            .new_object("child", Vec3::new(1., 1., 0.))
+            .unwrap();
+        push_sphere(&mut object_collection, child_id, Vec3::ZERO);
+        let (grandchild_id, _) = object_collection
+            .new_object("grandchild", Vec3::new(0., 1., 0.))
+            .unwrap();
+        object_collection
+            .set_object_parent(child_id, Some(parent_id))
+            .unwrap();
+        object_collection
+            .set_object_parent(grandchild_id, Some(child_id))
+            .unwrap();
+
+        assert!(
+            world_origin(&object_collection, child_id).distance(Vec3::new(1., 1., 0.)) < EPSILON
+        );
+        let child = object_collection.get_object(child_id).unwrap();
+        assert!(child.origin.distance(Vec3::new(1., 0., 0.)) < EPSILON);
+        assert_eq!(
+            object_collection.descendants(parent_id),
+            vec![child_id, grandchild_id]
+        );
+
+        // moving the parent moves and updates the descendants
+        _ = object_collection.get_and_clear_objects_delta();
+        object_collection
+            .set_object_origin(parent_id, Vec3::new(2., 0., 0.))
+            .unwrap();
+        let delta = object_collection.get_and_clear_objects_delta();
+        assert_eq!(delta.len(), 3);
+        assert!(
+            world_origin(&object_collection, child_id).distance(Vec3::new(2., 1., 0.)) < EPSILON
+        );
+        let sdf = ObjectSdf::new(object_collection.get_object(child_id).unwrap());
+        assert!((sdf.distance(Vec3::new(2., 2., 0.)) - 0.5).abs() < EPSILON);
+    }
+
+    #[test]
+    fn set_object_parent_rejects_cycles() {
+        let mut object_collection = ObjectCollection::new();
+        let (parent_id, _) = object_collection.new_object("parent", Vec3::ZERO).unwrap();
+        let (child_id, _) = object_collection.new_object("child", Vec3::ZERO).unwrap();
+        let (grandchild_id, _) = object_collection
+            .new_object("grandchild", Vec3::ZERO)
+            .unwrap();
+        object_collection
+            .set_object_parent(child_id, Some(parent_id))
+            .unwrap();
+        object_collection
+            .set_object_parent(grandchild_id, Some(child_id))
+            .unwrap();
+
+        assert!(object_collection
+            .set_object_parent(parent_id, Some(grandchild_id))
+            .is_err());
+        assert!(object_collection
+            .set_object_parent(child_id, Some(child_id))
+            .is_err());
+        assert_eq!(
+            object_collection.get_object(parent_id).unwrap().parent(),
+            None
+        );
+        assert_eq!(
+            object_collection.descendants(parent_id),
+            vec![child_id, grandchild_id]
+        );
+    }
+
+    #[test]
+    fn remove_object_reparents_children_in_place() {
+        let mut object_collection = ObjectCollection::new();
+        let (parent_id, _) = object_collection
+            .new_object("parent", Vec3::new(1., 0., 0.))
+            .unwrap();
+        object_collection
+            .set_object_rotation(parent_id, Quat::from_rotation_z(FRAC_PI_2))
+            .unwrap();
+        let (child_id, _) = object_collection
+            .new_object("child", Vec3::new(1., 1., 0.))
+            .unwrap();
+        push_sphere(&mut object_collection, child_id, Vec3::ZERO);
+        let (grandchild_id, _) = object_collection
+            .new_object("grandchild", Vec3::new(0., 1., 0.))
+            .unwrap();
+        object_collection
+            .set_object_parent(child_id, Some(parent_id))
+            .unwrap();
+        object_collection
+            .set_object_parent(grandchild_id, Some(child_id))
+            .unwrap();
+
+        _ = object_collection.get_and_clear_objects_delta();
+        object_collection.remove_object(parent_id).unwrap();
+        let child = object_collection.get_object(child_id).unwrap();
+        assert_eq!(child.parent(), None);
+        assert!(child.origin.distance(Vec3::new(1., 1., 0.)) < EPSILON);
+        assert!(
+            world_origin(&object_collection, child_id).distance(Vec3::new(1., 1., 0.)) < EPSILON
+        );
+        assert!(
+            world_origin(&object_collection, grandchild_id).distance(Vec3::new(0., 1., 0.))
+                < EPSILON
+        );
+
+        // the gpu gets the reparented child in the same place
+        let delta = object_collection.get_and_clear_objects_delta();
+        let ObjectDeltaOperation::Update(gpu_child) = &delta[&child_id] else {
+            panic!("child should be updated");
+        };
+        assert!(
+            gpu_child
+                .world_transform()
+                .origin
+                .distance(Vec3::new(1., 1., 0.))
+                < EPSILON
+        );
+        let gpu_child_aabb = gpu_child.aabb();
+        let gpu_child_center = (gpu_child_aabb.min + gpu_child_aabb.max) / 2.;
+        assert!(gpu_child_center.distance(Vec3::new(1., 1., 0.)) < EPSILON);
+    }
+
+    #[test]
+    fn remove_object_and_descendants() {
+        let mut object_collection = ObjectCollection::new();
+        let (parent_id, _) = object_collection.new_object("parent", Vec3::ZERO).unwrap();
+        let (child_id, _) = object_collection.new_object("child", Vec3::ZERO).unwrap();
+        let (grandchild_id, _) = object_collection
+            .new_object("grandchild", Vec3::ZERO)
+            .unwrap();
+        object_collection
+            .set_object_parent(child_id, Some(parent_id))
+            .unwrap();
+        object_collection
+            .set_object_parent(grandchild_id, Some(child_id))
+            .unwrap();
+
+        let removed_ids = object_collection
+            .remove_object_and_descendants(child_id)
+            .unwrap();
+        assert_eq!(removed_ids, vec![child_id, grandchild_id]);
+        assert_eq!(
+            object_collection
+                .objects()
+                .keys()
+                .copied()
+                .collect::<Vec<_>>(),
+            vec![parent_id]
+        );
+        assert!(object_collection.descendants(parent_id).is_empty());
+    }
+
+    #[test]
+    fn ray_cast_hits_nearest() {
+        let mut object_collection = ObjectCollection::new();
+        let (near_object_id, _) = object_collection.new_object("near", Vec3::ZERO).unwrap();
+        push_sphere(&mut object_collection, near_object_id, Vec3::ZERO);
+        push_sphere(
+            &mut object_collection,
+            near_object_id,
+            Vec3::new(0., 0., 2.),
+        );
+        let (far_object_id, _) = object_collection
+            .new_object("far", Vec3::new(3., 0., 0.))
+            .unwrap();
+        push_sphere(&mut object_collection, far_object_id, Vec3::ZERO);
+
+        // direction doesn't need to be normalized
+        let hit = object_collection
+            .ray_cast(Vec3::new(-5., 0., 0.), Vec3::new(2., 0., 0.), 100.)
+            .unwrap();
+        assert_eq!(hit.object_id, near_object_id);
+        assert_eq!(hit.primitive_op_index, Some(0));
+        assert!((hit.distance - 4.5).abs() < 0.01);
+        assert!(hit.position.abs_diff_eq(Vec3::new(-0.5, 0., 0.), 0.01));
+        assert!(hit.normal.abs_diff_eq(-Vec3::X, 0.01));
+
+        let hit = object_collection
+            .ray_cast(Vec3::new(8., 0., 0.), -Vec3::X, 100.)
+            .unwrap();
+        assert_eq!(hit.object_id, far_object_id);
+
+        let hit = object_collection
+            .ray_cast(Vec3::new(0., 0., 5.), -Vec3::Z, 100.)
+            .unwrap();
+        assert_eq!(hit.primitive_op_index, Some(1));
+        assert!(hit.normal.abs_diff_eq(Vec3::Z, 0.01));
+    }
+
+    #[test]
+    fn ray_cast_miss() {
+        let mut object_collection = ObjectCollection::new();
+        let (object_id, _) = object_collection.new_object("object", Vec3::ZERO).unwrap();
+        push_sphere(&mut object_collection, object_id, Vec3::ZERO);
+
+        let origin = Vec3::new(-5., 0., 0.);
+        assert!(object_collection
+            .ray_cast(Vec3::new(-5., 1., 0.), Vec3::X, 100.)
+            .is_none());
+        assert!(object_collection.ray_cast(origin, -Vec3::X, 100.).is_none());
+        assert!(object_collection.ray_cast(origin, Vec3::X, 4.).is_none());
+        assert!(object_collection
+            .ray_cast(origin, Vec3::ZERO, 100.)
+            .is_none());
+    }
+
+    #[test]
+    fn ray_cast_skips_hidden_objects() {
+        let mut object_collection = ObjectCollection::new();
+        let (hidden_object_id, _) = object_collection.new_object("hidden", Vec3::ZERO).unwrap();
+        push_sphere(&mut object_collection, hidden_object_id, Vec3::ZERO);
+        let (object_id, _) = object_collection
+            .new_object("behind", Vec3::new(3., 0., 0.))
+            .unwrap();
+        push_sphere(&mut object_collection, object_id, Vec3::ZERO);
+        object_collection
+            .set_object_visible(hidden_object_id, false)
+            .unwrap();
+
+        let hit = object_collection
+            .ray_cast(Vec3::new(-5., 0., 0.), Vec3::X, 100.)
+            .unwrap();
+        assert_eq!(hit.object_id, object_id);
+        assert!((hit.distance - 7.5).abs() < 0.01);
+    }
+
+    #[test]
+    fn ray_cast_hits_instance() {
+        let mut object_collection = ObjectCollection::new();
+        let (definition_id, _) = object_collection
+            .new_object("definition", Vec3::ZERO)
+            .unwrap();
+        push_sphere(&mut object_collection, definition_id, Vec3::ZERO);
+        let instance_id = object_collection.new_instance(definition_id).unwrap();
+        object_collection
+            .set_object_origin(instance_id, Vec3::new(3., 0., 0.))
+            .unwrap();
+        object_collection.set_object_scale(instance_id, 2.).unwrap();
+
+        let hit = object_collection
+            .ray_cast(Vec3::new(3., -5., 0.), Vec3::Y, 100.)
+            .unwrap();
+        assert_eq!(hit.object_id, instance_id);
+        assert_eq!(hit.primitive_op_index, Some(0));
+        assert!((hit.distance - 4.).abs() < 0.01);
+        assert!(hit.normal.abs_diff_eq(-Vec3::Y, 0.01));
+    }
+
+    #[test]
+    fn objects_in_layer() {
+        let mut object_collection = ObjectCollection::new();
+        let layer_id = object_collection.new_layer("reference").unwrap();
+        let (object_id, _) = object_collection.new_object("object", Vec3::ZERO).unwrap();
+        let (other_object_id, _) = object_collection
+            .new_object("other object", Vec3::ZERO)
+            .unwrap();
+        object_collection
+            .set_object_layer(object_id, Some(layer_id))
+            .unwrap();
+        assert_eq!(
+            object_collection.objects_in_layer(Some(layer_id)),
+            vec![object_id]
+        );
+        assert_eq!(
+            object_collection.objects_in_layer(None),
+            vec![other_object_id]
+        );
+        let invalid_layer_id = LayerId(layer_id.raw_id() + 1);
+        assert!(object_collection
+            .set_object_layer(other_object_id, Some(invalid_layer_id))
+            .is_err());
+    }
+
+    #[test]
+    fn set_layer_visible_hides_objects() {
+        let mut object_collection = ObjectCollection::new();
+        let layer_id = object_collection.new_layer("reference").unwrap();
+        let (object_id, _) = object_collection.new_object("object", Vec3::ZERO).unwrap();
+        let (other_object_id, _) = object_collection
+            .new_object("other object", Vec3::ZERO)
+            .unwrap();
+        object_collection
+            .set_object_layer(object_id, Some(layer_id))
+            .unwrap();
+
+        // hiding the layer hides its objects on the gpu
+        _ = object_collection.get_and_clear_objects_delta();
+        object_collection
+            .set_layer_visible(layer_id, false)
+            .unwrap();
+        let delta = object_collection.get_and_clear_objects_delta();
+        assert_eq!(delta.len(), 1);
+        let ObjectDeltaOperation::Update(gpu_object) = &delta[&object_id] else {
+            panic!("object should be updated");
+        };
+        assert!(!gpu_object.visible);
+        assert!(!object_collection.is_object_visible(object_id));
+        assert!(object_collection.is_object_visible(other_object_id));
+
+        object_collection.set_layer_visible(layer_id, true).unwrap();
+        assert!(object_collection.is_object_visible(object_id));
+    }
+
+    #[test]
+    fn set_layer_selectable() {
+        let mut object_collection = ObjectCollection::new();
+        let layer_id = object_collection.new_layer("reference").unwrap();
+        let (object_id, _) = object_collection.new_object("object", Vec3::ZERO).unwrap();
+        let (other_object_id, _) = object_collection
+            .new_object("other object", Vec3::ZERO)
+            .unwrap();
+        object_collection
+            .set_object_layer(object_id, Some(layer_id))
+            .unwrap();
+        object_collection
+            .set_layer_selectable(layer_id, false)
+            .unwrap();
+        assert!(!object_collection.is_object_selectable(object_id));
+        assert!(object_collection.is_object_selectable(other_object_id));
+        // selectability doesn't affect rendering
+        assert!(object_collection.is_object_visible(object_id));
+    }
+
+    #[test]
+    fn remove_layer_clears_membership() {
+        let mut object_collection = ObjectCollection::new();
+        let layer_id = object_collection.new_layer("reference").unwrap();
+        let (object_id, _) = object_collection.new_object("object", Vec3::ZERO).unwrap();
+        object_collection
+            .set_object_layer(object_id, Some(layer_id))
+            .unwrap();
+        object_collection
+            .set_layer_visible(layer_id, false)
+            .unwrap();
+
+        // removing the layer leaves its objects without a layer
+        _ = object_collection.get_and_clear_objects_delta();
+        object_collection.remove_layer(layer_id).unwrap();
+        assert_eq!(
+            object_collection.get_object(object_id).unwrap().layer(),
+            None
+        );
+        assert!(object_collection.is_object_visible(object_id));
+        let delta = object_collection.get_and_clear_objects_delta();
+        let ObjectDeltaOperation::Update(gpu_object) = &delta[&object_id] else {
+            panic!("object should be updated");
+        };
+        assert!(gpu_object.visible);
+        assert!(object_collection.get_layer(layer_id).is_none());
+    }
+
+    #[test]
+    fn push_scene_remaps_layers() {
+        let mut object_collection = ObjectCollection::new();
+        let layer_id = object_collection.new_layer("reference").unwrap();
+        let (object_id, _) = object_collection.new_object("object", Vec3::ZERO).unwrap();
+        object_collection
+            .new_object("other object", Vec3::ZERO)
+            .unwrap();
+        object_collection
+            .set_object_layer(object_id, Some(layer_id))
+            .unwrap();
+        object_collection
+            .set_layer_visible(layer_id, false)
+            .unwrap();
+        let layers: Vec<_> = object_collection
+            .layers()
+            .iter()
+            .map(|(&layer_id, layer)| (layer_id, layer.clone()))
+            .collect();
+        let objects: Vec<_> = object_collection
+            .objects()
+            .iter()
+            .map(|(&object_id, object)| (object_id, object.clone()))
+            .collect();
+
+        // the loaded layer can't reuse the id of an existing one
+        let mut loaded_collection = ObjectCollection::new();
+        loaded_collection.new_layer("existing").unwrap();
+        let loaded_object_ids = loaded_collection
+            .push_scene(layers, Vec::new(), objects)
+            .unwrap();
+        let loaded_layer_id = loaded_collection
+            .get_object(loaded_object_ids[0])
+            .unwrap()
+            .layer()
+            .unwrap();
+        assert_eq!(
+            loaded_collection.get_layer(loaded_layer_id).unwrap().name,
+            "reference"
+        );
+        assert!(!loaded_collection.is_object_visible(loaded_object_ids[0]));
+        assert_eq!(
+            loaded_collection
+                .get_object(loaded_object_ids[1])
+                .unwrap()
+                .layer(),
+            None
+        );
+    }
+
+    #[test]
+    fn set_object_tags() {
+        let mut object_collection = ObjectCollection::new();
+        let (object_id, _) = object_collection.new_object("object", Vec3::ZERO).unwrap();
+        let (other_object_id, _) = object_collection
+            .new_object("other object", Vec3::ZERO)
+            .unwrap();
+        object_collection
+            .set_object_tags(object_id, vec!["wip".to_string(), "prop".to_string()])
+            .unwrap();
+        object_collection
+            .set_object_tags(other_object_id, vec!["wip".to_string()])
+            .unwrap();
+        assert_eq!(
+            object_collection.objects_with_tag("wip"),
+            vec![object_id, other_object_id]
+        );
+        assert_eq!(object_collection.objects_with_tag("prop"), vec![object_id]);
+
+        // tags are replaced, not appended
+        object_collection
+            .set_object_tags(object_id, vec!["prop".to_string()])
+            .unwrap();
+        assert_eq!(
+            object_collection.objects_with_tag("wip"),
+            vec![other_object_id]
+        );
+        assert!(object_collection.objects_with_tag("missing").is_empty());
+    }
+
+    const RED: Vec3 = Vec3::new(1., 0., 0.);
+
+    /// Albedo and specular of the primitive op the next time the object is sent to the gpu
+    fn gpu_albedo_and_specular(
+        object_collection: &mut ObjectCollection,
+        object_id: ObjectId,
+        primitive_op_id: PrimitiveOpId,
+    ) -> (Vec3, f32) {
+        let delta = object_collection.get_and_clear_objects_delta();
+        let (ObjectDeltaOperation::Add(gpu_object) | ObjectDeltaOperation::Update(gpu_object)) =
+            &delta[&object_id]
+        else {
+            panic!("object should be sent to the gpu");
+        };
+        let gpu_primitive_op = gpu_object.get_primitive_op(primitive_op_id).unwrap();
+        (gpu_primitive_op.albedo, gpu_primitive_op.specular)
+    }
+
+    #[test]
+    fn material_resolved_for_gpu() {
+        let mut object_collection = ObjectCollection::new();
+        let material_id = object_collection.new_material("red").unwrap();
+        let mut red_material = object_collection.get_material(material_id).unwrap().clone();
+        red_material.albedo = RED;
+        red_material.specular = 0.1;
+        object_collection
+            .set_material(material_id, red_material)
+            .unwrap();
+        let (object_id, _) = object_collection.new_object("object", Vec3::ZERO).unwrap();
+        let primitive_op_id = push_sphere(&mut object_collection, object_id, Vec3::ZERO);
+        object_collection
+            .set_primitive_op_material_in_object(
+                object_id,
+                primitive_op_id,
+                Some(material_id),
+                false,
+            )
+            .unwrap();
+        assert_eq!(
+            object_collection.objects_using_material(material_id),
+            vec![object_id]
+        );
+        assert_eq!(
+            gpu_albedo_and_specular(&mut object_collection, object_id, primitive_op_id),
+            (RED, 0.1)
+        );
+        // the primitive op keeps its own colors for when the material is unset
+        let object = object_collection.get_object(object_id).unwrap();
+        assert_eq!(
+            object.get_primitive_op(primitive_op_id).unwrap().albedo,
+            Vec3::ONE
+        );
+
+        // editing the material updates the objects using it
+        let mut new_material = object_collection.get_material(material_id).unwrap().clone();
+        new_material.albedo = Vec3::Z;
+        object_collection
+            .set_material(material_id, new_material)
+            .unwrap();
+        assert_eq!(
+            gpu_albedo_and_specular(&mut object_collection, object_id, primitive_op_id),
+            (Vec3::Z, 0.1)
+        );
+    }
+
+    #[test]
+    fn set_primitive_op_material_rejects_unknown_material() {
+        let mut object_collection = ObjectCollection::new();
+        let material_id = object_collection.new_material("material").unwrap();
+        let (object_id, _) = object_collection.new_object("object", Vec3::ZERO).unwrap();
+        let primitive_op_id = push_sphere(&mut object_collection, object_id, Vec3::ZERO);
+        assert!(object_collection
+            .set_primitive_op_material_in_object(
+                object_id,
+                primitive_op_id,
+                Some(MaterialId(material_id.0 + 1)),
+                false
+            )
+            .is_err());
+        let object = object_collection.get_object(object_id).unwrap();
+        assert_eq!(
+            object.get_primitive_op(primitive_op_id).unwrap().material,
+            None
+        );
+    }
+
+    #[test]
+    fn material_override_keeps_primitive_op_colors() {
+        let mut object_collection = ObjectCollection::new();
+        let material_id = object_collection.new_material("red").unwrap();
+        let mut red_material = object_collection.get_material(material_id).unwrap().clone();
+        red_material.albedo = RED;
+        red_material.specular = 0.1;
+        object_collection
+            .set_material(material_id, red_material)
+            .unwrap();
+        let (object_id, _) = object_collection.new_object("object", Vec3::ZERO).unwrap();
+        let primitive_op_id = push_sphere(&mut object_collection, object_id, Vec3::ZERO);
+        object_collection
+            .set_primitive_op_material_in_object(
+                object_id,
+                primitive_op_id,
+                Some(material_id),
+                true,
+            )
+            .unwrap();
+        assert_eq!(
+            gpu_albedo_and_specular(&mut object_collection, object_id, primitive_op_id),
+            (Vec3::ONE, 0.5)
+        );
+        // still counts as using the material
+        assert_eq!(
+            object_collection.objects_using_material(material_id),
+            vec![object_id]
+        );
+    }
+
+    #[test]
+    fn remove_material_keeps_colors() {
+        let mut object_collection = ObjectCollection::new();
+        let material_id = object_collection.new_material("red").unwrap();
+        let mut red_material = object_collection.get_material(material_id).unwrap().clone();
+        red_material.albedo = RED;
+        red_material.specular = 0.1;
+        object_collection
+            .set_material(material_id, red_material)
+            .unwrap();
+        let (object_id, _) = object_collection.new_object("object", Vec3::ZERO).unwrap();
+        let material_op_id = push_sphere(&mut object_collection, object_id, Vec3::ZERO);
+        let override_op_id = push_sphere(&mut object_collection, object_id, Vec3::X);
+        object_collection
+            .set_primitive_op_material_in_object(
+                object_id,
+                material_op_id,
+                Some(material_id),
+                false,
+            )
+            .unwrap();
+        object_collection
+            .set_primitive_op_material_in_object(object_id, override_op_id, Some(material_id), true)
+            .unwrap();
+
+        // primitive ops using the material take on its colors, overridden ones keep their own
+        _ = object_collection.get_and_clear_objects_delta();
+        object_collection.remove_material(material_id).unwrap();
+        assert!(object_collection.get_material(material_id).is_none());
+        assert_eq!(
+            gpu_albedo_and_specular(&mut object_collection, object_id, material_op_id),
+            (RED, 0.1)
+        );
+        let object = object_collection.get_object(object_id).unwrap();
+        let material_op = object.get_primitive_op(material_op_id).unwrap();
+        assert_eq!(material_op.material, None);
+        assert_eq!((material_op.albedo, material_op.specular), (RED, 0.1));
+        let override_op = object.get_primitive_op(override_op_id).unwrap();
+        assert_eq!(override_op.material, None);
+        assert!(!override_op.material_override);
+        assert_eq!((override_op.albedo, override_op.specular), (Vec3::ONE, 0.5));
+    }
+
+    /// Sets the radius of the sphere via `object_id`, which may be an instance.
+    fn set_sphere_radius(
+        object_collection: &mut ObjectCollection,
+        object_id: ObjectId,
+        primitive_op_id: PrimitiveOpId,
+        radius: f32,
+    ) {
+        object_collection
+            .set_primitive_op_id_in_object(
+                object_id,
+                primitive_op_id,
+                Some(Sphere::new(radius).into()),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn new_instance_shares_definition() {
+        let mut object_collection = ObjectCollection::new();
+        let (definition_id, _) = object_collection
+            .new_object("definition", Vec3::ZERO)
+            .unwrap();
+        push_sphere(&mut object_collection, definition_id, Vec3::ZERO);
+        let instance_id = object_collection.new_instance(definition_id).unwrap();
+        object_collection
+            .set_object_origin(instance_id, Vec3::new(2., 0., 0.))
+            .unwrap();
+        // instances of instances share the original definition
+        let nested_instance_id = object_collection.new_instance(instance_id).unwrap();
+        assert_eq!(
+            object_collection.instances(definition_id),
+            vec![instance_id, nested_instance_id]
+        );
+        let nested_instance = object_collection.get_object(nested_instance_id).unwrap();
+        assert_eq!(nested_instance.instance_of(), Some(definition_id));
+        assert_eq!(nested_instance.origin, Vec3::new(2., 0., 0.));
+
+        let instance = object_collection.get_object(instance_id).unwrap();
+        assert!(instance.primitive_ops.is_empty());
+        let definition = object_collection.definition(instance_id).unwrap();
+        let sdf = ObjectSdf::with_transform(definition, &instance.world_transform());
+        assert!((sdf.distance(Vec3::new(2., 1., 0.)) - 0.5).abs() < EPSILON);
+    }
+
+    #[test]
+    fn instance_edits_update_definition() {
+        let mut object_collection = ObjectCollection::new();
+        let (definition_id, _) = object_collection
+            .new_object("definition", Vec3::ZERO)
+            .unwrap();
+        let primitive_op_id = push_sphere(&mut object_collection, definition_id, Vec3::ZERO);
+        let instance_id = object_collection.new_instance(definition_id).unwrap();
+        object_collection
+            .set_object_origin(instance_id, Vec3::new(2., 0., 0.))
+            .unwrap();
+        let other_instance_id = object_collection.new_instance(definition_id).unwrap();
+
+        // editing the definition via an instance updates all instances
+        _ = object_collection.get_and_clear_objects_delta();
+        set_sphere_radius(&mut object_collection, instance_id, primitive_op_id, 1.);
+        let delta = object_collection.get_and_clear_objects_delta();
+        assert_eq!(delta.len(), 3);
+        let ObjectDeltaOperation::Update(gpu_instance) = &delta[&instance_id] else {
+            panic!("instance should be updated");
+        };
+        assert!(gpu_instance.aabb().max.x > 2.9);
+        assert!(delta.contains_key(&other_instance_id));
+        let definition = object_collection.get_object(definition_id).unwrap();
+        let sdf = ObjectSdf::new(definition);
+        assert!((sdf.distance(Vec3::new(0., 2., 0.)) - 1.).abs() < EPSILON);
+    }
+
+    #[test]
+    fn make_instance_unique() {
+        let mut object_collection = ObjectCollection::new();
+        let (definition_id, _) = object_collection
+            .new_object("definition", Vec3::ZERO)
+            .unwrap();
+        let primitive_op_id = push_sphere(&mut object_collection, definition_id, Vec3::ZERO);
+        let instance_id = object_collection.new_instance(definition_id).unwrap();
+        object_collection
+            .set_object_origin(instance_id, Vec3::new(2., 0., 0.))
+            .unwrap();
+        object_collection.make_instance_unique(instance_id).unwrap();
+        assert!(object_collection.instances(definition_id).is_empty());
+
+        // the unique copy no longer follows the definition
+        set_sphere_radius(&mut object_collection, definition_id, primitive_op_id, 1.);
+        let instance = object_collection.get_object(instance_id).unwrap();
+        assert_eq!(instance.instance_of(), None);
+        let sdf = ObjectSdf::new(instance);
+        assert!((sdf.distance(Vec3::new(2., 1., 0.)) - 0.5).abs() < EPSILON);
+
+        // does nothing for objects that aren't instances
+        object_collection
+            .make_instance_unique(definition_id)
+            .unwrap();
+    }
+
+    #[test]
+    fn remove_object_makes_instances_unique() {
+        let mut object_collection = ObjectCollection::new();
+        let (definition_id, _) = object_collection
+            .new_object("definition", Vec3::ZERO)
+            .unwrap();
+        let primitive_op_id = push_sphere(&mut object_collection, definition_id, Vec3::ZERO);
+        let instance_id = object_collection.new_instance(definition_id).unwrap();
+        object_collection
+            .set_object_origin(instance_id, Vec3::new(2., 0., 0.))
+            .unwrap();
+        set_sphere_radius(&mut object_collection, instance_id, primitive_op_id, 1.);
+
+        // removing the definition gives the instances their own primitive ops
+        object_collection.remove_object(definition_id).unwrap();
+        let instance = object_collection.get_object(instance_id).unwrap();
+        assert_eq!(instance.instance_of(), None);
+        let sdf = ObjectSdf::new(instance);
+        assert!((sdf.distance(Vec3::new(2., 2., 0.)) - 1.).abs() < EPSILON);
+    }
+
+    #[test]
+    fn push_objects_clears_unknown_materials() {
+        let mut object_collection = ObjectCollection::new();
+        let material_id = object_collection.new_material("material").unwrap();
+        let (object_id, _) = object_collection.new_object("object", Vec3::ZERO).unwrap();
+        let primitive_op_id = push_sphere(&mut object_collection, object_id, Vec3::ZERO);
+        object_collection
+            .set_primitive_op_material_in_object(
+                object_id,
+                primitive_op_id,
+                Some(material_id),
+                true,
+            )
+            .unwrap();
+        let objects = object_collection.object_tree(object_id).unwrap();
+
+        // pasting in the same collection keeps the material
+        let pasted_ids = object_collection.push_objects(objects.clone()).unwrap();
+        let pasted_object = object_collection.get_object(pasted_ids[0]).unwrap();
+        assert_eq!(pasted_object.primitive_ops[0].material, Some(material_id));
+
+        // but not in one without it
+        let mut other_collection = ObjectCollection::new();
+        let pasted_ids = other_collection.push_objects(objects).unwrap();
+        let pasted_op = &other_collection
+            .get_object(pasted_ids[0])
+            .unwrap()
+            .primitive_ops[0];
+        assert_eq!(pasted_op.material, None);
+        assert!(!pasted_op.material_override);
+        assert_eq!(pasted_op.albedo, Vec3::ONE);
+        assert!(other_collection
+            .objects_using_material(material_id)
+            .is_empty());
+    }
+
+    #[test]
+    fn push_primitive_op_copy_clears_unknown_material() {
+        let mut object_collection = ObjectCollection::new();
+        let material_id = object_collection.new_material("material").unwrap();
+        let (object_id, _) = object_collection.new_object("object", Vec3::ZERO).unwrap();
+        let primitive_op_id = push_sphere(&mut object_collection, object_id, Vec3::ZERO);
+        object_collection
+            .set_primitive_op_material_in_object(
+                object_id,
+                primitive_op_id,
+                Some(material_id),
+                false,
+            )
+            .unwrap();
+        let object = object_collection.get_object(object_id).unwrap();
+        let primitive_op = object.get_primitive_op(primitive_op_id).unwrap().clone();
+
+        let mut other_collection = ObjectCollection::new();
+        let (other_object_id, _) = other_collection.new_object("object", Vec3::ZERO).unwrap();
+        let pasted_op_id = other_collection
+            .push_primitive_op_copy_to_object(other_object_id, primitive_op)
+            .unwrap();
+        let other_object = other_collection.get_object(other_object_id).unwrap();
+        let pasted_op = other_object.get_primitive_op(pasted_op_id).unwrap();
+        assert_eq!(pasted_op.material, None);
+        assert_eq!(pasted_op.albedo, Vec3::ONE);
+    }
+}
@@ -0,0 +1,84 @@
+use crate::engine::{
+    aabb::{box_half_extents, Aabb},
+    primitives::primitive_transform::MIN_SCALE,
+};
+use glam::{Mat3, Quat, Vec3};
+use serde::{Deserialize, Serialize};
+
+/// Places the primitive ops of an object in the world: uniform scale, then rotation, then
+/// translation to `origin`.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ObjectTransform {
+    pub origin: Vec3,
+    pub rotation: Quat,
+    pub scale: f32,
+}
+
+impl ObjectTransform {
+    pub const IDENTITY: Self = Self {
+        origin: Vec3::ZERO,
+        rotation: Quat::IDENTITY,
+        scale: 1.,
+    };
+
+    pub const fn new(origin: Vec3, rotation: Quat, scale: f32) -> Self {
+        Self {
+            origin,
+            rotation,
+            scale,
+        }
+    }
+
+    /// Scale with zero/negative values clamped to avoid dividing by zero in the shaders.
+    pub fn clamped_scale(&self) -> f32 {
+        self.scale.max(MIN_SCALE)
+    }
+
+    /// Maps object space to world space orientation: scale, then rotation.
+    pub fn linear_transform(&self) -> Mat3 {
+        Mat3::from_quat(self.rotation) * self.clamped_scale()
+    }
+
+    /// Object space `point` in world space
+    pub fn transform_point(&self, point: Vec3) -> Vec3 {
+        self.origin + self.linear_transform() * point
+    }
+
+    /// `child` is relative to this transform. Returns the combined transform, as if `child` had
+    /// been applied first.
+    pub fn mul_transform(&self, child: &ObjectTransform) -> ObjectTransform {
+        Self {
+            origin: self.transform_point(child.origin),
+            rotation: (self.rotation * child.rotation).normalize(),
+            scale: self.clamped_scale() * child.clamped_scale(),
+        }
+    }
+
+    /// Undoes this transform, so that `self.inverse().mul_transform(&self)` is the identity.
+    pub fn inverse(&self) -> ObjectTransform {
+        let inverse_rotation = self.rotation.inverse();
+        let inverse_scale = 1. / self.clamped_scale();
+        Self {
+            origin: -(inverse_rotation * self.origin) * inverse_scale,
+            rotation: inverse_rotation,
+            scale: inverse_scale,
+        }
+    }
+
+    /// Bounds in world space of the object space `aabb`.
+    pub fn transform_aabb(&self, aabb: &Aabb) -> Aabb {
+        let linear_transform = self.linear_transform();
+        let center = self.origin + linear_transform * ((aabb.min + aabb.max) / 2.);
+        let half_extents = box_half_extents((aabb.max - aabb.min) / 2., linear_transform);
+        Aabb {
+            max: center + half_extents,
+            min: center - half_extents,
+        }
+    }
+}
+
+impl Default for ObjectTransform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
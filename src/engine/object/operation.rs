@@ -1,7 +1,9 @@
 use crate::renderer::shader_interfaces::primitive_op_buffer::{op_codes, PrimitiveOpBufferUnit};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+/// How a primitive op combines with the shape made by the previous ops. The blend distance of
+/// the primitive op sets the size of the rounded, chamfered or stepped edge where they meet.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
 pub enum Operation {
     /// Combination of this primitive and current shape. Equivalent to AND.
     Union,
@@ -11,12 +13,46 @@ pub enum Operation {
     Subtraction,
     /// No-op
     Nop,
+    /// Union with a 45 degree chamfer where the surfaces meet.
+    ChamferUnion,
+    /// Subtraction with a 45 degree chamfer where the surfaces meet.
+    ChamferSubtraction,
+    /// Union with a staircase of `steps` steps where the surfaces meet.
+    StairsUnion { steps: u32 },
+    /// Subtraction with a staircase of `steps` steps where the surfaces meet.
+    StairsSubtraction { steps: u32 },
+    /// Union with a row of `steps` round columns where the surfaces meet.
+    ColumnsUnion { steps: u32 },
+    /// Subtraction with a row of `steps` round columns where the surfaces meet.
+    ColumnsSubtraction { steps: u32 },
+    /// Cuts a groove `width` wide on either side of this primitive's surface into the current
+    /// shape. The blend distance is the depth of the groove.
+    Groove { width: f32 },
+    /// Raises a tongue `width` wide on either side of this primitive's surface out of the
+    /// current shape. The blend distance is the height of the tongue.
+    Tongue { width: f32 },
+    /// Linearly interpolates between the current shape (0) and this primitive (1) by `factor`.
+    Morph { factor: f32 },
+    /// Symmetric difference: the volume inside exactly one of this primitive and the current
+    /// shape.
+    Xor,
 }
 
+// Note: new variants must be added to the end of the enum to keep save files loading.
 const VARIANTS: &[Operation] = &[
     Operation::Union,
     Operation::Intersection,
     Operation::Subtraction,
+    Operation::ChamferUnion,
+    Operation::ChamferSubtraction,
+    Operation::StairsUnion { steps: 4 },
+    Operation::StairsSubtraction { steps: 4 },
+    Operation::ColumnsUnion { steps: 4 },
+    Operation::ColumnsSubtraction { steps: 4 },
+    Operation::Groove { width: 0.05 },
+    Operation::Tongue { width: 0.05 },
+    Operation::Morph { factor: 0.5 },
+    Operation::Xor,
     Operation::Nop,
 ];
 
@@ -27,15 +63,50 @@ impl Operation {
             Self::Intersection => op_codes::INTERSECTION,
             Self::Subtraction => op_codes::SUBTRACTION,
             Self::Nop => op_codes::NOP,
+            Self::ChamferUnion => op_codes::CHAMFER_UNION,
+            Self::ChamferSubtraction => op_codes::CHAMFER_SUBTRACTION,
+            Self::StairsUnion { .. } => op_codes::STAIRS_UNION,
+            Self::StairsSubtraction { .. } => op_codes::STAIRS_SUBTRACTION,
+            Self::ColumnsUnion { .. } => op_codes::COLUMNS_UNION,
+            Self::ColumnsSubtraction { .. } => op_codes::COLUMNS_SUBTRACTION,
+            Self::Groove { .. } => op_codes::GROOVE,
+            Self::Tongue { .. } => op_codes::TONGUE,
+            Self::Morph { .. } => op_codes::MORPH,
+            Self::Xor => op_codes::XOR,
         }
     }
 
+    /// Extra parameter (f32) sent to the shaders with the op code: the step count of stairs and
+    /// columns, the width of grooves and tongues or the morph factor.
+    pub fn encoded_param(&self) -> PrimitiveOpBufferUnit {
+        let param = match *self {
+            Self::StairsUnion { steps }
+            | Self::StairsSubtraction { steps }
+            | Self::ColumnsUnion { steps }
+            | Self::ColumnsSubtraction { steps } => steps.max(1) as f32,
+            Self::Groove { width } | Self::Tongue { width } => width.max(0.),
+            Self::Morph { factor } => factor.clamp(0., 1.),
+            _ => 0.,
+        };
+        param.to_bits()
+    }
+
     pub fn name(&self) -> &str {
         match *self {
             Self::Union => "Union",
             Self::Intersection => "Intersection",
             Self::Subtraction => "Subtraction",
             Self::Nop => "No-op",
+            Self::ChamferUnion => "Chamfer Union",
+            Self::ChamferSubtraction => "Chamfer Subtraction",
+            Self::StairsUnion { .. } => "Stairs Union",
+            Self::StairsSubtraction { .. } => "Stairs Subtraction",
+            Self::ColumnsUnion { .. } => "Columns Union",
+            Self::ColumnsSubtraction { .. } => "Columns Subtraction",
+            Self::Groove { .. } => "Groove",
+            Self::Tongue { .. } => "Tongue",
+            Self::Morph { .. } => "Morph",
+            Self::Xor => "XOR",
         }
     }
 
@@ -1,6 +1,15 @@
-use super::operation::Operation;
+use super::{
+    domain_modifier::DomainModifier, material::MaterialId, operation::Operation,
+    primitive_op_group::PrimitiveOpGroupId, surface_modifier::SurfaceModifier,
+};
 use crate::{
-    engine::primitives::{primitive::Primitive, primitive_transform::PrimitiveTransform},
+    engine::{
+        aabb::Aabb,
+        primitives::{
+            primitive::{EncodablePrimitive, Primitive},
+            primitive_transform::PrimitiveTransform,
+        },
+    },
     helper::unique_id_gen::{UniqueId, UniqueIdType},
 };
 use glam::Vec3;
@@ -11,13 +20,26 @@ use serde::{Deserialize, Serialize};
 #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct PrimitiveOp {
     id: PrimitiveOpId,
+    /// `None` if the primitive op is at the root of the object
+    group: Option<PrimitiveOpGroupId>,
     pub primitive: Primitive,
     pub transform: PrimitiveTransform,
     pub op: Operation,
     /// Amount of blending between this primitive op and the previous ops in world-space units.
     pub blend: f32,
+    /// Used when there's no material or `material_override` is set
     pub albedo: Vec3,
+    /// Used when there's no material or `material_override` is set
     pub specular: f32,
+    /// Scene material providing the albedo and specular, see
+    /// [`ObjectCollection::materials`](super::object_collection::ObjectCollection::materials)
+    pub material: Option<MaterialId>,
+    /// Use `albedo` and `specular` instead of the values of `material`
+    pub material_override: bool,
+    /// Applied in order to the primitive space before evaluating the primitive.
+    pub modifiers: Vec<DomainModifier>,
+    /// Applied in order to the distance returned by the primitive.
+    pub surface_modifiers: Vec<SurfaceModifier>,
 }
 
 impl PrimitiveOp {
@@ -32,12 +54,17 @@ impl PrimitiveOp {
     ) -> Self {
         Self {
             id,
+            group: None,
             primitive,
             transform,
             op,
             blend,
             albedo,
             specular,
+            material: None,
+            material_override: false,
+            modifiers: Vec::new(),
+            surface_modifiers: Vec::new(),
         }
     }
 
@@ -45,6 +72,57 @@ impl PrimitiveOp {
     pub fn id(&self) -> PrimitiveOpId {
         self.id
     }
+
+    #[inline]
+    pub fn group(&self) -> Option<PrimitiveOpGroupId> {
+        self.group
+    }
+
+    /// Ids are unique within an object, so copies moving between objects need a new one.
+    pub(super) fn set_id(&mut self, id: PrimitiveOpId) {
+        self.id = id;
+    }
+
+    /// Use [`Object::move_primitive_op`](super::object::Object::move_primitive_op) to keep
+    /// the primitive ops of each group together.
+    pub(super) fn set_group(&mut self, group: Option<PrimitiveOpGroupId>) {
+        self.group = group;
+    }
+
+    /// The material that provides the albedo and specular, if it isn't overridden.
+    pub fn active_material(&self) -> Option<MaterialId> {
+        if self.material_override {
+            None
+        } else {
+            self.material
+        }
+    }
+
+    /// Bounds of the primitive after the modifiers and transform are applied.
+    pub fn aabb(&self) -> Aabb {
+        if self.modifiers.is_empty() && self.surface_modifiers.is_empty() {
+            return self.primitive.aabb(self.transform);
+        }
+
+        let primitive_aabb = self.primitive.aabb(PrimitiveTransform::DEFAULT);
+        let (min, max) = self.surface_modifiers.iter().fold(
+            (primitive_aabb.min, primitive_aabb.max),
+            |(min, max), surface_modifier| surface_modifier.modified_bounds(min, max),
+        );
+        // domain modifiers map positions into primitive space, so go through them backwards to
+        // find which positions end up inside the primitive bounds
+        let (min, max) = self
+            .modifiers
+            .iter()
+            .rev()
+            .fold((min, max), |(min, max), modifier| {
+                modifier.unmodified_bounds(min, max)
+            });
+
+        let transform = self.transform.linear_transform();
+        let center = self.transform.center + transform * ((min + max) / 2.);
+        Aabb::new_transformed_box(center, max - min, transform)
+    }
 }
 
 // PRIMITIVE OP ID
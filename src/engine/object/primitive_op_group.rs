@@ -0,0 +1,148 @@
+use super::{operation::Operation, primitive_op::PrimitiveOp};
+use crate::helper::unique_id_gen::{UniqueId, UniqueIdType};
+use serde::{Deserialize, Serialize};
+
+// PRIMITIVE OP GROUP
+
+/// Primitive ops and groups inside a group are combined with each other first. The result is
+/// then combined with the previous primitive ops of the parent group (or object) using the
+/// group's `op` and `blend`, as if the group was a single primitive.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PrimitiveOpGroup {
+    id: PrimitiveOpGroupId,
+    /// `None` if the group is at the root of the object
+    parent: Option<PrimitiveOpGroupId>,
+    pub name: String,
+    pub op: Operation,
+    /// Amount of blending between this group and the previous ops in world-space units.
+    pub blend: f32,
+}
+
+impl PrimitiveOpGroup {
+    pub fn new(id: PrimitiveOpGroupId, parent: Option<PrimitiveOpGroupId>, name: String) -> Self {
+        Self {
+            id,
+            parent,
+            name,
+            op: Operation::Union,
+            blend: 0.,
+        }
+    }
+
+    #[inline]
+    pub fn id(&self) -> PrimitiveOpGroupId {
+        self.id
+    }
+
+    #[inline]
+    pub fn parent(&self) -> Option<PrimitiveOpGroupId> {
+        self.parent
+    }
+
+    /// Use [`Object::move_group`](super::object::Object::move_group) to keep the
+    /// group tree valid.
+    pub(super) fn set_parent(&mut self, parent: Option<PrimitiveOpGroupId>) {
+        self.parent = parent;
+    }
+}
+
+// EVALUATION STEP
+
+/// The primitive op tree of an object flattened into the order that `map()` in
+/// `scene_geometry.frag` evaluates it in.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EvaluationStep<'a> {
+    /// Starts combining the following steps into a new result
+    GroupBegin(&'a PrimitiveOpGroup),
+    Primitive(&'a PrimitiveOp),
+    /// Combines the result of the group with the result before the group began
+    GroupEnd(&'a PrimitiveOpGroup),
+}
+
+/// Returns the steps to evaluate `primitive_ops` in order, opening and closing the groups they
+/// belong to along the way. Primitive ops in the same group should be next to each other,
+/// otherwise the group gets evaluated in several parts.
+pub fn evaluation_steps<'a>(
+    primitive_ops: &'a [PrimitiveOp],
+    groups: &'a [PrimitiveOpGroup],
+) -> Vec<EvaluationStep<'a>> {
+    let mut steps = Vec::with_capacity(primitive_ops.len());
+    let mut open_groups: Vec<&PrimitiveOpGroup> = Vec::new();
+
+    for primitive_op in primitive_ops {
+        let ancestors = group_ancestors(primitive_op.group(), groups);
+
+        // close the groups that this primitive op isn't in
+        let common_depth = open_groups
+            .iter()
+            .zip(&ancestors)
+            .take_while(|(open_group, ancestor)| open_group.id() == ancestor.id())
+            .count();
+        while open_groups.len() > common_depth {
+            let closed_group = open_groups.pop().expect("checked length");
+            steps.push(EvaluationStep::GroupEnd(closed_group));
+        }
+
+        for group in &ancestors[common_depth..] {
+            steps.push(EvaluationStep::GroupBegin(group));
+            open_groups.push(group);
+        }
+
+        steps.push(EvaluationStep::Primitive(primitive_op));
+    }
+
+    while let Some(closed_group) = open_groups.pop() {
+        steps.push(EvaluationStep::GroupEnd(closed_group));
+    }
+    steps
+}
+
+/// Returns `group_id` and its ancestors, starting from the root. Invalid ids are skipped.
+pub fn group_ancestors(
+    group_id: Option<PrimitiveOpGroupId>,
+    groups: &[PrimitiveOpGroup],
+) -> Vec<&PrimitiveOpGroup> {
+    let mut ancestors = Vec::new();
+    let mut next_id = group_id;
+    while let Some(id) = next_id {
+        let Some(group) = groups.iter().find(|group| group.id() == id) else {
+            break;
+        };
+        // guards against cycles which shouldn't be possible anyway
+        if ancestors
+            .iter()
+            .any(|ancestor: &&PrimitiveOpGroup| ancestor.id() == id)
+        {
+            break;
+        }
+        ancestors.push(group);
+        next_id = group.parent();
+    }
+    ancestors.reverse();
+    ancestors
+}
+
+// PRIMITIVE OP GROUP ID
+
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub struct PrimitiveOpGroupId(pub UniqueId);
+
+impl UniqueIdType for PrimitiveOpGroupId {
+    fn raw_id(&self) -> UniqueId {
+        self.0
+    }
+}
+
+impl From<UniqueId> for PrimitiveOpGroupId {
+    fn from(id: UniqueId) -> Self {
+        Self(id)
+    }
+}
+
+impl std::fmt::Display for PrimitiveOpGroupId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.raw_id())
+    }
+}
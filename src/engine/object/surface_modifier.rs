@@ -0,0 +1,206 @@
+use crate::renderer::shader_interfaces::primitive_op_buffer::{
+    surface_modifier_codes, PrimitiveOpBufferUnit, FBM_OCTAVES, SURFACE_MODIFIER_LEN,
+};
+use glam::Vec3;
+use serde::{Deserialize, Serialize};
+
+/// Changes the distance returned by a primitive's signed distance function. A primitive op
+/// applies its surface modifiers in order after evaluating the primitive.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum SurfaceModifier {
+    /// Moves the surface in and out by a procedural pattern
+    Displace {
+        pattern: NoisePattern,
+        /// Maximum distance the surface moves
+        amplitude: f32,
+        /// Pattern features per unit
+        frequency: f32,
+        seed: u32,
+    },
+    /// Inflates the surface by `radius`, rounding off edges and corners
+    Round { radius: f32 },
+    /// Hollows out the primitive, leaving a wall of `thickness` inside the original surface
+    Shell { thickness: f32 },
+    /// Replaces the surface with concentric shells. The first shell is `thickness` thick on
+    /// either side of the surface and each subsequent layer splits the previous shells in two
+    /// with half the thickness.
+    Onion { thickness: f32, layers: u32 },
+    /// Keeps just the surface with no inside, using the absolute distance
+    SurfaceOnly,
+}
+
+impl SurfaceModifier {
+    pub const VARIANTS: &'static [SurfaceModifier] = &[
+        Self::Displace {
+            pattern: NoisePattern::Fbm,
+            amplitude: 0.05,
+            frequency: 4.,
+            seed: 0,
+        },
+        Self::Round { radius: 0.05 },
+        Self::Shell { thickness: 0.05 },
+        Self::Onion {
+            thickness: 0.05,
+            layers: 1,
+        },
+        Self::SurfaceOnly,
+    ];
+
+    pub fn variants_with_names() -> Vec<(Self, &'static str)> {
+        Self::VARIANTS
+            .iter()
+            .map(|modifier| (*modifier, modifier.name()))
+            .collect()
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Displace { .. } => "Displace",
+            Self::Round { .. } => "Round",
+            Self::Shell { .. } => "Shell",
+            Self::Onion { .. } => "Onion",
+            Self::SurfaceOnly => "Surface only",
+        }
+    }
+
+    /// Modifier code followed by its parameters.
+    /// Corresponds to `apply_surface_modifiers()` in `scene_geometry.frag`.
+    pub fn encoded(&self) -> [PrimitiveOpBufferUnit; SURFACE_MODIFIER_LEN] {
+        match *self {
+            Self::Displace {
+                pattern,
+                amplitude,
+                frequency,
+                seed,
+            } => [
+                pattern.code(),
+                amplitude.to_bits(),
+                frequency.to_bits(),
+                seed,
+                self.distance_scale().to_bits(),
+            ],
+            Self::Round { radius } => [
+                surface_modifier_codes::ROUND,
+                radius.to_bits(),
+                0,
+                0,
+                self.distance_scale().to_bits(),
+            ],
+            Self::Shell { thickness } => [
+                surface_modifier_codes::SHELL,
+                thickness.max(0.).to_bits(),
+                0,
+                0,
+                self.distance_scale().to_bits(),
+            ],
+            Self::Onion { thickness, layers } => [
+                surface_modifier_codes::ONION,
+                thickness.max(0.).to_bits(),
+                layers,
+                0,
+                self.distance_scale().to_bits(),
+            ],
+            Self::SurfaceOnly => [
+                surface_modifier_codes::SURFACE_ONLY,
+                0,
+                0,
+                0,
+                self.distance_scale().to_bits(),
+            ],
+        }
+    }
+
+    /// Factor applied to the modified distance to stop the ray marcher from overshooting the
+    /// surface where the modifier makes distances change faster than 1 unit per unit.
+    pub fn distance_scale(&self) -> f32 {
+        match *self {
+            Self::Displace {
+                pattern,
+                amplitude,
+                frequency,
+                ..
+            } => 1. / (1. + (amplitude * frequency).abs() * pattern.max_gradient()),
+            // offsetting and taking the absolute value of a distance keep it exact
+            Self::Round { .. } | Self::Shell { .. } | Self::Onion { .. } | Self::SurfaceOnly => 1.,
+        }
+    }
+
+    /// Distance the surface can move outwards. Bounding boxes need to grow by this much.
+    pub fn max_outset(&self) -> f32 {
+        match *self {
+            Self::Displace { amplitude, .. } => amplitude.abs(),
+            Self::Round { radius } => radius.max(0.),
+            // the onion layers have a total thickness of t + t/2 + t/4...
+            Self::Onion { thickness, layers } => {
+                thickness.max(0.) * (2. - 0.5_f32.powi(layers as i32 - 1))
+            }
+            Self::Shell { .. } | Self::SurfaceOnly => 0.,
+        }
+    }
+
+    /// Given the bounds (`min`, `max`) of the unmodified shape, returns bounds of the modified
+    /// shape.
+    pub fn modified_bounds(&self, min: Vec3, max: Vec3) -> (Vec3, Vec3) {
+        let outset = self.max_outset();
+        (min - outset, max + outset)
+    }
+}
+
+// ~~ Noise Pattern ~~
+
+/// Procedural patterns with values in [-1, 1]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NoisePattern {
+    /// Smoothly interpolated random values on a grid
+    Value,
+    /// Several octaves of value noise
+    Fbm,
+    /// Distance to randomly scattered points. Produces cell-like bumps.
+    Voronoi,
+    /// Product of sine waves along each axis
+    Sine,
+}
+
+impl NoisePattern {
+    pub const VARIANTS: &'static [NoisePattern] =
+        &[Self::Value, Self::Fbm, Self::Voronoi, Self::Sine];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Value => "Value noise",
+            Self::Fbm => "fBm",
+            Self::Voronoi => "Voronoi",
+            Self::Sine => "Sine",
+        }
+    }
+
+    pub fn code(&self) -> PrimitiveOpBufferUnit {
+        match self {
+            Self::Value => surface_modifier_codes::DISPLACE_VALUE_NOISE,
+            Self::Fbm => surface_modifier_codes::DISPLACE_FBM,
+            Self::Voronoi => surface_modifier_codes::DISPLACE_VORONOI,
+            Self::Sine => surface_modifier_codes::DISPLACE_SINE,
+        }
+    }
+
+    /// Upper bound of how fast the pattern changes per feature (at a frequency of 1).
+    pub fn max_gradient(&self) -> f32 {
+        // values differ by up to 2 between grid points and the smoothstep slope peaks at 1.5
+        const VALUE_NOISE_MAX_GRADIENT: f32 = 3. * 1.7320508;
+        match self {
+            Self::Value => VALUE_NOISE_MAX_GRADIENT,
+            // each octave doubles the frequency and halves the amplitude
+            Self::Fbm => VALUE_NOISE_MAX_GRADIENT * FBM_OCTAVES as f32 / fbm_amplitude_sum(),
+            // twice the distance to the nearest point
+            Self::Voronoi => 2.,
+            // one full wave per unit
+            Self::Sine => std::f32::consts::TAU,
+        }
+    }
+}
+
+/// Sum of the octave amplitudes (1 + 1/2 + 1/4 + ...) that fBm is divided by to keep its values
+/// in [-1, 1].
+pub fn fbm_amplitude_sum() -> f32 {
+    2. - 0.5_f32.powi(FBM_OCTAVES as i32 - 1)
+}
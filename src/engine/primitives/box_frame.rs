@@ -0,0 +1,73 @@
+use super::{primitive::EncodablePrimitive, primitive_transform::PrimitiveTransform};
+use crate::{
+    engine::{
+        aabb::Aabb,
+        config_engine::{primitive_names, DEFAULT_DIMENSIONS, DEFAULT_THICKNESS},
+    },
+    renderer::shader_interfaces::primitive_op_buffer::{
+        primitive_codes, PrimitiveOpBufferUnit, PrimitivePropsSlice,
+    },
+};
+use glam::Vec3;
+use serde::{Deserialize, Serialize};
+
+/// The edges of a box
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BoxFrame {
+    /// x/y/z lengths of the box
+    pub dimensions: Vec3,
+    /// Width of the edges
+    pub thickness: f32,
+}
+
+impl BoxFrame {
+    pub const fn new(dimensions: Vec3, thickness: f32) -> Self {
+        Self {
+            dimensions,
+            thickness,
+        }
+    }
+
+    pub const DEFAULT: Self = Self {
+        dimensions: DEFAULT_DIMENSIONS,
+        thickness: DEFAULT_THICKNESS,
+    };
+}
+
+impl Default for BoxFrame {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl EncodablePrimitive for BoxFrame {
+    fn type_name(&self) -> &'static str {
+        primitive_names::BOX_FRAME
+    }
+
+    fn type_code(&self) -> PrimitiveOpBufferUnit {
+        primitive_codes::BOX_FRAME
+    }
+
+    fn encoded_props(&self) -> PrimitivePropsSlice {
+        let half_dimensions = self.dimensions / 2.;
+        // the shader expects half the edge width
+        let half_thickness = self.thickness / 2.;
+        [
+            half_dimensions.x.to_bits(),
+            half_dimensions.y.to_bits(),
+            half_dimensions.z.to_bits(),
+            half_thickness.to_bits(),
+            0,
+            0,
+        ]
+    }
+
+    fn aabb(&self, primitive_transform: PrimitiveTransform) -> Aabb {
+        Aabb::new_transformed_box(
+            primitive_transform.center,
+            self.dimensions,
+            primitive_transform.linear_transform(),
+        )
+    }
+}
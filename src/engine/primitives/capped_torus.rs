@@ -0,0 +1,80 @@
+use super::{primitive::EncodablePrimitive, primitive_transform::PrimitiveTransform};
+use crate::{
+    engine::{
+        aabb::{disc_half_extents, sphere_half_extents, Aabb},
+        config_engine::{primitive_names, DEFAULT_ARC_ANGLE, DEFAULT_MINOR_RADIUS, DEFAULT_RADIUS},
+    },
+    helper::angle::Angle,
+    renderer::shader_interfaces::primitive_op_buffer::{
+        primitive_codes, PrimitiveOpBufferUnit, PrimitivePropsSlice,
+    },
+};
+use serde::{Deserialize, Serialize};
+
+/// A section of a [`Torus`](super::torus::Torus) ring around the z axis. The arc is centered on
+/// the positive y axis.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CappedTorus {
+    /// Distance from the center to the middle of the ring
+    pub radius_major: f32,
+    /// Radius of the ring cross-section
+    pub radius_minor: f32,
+    /// Angle covered by the ring, between 0 and a full revolution
+    pub arc_angle: Angle,
+}
+
+impl CappedTorus {
+    pub const fn new(radius_major: f32, radius_minor: f32, arc_angle: Angle) -> Self {
+        Self {
+            radius_major,
+            radius_minor,
+            arc_angle,
+        }
+    }
+
+    pub const DEFAULT: Self = Self {
+        radius_major: DEFAULT_RADIUS,
+        radius_minor: DEFAULT_MINOR_RADIUS,
+        arc_angle: DEFAULT_ARC_ANGLE,
+    };
+}
+
+impl Default for CappedTorus {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl EncodablePrimitive for CappedTorus {
+    fn type_name(&self) -> &'static str {
+        primitive_names::CAPPED_TORUS
+    }
+
+    fn type_code(&self) -> PrimitiveOpBufferUnit {
+        primitive_codes::CAPPED_TORUS
+    }
+
+    fn encoded_props(&self) -> PrimitivePropsSlice {
+        // the shader takes the sin/cos of half the arc angle
+        let half_arc_angle = (self.arc_angle.radians() / 2.).clamp(0., std::f64::consts::PI);
+        let cap_sin = half_arc_angle.sin() as f32;
+        let cap_cos = half_arc_angle.cos() as f32;
+        [
+            self.radius_major.to_bits(),
+            self.radius_minor.to_bits(),
+            cap_sin.to_bits(),
+            cap_cos.to_bits(),
+            0,
+            0,
+        ]
+    }
+
+    fn aabb(&self, primitive_transform: PrimitiveTransform) -> Aabb {
+        // todo tighter fit for small arc angles
+        // the ring swept by the cross-section circle
+        let transform = primitive_transform.linear_transform();
+        let half_extents = disc_half_extents(self.radius_major, transform)
+            + sphere_half_extents(self.radius_minor, transform);
+        Aabb::new(primitive_transform.center, half_extents * 2.)
+    }
+}
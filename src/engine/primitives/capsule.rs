@@ -0,0 +1,70 @@
+use super::{primitive::EncodablePrimitive, primitive_transform::PrimitiveTransform};
+use crate::{
+    engine::{
+        aabb::{sphere_half_extents, z_segment_half_extents, Aabb},
+        config_engine::{primitive_names, DEFAULT_HEIGHT, DEFAULT_RADIUS},
+    },
+    renderer::shader_interfaces::primitive_op_buffer::{
+        primitive_codes, PrimitiveOpBufferUnit, PrimitivePropsSlice,
+    },
+};
+use serde::{Deserialize, Serialize};
+
+/// A cylinder along the z axis with hemispherical ends.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Capsule {
+    pub radius: f32,
+    /// Total length along the z axis including the rounded ends
+    pub height: f32,
+}
+
+impl Capsule {
+    pub const fn new(radius: f32, height: f32) -> Self {
+        Self { radius, height }
+    }
+
+    pub const DEFAULT: Self = Self {
+        radius: DEFAULT_RADIUS / 2.,
+        height: DEFAULT_HEIGHT,
+    };
+
+    /// Half the distance between the centers of the two rounded ends
+    #[inline]
+    pub fn half_segment_length(&self) -> f32 {
+        (self.height / 2. - self.radius).max(0.)
+    }
+}
+
+impl Default for Capsule {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl EncodablePrimitive for Capsule {
+    fn type_name(&self) -> &'static str {
+        primitive_names::CAPSULE
+    }
+
+    fn type_code(&self) -> PrimitiveOpBufferUnit {
+        primitive_codes::CAPSULE
+    }
+
+    fn encoded_props(&self) -> PrimitivePropsSlice {
+        [
+            self.radius.to_bits(),
+            self.half_segment_length().to_bits(),
+            0,
+            0,
+            0,
+            0,
+        ]
+    }
+
+    fn aabb(&self, primitive_transform: PrimitiveTransform) -> Aabb {
+        let transform = primitive_transform.linear_transform();
+        let half_extents = sphere_half_extents(self.radius, transform)
+            + z_segment_half_extents(self.half_segment_length(), transform);
+        Aabb::new(primitive_transform.center, half_extents * 2.)
+    }
+}
@@ -0,0 +1,87 @@
+use super::{primitive::EncodablePrimitive, primitive_transform::PrimitiveTransform};
+use crate::{
+    engine::{
+        aabb::{disc_half_extents, Aabb},
+        config_engine::{primitive_names, DEFAULT_HEIGHT, DEFAULT_RADIUS},
+    },
+    renderer::shader_interfaces::primitive_op_buffer::{
+        primitive_codes, PrimitiveOpBufferUnit, PrimitivePropsSlice,
+    },
+};
+use serde::{Deserialize, Serialize};
+
+/// A cone along the z axis with the base at the bottom. Setting `cap_radius` truncates the tip.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Cone {
+    /// Radius of the base
+    pub radius: f32,
+    /// Length along the z axis
+    pub height: f32,
+    /// Radius of the flat top. `None` for a pointed tip.
+    pub cap_radius: Option<f32>,
+}
+
+impl Cone {
+    pub const fn new(radius: f32, height: f32, cap_radius: Option<f32>) -> Self {
+        Self {
+            radius,
+            height,
+            cap_radius,
+        }
+    }
+
+    pub const DEFAULT: Self = Self {
+        radius: DEFAULT_RADIUS,
+        height: DEFAULT_HEIGHT,
+        cap_radius: None,
+    };
+
+    #[inline]
+    pub fn cap_radius_or_zero(&self) -> f32 {
+        self.cap_radius.unwrap_or(0.)
+    }
+}
+
+impl Default for Cone {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl EncodablePrimitive for Cone {
+    fn type_name(&self) -> &'static str {
+        primitive_names::CONE
+    }
+
+    fn type_code(&self) -> PrimitiveOpBufferUnit {
+        primitive_codes::CONE
+    }
+
+    fn encoded_props(&self) -> PrimitivePropsSlice {
+        let half_height = self.height / 2.;
+        [
+            self.radius.to_bits(),
+            self.cap_radius_or_zero().to_bits(),
+            half_height.to_bits(),
+            0,
+            0,
+            0,
+        ]
+    }
+
+    fn aabb(&self, primitive_transform: PrimitiveTransform) -> Aabb {
+        let center = primitive_transform.center;
+        let transform = primitive_transform.linear_transform();
+        let half_height_offset = transform.z_axis * (self.height / 2.);
+
+        // bounds of the base and top discs
+        let base_half_extents = disc_half_extents(self.radius, transform);
+        let mut aabb = Aabb::new(center - half_height_offset, base_half_extents * 2.);
+        let top_half_extents = disc_half_extents(self.cap_radius_or_zero(), transform);
+        aabb.union(Aabb::new(
+            center + half_height_offset,
+            top_half_extents * 2.,
+        ));
+        aabb
+    }
+}
@@ -4,9 +4,11 @@ use crate::{
         aabb::Aabb,
         config_engine::{primitive_names, DEFAULT_DIMENSIONS},
     },
-    renderer::shader_interfaces::primitive_op_buffer::PrimitivePropsSlice,
+    renderer::shader_interfaces::primitive_op_buffer::{
+        primitive_codes, PrimitiveOpBufferUnit, PrimitivePropsSlice,
+    },
 };
-use glam::{Vec2, Vec3};
+use glam::Vec3;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
@@ -35,55 +37,28 @@ impl EncodablePrimitive for Cube {
         primitive_names::CUBE
     }
 
+    fn type_code(&self) -> PrimitiveOpBufferUnit {
+        primitive_codes::CUBE
+    }
+
     fn encoded_props(&self) -> PrimitivePropsSlice {
-        let width = self.dimensions.x / 2.0;
-        let depth = self.dimensions.y / 2.0;
-        let height = self.dimensions.z / 2.0;
-        let thickness = 0.5_f32;
-        let corner_radius = Vec2::new(-1.0, 0.0);
+        let half_dimensions = self.dimensions / 2.;
         [
-            width.to_bits(),
-            depth.to_bits(),
-            height.to_bits(),
-            thickness.to_bits(),
-            corner_radius.x.to_bits(),
-            corner_radius.y.to_bits(),
+            half_dimensions.x.to_bits(),
+            half_dimensions.y.to_bits(),
+            half_dimensions.z.to_bits(),
+            0,
+            0,
+            0,
         ]
     }
 
     fn aabb(&self, primitive_transform: PrimitiveTransform) -> Aabb {
         // todo calculate only when props/transform changed? cache result?
-
-        let half_dimensions = self.dimensions / 2_f32;
-        let four_corners = vec![
-            half_dimensions,
-            Vec3 {
-                x: -half_dimensions.x,
-                ..half_dimensions
-            },
-            Vec3 {
-                y: -half_dimensions.y,
-                ..half_dimensions
-            },
-            Vec3 {
-                z: -half_dimensions.z,
-                ..half_dimensions
-            },
-        ];
-
-        let rotation = primitive_transform.total_rotation();
-        let rotated_four_corners_abs = four_corners
-            .into_iter()
-            .map(|corner| rotation.mul_vec3(corner).abs())
-            .collect::<Vec<_>>();
-
-        let mut aabb_dimensions = Vec3::ZERO;
-        for rotated_corner in rotated_four_corners_abs {
-            aabb_dimensions.x = aabb_dimensions.x.max(rotated_corner.x);
-            aabb_dimensions.y = aabb_dimensions.y.max(rotated_corner.y);
-            aabb_dimensions.z = aabb_dimensions.z.max(rotated_corner.z);
-        }
-
-        Aabb::new(primitive_transform.center, aabb_dimensions * 2_f32)
+        Aabb::new_transformed_box(
+            primitive_transform.center,
+            self.dimensions,
+            primitive_transform.linear_transform(),
+        )
     }
 }
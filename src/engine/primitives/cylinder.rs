@@ -0,0 +1,58 @@
+use super::{primitive::EncodablePrimitive, primitive_transform::PrimitiveTransform};
+use crate::{
+    engine::{
+        aabb::{disc_half_extents, z_segment_half_extents, Aabb},
+        config_engine::{primitive_names, DEFAULT_HEIGHT, DEFAULT_RADIUS},
+    },
+    renderer::shader_interfaces::primitive_op_buffer::{
+        primitive_codes, PrimitiveOpBufferUnit, PrimitivePropsSlice,
+    },
+};
+use serde::{Deserialize, Serialize};
+
+/// A cylinder along the z axis.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Cylinder {
+    pub radius: f32,
+    /// Length along the z axis
+    pub height: f32,
+}
+
+impl Cylinder {
+    pub const fn new(radius: f32, height: f32) -> Self {
+        Self { radius, height }
+    }
+
+    pub const DEFAULT: Self = Self {
+        radius: DEFAULT_RADIUS,
+        height: DEFAULT_HEIGHT,
+    };
+}
+
+impl Default for Cylinder {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl EncodablePrimitive for Cylinder {
+    fn type_name(&self) -> &'static str {
+        primitive_names::CYLINDER
+    }
+
+    fn type_code(&self) -> PrimitiveOpBufferUnit {
+        primitive_codes::CYLINDER
+    }
+
+    fn encoded_props(&self) -> PrimitivePropsSlice {
+        let half_height = self.height / 2.;
+        [self.radius.to_bits(), half_height.to_bits(), 0, 0, 0, 0]
+    }
+
+    fn aabb(&self, primitive_transform: PrimitiveTransform) -> Aabb {
+        let transform = primitive_transform.linear_transform();
+        let half_extents = disc_half_extents(self.radius, transform)
+            + z_segment_half_extents(self.height / 2., transform);
+        Aabb::new(primitive_transform.center, half_extents * 2.)
+    }
+}
@@ -0,0 +1,68 @@
+use super::{
+    primitive::EncodablePrimitive, primitive_transform::PrimitiveTransform, profile::Profile,
+};
+use crate::{
+    engine::{
+        aabb::{box_half_extents, Aabb},
+        config_engine::{primitive_names, DEFAULT_HEIGHT},
+    },
+    renderer::shader_interfaces::primitive_op_buffer::{
+        primitive_codes, PrimitiveOpBufferUnit, PrimitivePropsSlice,
+    },
+};
+use serde::{Deserialize, Serialize};
+
+/// A 2D profile in the xy plane extruded along the z axis.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Extrusion {
+    pub profile: Profile,
+    /// Length along the z axis
+    pub depth: f32,
+}
+
+impl Extrusion {
+    pub const fn new(profile: Profile, depth: f32) -> Self {
+        Self { profile, depth }
+    }
+
+    pub const DEFAULT: Self = Self {
+        profile: Profile::DEFAULT,
+        depth: DEFAULT_HEIGHT,
+    };
+}
+
+impl Default for Extrusion {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl EncodablePrimitive for Extrusion {
+    fn type_name(&self) -> &'static str {
+        primitive_names::EXTRUSION
+    }
+
+    fn type_code(&self) -> PrimitiveOpBufferUnit {
+        primitive_codes::EXTRUSION
+    }
+
+    fn encoded_props(&self) -> PrimitivePropsSlice {
+        let half_depth = self.depth / 2.;
+        [half_depth.to_bits(), 0, 0, 0, 0, 0]
+    }
+
+    fn encoded_side_data(&self) -> Vec<PrimitiveOpBufferUnit> {
+        self.profile.encoded()
+    }
+
+    fn aabb(&self, primitive_transform: PrimitiveTransform) -> Aabb {
+        let (profile_min, profile_max) = self.profile.bounds();
+        let local_center = ((profile_min + profile_max) / 2.).extend(0.);
+        let local_half_dimensions = ((profile_max - profile_min) / 2.).extend(self.depth / 2.);
+
+        let transform = primitive_transform.linear_transform();
+        let center = primitive_transform.center + transform * local_center;
+        let half_extents = box_half_extents(local_half_dimensions, transform);
+        Aabb::new(center, half_extents * 2.)
+    }
+}
@@ -1,5 +1,15 @@
+pub mod box_frame;
+pub mod capped_torus;
+pub mod capsule;
+pub mod cone;
 pub mod cube;
+pub mod cylinder;
+pub mod extrusion;
 pub mod primitive;
 pub mod primitive_transform;
+pub mod profile;
+pub mod revolution;
 pub mod sphere;
+pub mod torus;
+pub mod tube;
 pub mod uber_primitive;
@@ -1,19 +1,33 @@
 use super::{
-    cube::Cube, primitive_transform::PrimitiveTransform, sphere::Sphere,
+    box_frame::BoxFrame, capped_torus::CappedTorus, capsule::Capsule, cone::Cone, cube::Cube,
+    cylinder::Cylinder, extrusion::Extrusion, primitive_transform::PrimitiveTransform,
+    revolution::Revolution, sphere::Sphere, torus::Torus, tube::Tube,
     uber_primitive::UberPrimitive,
 };
 use crate::{
-    engine::aabb::Aabb, helper::from_enum_macro::impl_from_for_enum_variant,
-    renderer::shader_interfaces::primitive_op_buffer::PrimitivePropsSlice,
+    engine::aabb::Aabb,
+    helper::from_enum_macro::impl_from_for_enum_variant,
+    renderer::shader_interfaces::primitive_op_buffer::{
+        PrimitiveOpBufferUnit, PrimitivePropsSlice,
+    },
 };
 use serde::{Deserialize, Serialize};
 
 // ~~ Primitive ~~
 
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Primitive {
     Cube(Cube),
     Sphere(Sphere),
+    Torus(Torus),
+    CappedTorus(CappedTorus),
+    BoxFrame(BoxFrame),
+    Cylinder(Cylinder),
+    Cone(Cone),
+    Capsule(Capsule),
+    Extrusion(Extrusion),
+    Revolution(Revolution),
+    Tube(Tube),
     UberPrimitive(UberPrimitive),
 }
 
@@ -28,6 +42,15 @@ impl Primitive {
     pub const VARIANTS: &'static [Primitive] = &[
         Primitive::Cube(Cube::DEFAULT),
         Primitive::Sphere(Sphere::DEFAULT),
+        Primitive::Torus(Torus::DEFAULT),
+        Primitive::CappedTorus(CappedTorus::DEFAULT),
+        Primitive::BoxFrame(BoxFrame::DEFAULT),
+        Primitive::Cylinder(Cylinder::DEFAULT),
+        Primitive::Cone(Cone::DEFAULT),
+        Primitive::Capsule(Capsule::DEFAULT),
+        Primitive::Extrusion(Extrusion::DEFAULT),
+        Primitive::Revolution(Revolution::DEFAULT),
+        Primitive::Tube(Tube::DEFAULT),
         Primitive::UberPrimitive(UberPrimitive::DEFAULT),
     ];
     pub const DEFAULT: Primitive = Primitive::Cube(Cube::DEFAULT);
@@ -44,6 +67,15 @@ macro_rules! primitive_fn_match {
         match $self {
             Self::Sphere(p) => p.$primitive_fn(),
             Self::Cube(p) => p.$primitive_fn(),
+            Self::Torus(p) => p.$primitive_fn(),
+            Self::CappedTorus(p) => p.$primitive_fn(),
+            Self::BoxFrame(p) => p.$primitive_fn(),
+            Self::Cylinder(p) => p.$primitive_fn(),
+            Self::Cone(p) => p.$primitive_fn(),
+            Self::Capsule(p) => p.$primitive_fn(),
+            Self::Extrusion(p) => p.$primitive_fn(),
+            Self::Revolution(p) => p.$primitive_fn(),
+            Self::Tube(p) => p.$primitive_fn(),
             Self::UberPrimitive(p) => p.$primitive_fn(),
         }
     };
@@ -54,14 +86,31 @@ impl EncodablePrimitive for Primitive {
         primitive_fn_match!(self, type_name)
     }
 
+    fn type_code(&self) -> PrimitiveOpBufferUnit {
+        primitive_fn_match!(self, type_code)
+    }
+
     fn encoded_props(&self) -> PrimitivePropsSlice {
         primitive_fn_match!(self, encoded_props)
     }
 
+    fn encoded_side_data(&self) -> Vec<PrimitiveOpBufferUnit> {
+        primitive_fn_match!(self, encoded_side_data)
+    }
+
     fn aabb(&self, primitive_transform: PrimitiveTransform) -> Aabb {
         match self {
             Self::Sphere(p) => p.aabb(primitive_transform),
             Self::Cube(p) => p.aabb(primitive_transform),
+            Self::Torus(p) => p.aabb(primitive_transform),
+            Self::CappedTorus(p) => p.aabb(primitive_transform),
+            Self::BoxFrame(p) => p.aabb(primitive_transform),
+            Self::Cylinder(p) => p.aabb(primitive_transform),
+            Self::Cone(p) => p.aabb(primitive_transform),
+            Self::Capsule(p) => p.aabb(primitive_transform),
+            Self::Extrusion(p) => p.aabb(primitive_transform),
+            Self::Revolution(p) => p.aabb(primitive_transform),
+            Self::Tube(p) => p.aabb(primitive_transform),
             Self::UberPrimitive(p) => p.aabb(primitive_transform),
         }
     }
@@ -69,6 +118,15 @@ impl EncodablePrimitive for Primitive {
 
 impl_from_for_enum_variant!(Primitive, Cube);
 impl_from_for_enum_variant!(Primitive, Sphere);
+impl_from_for_enum_variant!(Primitive, Torus);
+impl_from_for_enum_variant!(Primitive, CappedTorus);
+impl_from_for_enum_variant!(Primitive, BoxFrame);
+impl_from_for_enum_variant!(Primitive, Cylinder);
+impl_from_for_enum_variant!(Primitive, Cone);
+impl_from_for_enum_variant!(Primitive, Capsule);
+impl_from_for_enum_variant!(Primitive, Extrusion);
+impl_from_for_enum_variant!(Primitive, Revolution);
+impl_from_for_enum_variant!(Primitive, Tube);
 impl_from_for_enum_variant!(Primitive, UberPrimitive);
 
 // ~~ Encodable Primitive ~~
@@ -78,13 +136,24 @@ pub trait EncodablePrimitive: Send + Sync + Serialize {
     /// Returns the primitive type as a str
     fn type_name(&self) -> &'static str;
 
+    /// Returns the code used by the shaders to select the signed distance function for this
+    /// primitive. See [`primitive_codes`](crate::renderer::shader_interfaces::primitive_op_buffer::primitive_codes).
+    fn type_code(&self) -> PrimitiveOpBufferUnit;
+
     /// Returns buffer compatible primitive data as a [`PrimitivePropsSlice`].
-    /// `parent_origin` is the world space origin of the parent object, which should be added to
-    /// the primitive center before encoding.
     ///
     /// _Note: must match the decode process in `scene_geometry.frag`_
     fn encoded_props(&self) -> PrimitivePropsSlice;
 
+    /// Returns variable-length data that doesn't fit in [`PrimitivePropsSlice`] e.g. profile
+    /// vertices. This gets written to the side-data region after the primitive op packets and
+    /// the shaders find it via the packet's side-data offset.
+    ///
+    /// _Note: must match the decode process in `scene_geometry.frag`_
+    fn encoded_side_data(&self) -> Vec<PrimitiveOpBufferUnit> {
+        Vec::new()
+    }
+
     /// Axis aligned bounding box
     fn aabb(&self, primitive_transform: PrimitiveTransform) -> Aabb;
 }
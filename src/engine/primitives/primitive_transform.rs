@@ -1,13 +1,17 @@
 use crate::{
+    engine::object::object_transform::ObjectTransform,
     helper::{
         angle::Angle,
         axis::{Axis, AxisRotation},
     },
     renderer::shader_interfaces::primitive_op_buffer::PrimitiveTransformSlice,
 };
-use glam::{Mat3, Quat, Vec3};
+use glam::{BVec3, Mat3, Quat, Vec3};
 use serde::{Deserialize, Serialize};
 
+/// Smallest scale allowed on any axis to avoid dividing by zero in the shaders
+pub const MIN_SCALE: f32 = 0.001;
+
 #[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub struct PrimitiveTransform {
     /// Primitive translation relative to object origin
@@ -17,6 +21,10 @@ pub struct PrimitiveTransform {
     rotation_tentative_append: AxisRotation,
     /// Primitive rotation quaternion
     rotation: Quat,
+    /// Per-axis scale applied before the rotation. Should be positive, use `mirror` to flip axes.
+    pub scale: Vec3,
+    /// Flips the primitive along each of its local axes
+    pub mirror: BVec3,
 }
 
 impl PrimitiveTransform {
@@ -38,26 +46,64 @@ impl PrimitiveTransform {
         Mat3::from_quat(self.total_rotation())
     }
 
-    pub fn gpu_encoded(&self, parent_origin: Vec3) -> PrimitiveTransformSlice {
-        let rotation_cols_array = self.rotation_matrix().to_cols_array();
+    /// Scale with zero/negative values clamped and the mirrored axes negated.
+    pub fn signed_scale(&self) -> Vec3 {
+        let scale = self.scale.max(Vec3::splat(MIN_SCALE));
+        Vec3::select(self.mirror, -scale, scale)
+    }
+
+    /// Maps primitive local space to world space orientation: mirror and scale, then rotation.
+    pub fn linear_transform(&self) -> Mat3 {
+        self.rotation_matrix() * Mat3::from_diagonal(self.signed_scale())
+    }
+
+    /// `parent_transform` places the parent object in the world and gets baked into the
+    /// encoded center and matrix.
+    pub fn gpu_encoded(&self, parent_transform: &ObjectTransform) -> PrimitiveTransformSlice {
+        // the object scale is uniform so it commutes with the primitive rotation
+        let rotation = parent_transform.rotation * self.total_rotation();
+        let signed_scale = self.signed_scale() * parent_transform.clamped_scale();
+
+        // the shaders do `(pos - center) * matrix` which multiplies by the transpose, so passing
+        // rotation * (1 / signed scale) gives the inverse of the world space linear transform
+        let matrix = Mat3::from_quat(rotation) * Mat3::from_diagonal(signed_scale.recip());
+        let matrix_cols_array = matrix.to_cols_array();
 
-        let center = self.center + parent_origin;
+        // distances in the scaled space are at most this much larger than in world space so
+        // multiplying by it keeps the field from overshooting (lipschitz bound of 1)
+        let distance_scale = signed_scale.abs().min_element();
+
+        let center = parent_transform.transform_point(self.center);
         [
             center.x.to_bits(),
             center.y.to_bits(),
             center.z.to_bits(),
-            rotation_cols_array[0].to_bits(),
-            rotation_cols_array[1].to_bits(),
-            rotation_cols_array[2].to_bits(),
-            rotation_cols_array[3].to_bits(),
-            rotation_cols_array[4].to_bits(),
-            rotation_cols_array[5].to_bits(),
-            rotation_cols_array[6].to_bits(),
-            rotation_cols_array[7].to_bits(),
-            rotation_cols_array[8].to_bits(),
+            matrix_cols_array[0].to_bits(),
+            matrix_cols_array[1].to_bits(),
+            matrix_cols_array[2].to_bits(),
+            matrix_cols_array[3].to_bits(),
+            matrix_cols_array[4].to_bits(),
+            matrix_cols_array[5].to_bits(),
+            matrix_cols_array[6].to_bits(),
+            matrix_cols_array[7].to_bits(),
+            matrix_cols_array[8].to_bits(),
+            distance_scale.to_bits(),
         ]
     }
 
+    /// Applies `transform` after this one, e.g. to move the primitive within its object. The
+    /// tentative rotation gets committed.
+    pub fn transformed(&self, transform: &ObjectTransform) -> Self {
+        Self {
+            center: transform.transform_point(self.center),
+            rotation_tentative_append: AxisRotation::DEFAULT,
+            // the object transform scale is uniform so it commutes with the rotation
+            rotation: (transform.rotation * self.total_rotation()).normalize(),
+            scale: self.scale * transform.clamped_scale(),
+            mirror: self.mirror,
+        }
+    }
+
     #[inline]
     pub fn rotation_tentative_append(&self) -> AxisRotation {
         self.rotation_tentative_append
@@ -88,6 +134,8 @@ impl PrimitiveTransform {
         center: Vec3::ZERO,
         rotation_tentative_append: AxisRotation::DEFAULT,
         rotation: Quat::IDENTITY,
+        scale: Vec3::ONE,
+        mirror: BVec3::FALSE,
     };
 }
 
@@ -0,0 +1,123 @@
+use crate::{
+    engine::config_engine::{DEFAULT_CORNER_RADIUS, DEFAULT_PROFILE_DIMENSIONS, DEFAULT_RADIUS},
+    renderer::shader_interfaces::primitive_op_buffer::{profile_codes, PrimitiveOpBufferUnit},
+};
+use glam::Vec2;
+use serde::{Deserialize, Serialize};
+
+/// A 2D shape that gets extruded or revolved into a 3D primitive.
+/// See [`Extrusion`](super::extrusion::Extrusion) and
+/// [`Revolution`](super::revolution::Revolution).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Profile {
+    Circle {
+        radius: f32,
+    },
+    RoundedRect {
+        /// x/y lengths of the rectangle
+        dimensions: Vec2,
+        corner_radius: f32,
+    },
+    /// Closed polygon. The last vertex connects back to the first.
+    Polygon {
+        vertices: Vec<Vec2>,
+    },
+}
+
+impl Profile {
+    pub fn variants_with_names() -> Vec<(Self, &'static str)> {
+        Self::VARIANTS
+            .iter()
+            .map(|profile| (profile.clone(), profile.type_name()))
+            .collect()
+    }
+
+    pub const VARIANTS: &'static [Profile] = &[
+        Self::DEFAULT,
+        Profile::RoundedRect {
+            dimensions: DEFAULT_PROFILE_DIMENSIONS,
+            corner_radius: DEFAULT_CORNER_RADIUS,
+        },
+        Profile::Polygon {
+            vertices: Vec::new(),
+        },
+    ];
+
+    pub const DEFAULT: Self = Self::Circle {
+        radius: DEFAULT_RADIUS,
+    };
+
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Self::Circle { .. } => "Circle",
+            Self::RoundedRect { .. } => "Rounded Rect",
+            Self::Polygon { .. } => "Polygon",
+        }
+    }
+
+    /// Triangle that polygon profiles start with when selected in the gui
+    pub fn default_polygon_vertices() -> Vec<Vec2> {
+        vec![
+            Vec2::new(-DEFAULT_RADIUS, -DEFAULT_RADIUS),
+            Vec2::new(DEFAULT_RADIUS, -DEFAULT_RADIUS),
+            Vec2::new(0., DEFAULT_RADIUS),
+        ]
+    }
+
+    /// Returns the (min, max) corners of the 2D bounding rectangle.
+    pub fn bounds(&self) -> (Vec2, Vec2) {
+        match self {
+            Self::Circle { radius } => (Vec2::splat(-radius), Vec2::splat(*radius)),
+            Self::RoundedRect { dimensions, .. } => (-*dimensions / 2., *dimensions / 2.),
+            Self::Polygon { vertices } => {
+                if vertices.is_empty() {
+                    return (Vec2::ZERO, Vec2::ZERO);
+                }
+                vertices
+                    .iter()
+                    .fold((Vec2::MAX, Vec2::MIN), |(min, max), vertex| {
+                        (min.min(*vertex), max.max(*vertex))
+                    })
+            }
+        }
+    }
+
+    /// Profile data written to the side-data region of the object buffer. Starts with a profile
+    /// code, followed by the profile parameters. Corresponds to `sdf_profile()` in
+    /// `scene_geometry.frag`.
+    pub fn encoded(&self) -> Vec<PrimitiveOpBufferUnit> {
+        match self {
+            Self::Circle { radius } => vec![profile_codes::CIRCLE, radius.to_bits()],
+            Self::RoundedRect {
+                dimensions,
+                corner_radius,
+            } => {
+                let half_dimensions = *dimensions / 2.;
+                // the corner radius can't be larger than the rectangle
+                let corner_radius = corner_radius.clamp(0., half_dimensions.min_element());
+                vec![
+                    profile_codes::ROUNDED_RECT,
+                    half_dimensions.x.to_bits(),
+                    half_dimensions.y.to_bits(),
+                    corner_radius.to_bits(),
+                ]
+            }
+            Self::Polygon { vertices } => {
+                let mut encoded = Vec::with_capacity(2 + vertices.len() * 2);
+                encoded.push(profile_codes::POLYGON);
+                encoded.push(vertices.len() as PrimitiveOpBufferUnit);
+                for vertex in vertices {
+                    encoded.push(vertex.x.to_bits());
+                    encoded.push(vertex.y.to_bits());
+                }
+                encoded
+            }
+        }
+    }
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
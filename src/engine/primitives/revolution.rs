@@ -0,0 +1,95 @@
+use super::{
+    primitive::EncodablePrimitive, primitive_transform::PrimitiveTransform, profile::Profile,
+};
+use crate::{
+    engine::{
+        aabb::{box_half_extents, Aabb},
+        config_engine::{primitive_names, DEFAULT_RADIUS},
+    },
+    helper::angle::Angle,
+    renderer::shader_interfaces::primitive_op_buffer::{
+        primitive_codes, PrimitiveOpBufferUnit, PrimitivePropsSlice,
+    },
+};
+use glam::Vec3;
+use serde::{Deserialize, Serialize};
+
+/// A 2D profile revolved around the z axis, like a part turned on a lathe. The profile x axis
+/// points away from the axis of revolution and the profile y axis points along it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Revolution {
+    pub profile: Profile,
+    /// Distance of the profile origin from the axis of revolution
+    pub offset: f32,
+    /// Angle covered by the revolution, between 0 and a full revolution. The arc is centered on
+    /// the positive y axis.
+    pub angle: Angle,
+}
+
+impl Revolution {
+    pub const fn new(profile: Profile, offset: f32, angle: Angle) -> Self {
+        Self {
+            profile,
+            offset,
+            angle,
+        }
+    }
+
+    pub const DEFAULT: Self = Self {
+        profile: Profile::Circle {
+            radius: DEFAULT_RADIUS / 2.,
+        },
+        offset: DEFAULT_RADIUS,
+        angle: Angle::Degrees(360.),
+    };
+}
+
+impl Default for Revolution {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl EncodablePrimitive for Revolution {
+    fn type_name(&self) -> &'static str {
+        primitive_names::REVOLUTION
+    }
+
+    fn type_code(&self) -> PrimitiveOpBufferUnit {
+        primitive_codes::REVOLUTION
+    }
+
+    fn encoded_props(&self) -> PrimitivePropsSlice {
+        // the shader takes the sin/cos of half the angle
+        let half_angle = (self.angle.radians() / 2.).clamp(0., std::f64::consts::PI);
+        let half_angle_sin = half_angle.sin() as f32;
+        let half_angle_cos = half_angle.cos() as f32;
+        [
+            self.offset.to_bits(),
+            half_angle_sin.to_bits(),
+            half_angle_cos.to_bits(),
+            0,
+            0,
+            0,
+        ]
+    }
+
+    fn encoded_side_data(&self) -> Vec<PrimitiveOpBufferUnit> {
+        self.profile.encoded()
+    }
+
+    fn aabb(&self, primitive_transform: PrimitiveTransform) -> Aabb {
+        // todo tighter fit for partial revolutions
+        let (profile_min, profile_max) = self.profile.bounds();
+        let radius = (self.offset + profile_min.x)
+            .abs()
+            .max((self.offset + profile_max.x).abs());
+        let local_center = Vec3::new(0., 0., (profile_min.y + profile_max.y) / 2.);
+        let local_half_dimensions = Vec3::new(radius, radius, (profile_max.y - profile_min.y) / 2.);
+
+        let transform = primitive_transform.linear_transform();
+        let center = primitive_transform.center + transform * local_center;
+        let half_extents = box_half_extents(local_half_dimensions, transform);
+        Aabb::new(center, half_extents * 2.)
+    }
+}
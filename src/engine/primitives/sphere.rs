@@ -1,12 +1,13 @@
 use super::{primitive::EncodablePrimitive, primitive_transform::PrimitiveTransform};
 use crate::{
     engine::{
-        aabb::Aabb,
+        aabb::{sphere_half_extents, Aabb},
         config_engine::{primitive_names, DEFAULT_RADIUS},
     },
-    renderer::shader_interfaces::primitive_op_buffer::PrimitivePropsSlice,
+    renderer::shader_interfaces::primitive_op_buffer::{
+        primitive_codes, PrimitiveOpBufferUnit, PrimitivePropsSlice,
+    },
 };
-use glam::{Vec2, Vec3};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
@@ -35,24 +36,17 @@ impl EncodablePrimitive for Sphere {
         primitive_names::SPHERE
     }
 
+    fn type_code(&self) -> PrimitiveOpBufferUnit {
+        primitive_codes::SPHERE
+    }
+
     fn encoded_props(&self) -> PrimitivePropsSlice {
-        let width = 0_f32;
-        let depth = 0_f32;
-        let height = self.radius;
-        let thickness = self.radius;
-        let corner_radius = Vec2::new(0_f32, self.radius);
-        [
-            width.to_bits(),
-            depth.to_bits(),
-            height.to_bits(),
-            thickness.to_bits(),
-            corner_radius.x.to_bits(),
-            corner_radius.y.to_bits(),
-        ]
+        [self.radius.to_bits(), 0, 0, 0, 0, 0]
     }
 
     fn aabb(&self, primitive_transform: PrimitiveTransform) -> Aabb {
-        // todo calculate only when props/transform changed? will need to make members private...
-        Aabb::new(primitive_transform.center, Vec3::splat(2. * self.radius))
+        let transform = primitive_transform.linear_transform();
+        let half_extents = sphere_half_extents(self.radius, transform);
+        Aabb::new(primitive_transform.center, half_extents * 2.)
     }
 }
@@ -0,0 +1,69 @@
+use super::{primitive::EncodablePrimitive, primitive_transform::PrimitiveTransform};
+use crate::{
+    engine::{
+        aabb::{disc_half_extents, sphere_half_extents, Aabb},
+        config_engine::{primitive_names, DEFAULT_MINOR_RADIUS, DEFAULT_RADIUS},
+    },
+    renderer::shader_interfaces::primitive_op_buffer::{
+        primitive_codes, PrimitiveOpBufferUnit, PrimitivePropsSlice,
+    },
+};
+use serde::{Deserialize, Serialize};
+
+/// A ring around the z axis.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Torus {
+    /// Distance from the center to the middle of the ring
+    pub radius_major: f32,
+    /// Radius of the ring cross-section
+    pub radius_minor: f32,
+}
+
+impl Torus {
+    pub const fn new(radius_major: f32, radius_minor: f32) -> Self {
+        Self {
+            radius_major,
+            radius_minor,
+        }
+    }
+
+    pub const DEFAULT: Self = Self {
+        radius_major: DEFAULT_RADIUS,
+        radius_minor: DEFAULT_MINOR_RADIUS,
+    };
+}
+
+impl Default for Torus {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl EncodablePrimitive for Torus {
+    fn type_name(&self) -> &'static str {
+        primitive_names::TORUS
+    }
+
+    fn type_code(&self) -> PrimitiveOpBufferUnit {
+        primitive_codes::TORUS
+    }
+
+    fn encoded_props(&self) -> PrimitivePropsSlice {
+        [
+            self.radius_major.to_bits(),
+            self.radius_minor.to_bits(),
+            0,
+            0,
+            0,
+            0,
+        ]
+    }
+
+    fn aabb(&self, primitive_transform: PrimitiveTransform) -> Aabb {
+        // the ring swept by the cross-section circle
+        let transform = primitive_transform.linear_transform();
+        let half_extents = disc_half_extents(self.radius_major, transform)
+            + sphere_half_extents(self.radius_minor, transform);
+        Aabb::new(primitive_transform.center, half_extents * 2.)
+    }
+}
@@ -0,0 +1,164 @@
+use super::{primitive::EncodablePrimitive, primitive_transform::PrimitiveTransform};
+use crate::{
+    engine::{
+        aabb::{sphere_half_extents, Aabb},
+        config_engine::{primitive_names, DEFAULT_MINOR_RADIUS, DEFAULT_RADIUS},
+    },
+    renderer::shader_interfaces::primitive_op_buffer::{
+        primitive_codes, tube_path_codes, PrimitiveOpBufferUnit, PrimitivePropsSlice,
+        TUBE_POINT_LEN,
+    },
+};
+use glam::Vec3;
+use serde::{Deserialize, Serialize};
+
+/// A tube swept along a path through its points. The tube radius is interpolated between
+/// the radii of the points.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Tube {
+    pub path: TubePath,
+    /// Path vertices or bezier control points in primitive space
+    pub points: Vec<TubePoint>,
+}
+
+impl Tube {
+    pub const fn new(path: TubePath, points: Vec<TubePoint>) -> Self {
+        Self { path, points }
+    }
+
+    /// Note: has no points. See [`Tube::default_points`].
+    pub const DEFAULT: Self = Self {
+        path: TubePath::QuadraticBezier,
+        points: Vec::new(),
+    };
+
+    /// Gentle arc that tubes start with when selected in the gui
+    pub fn default_points() -> Vec<TubePoint> {
+        vec![
+            TubePoint::new(Vec3::new(-DEFAULT_RADIUS, 0., 0.), DEFAULT_MINOR_RADIUS),
+            TubePoint::new(Vec3::new(0., DEFAULT_RADIUS, 0.), DEFAULT_MINOR_RADIUS),
+            TubePoint::new(Vec3::new(DEFAULT_RADIUS, 0., 0.), DEFAULT_MINOR_RADIUS),
+        ]
+    }
+
+    /// Points as they are sent to the shaders. A quadratic bezier path needs an odd number of
+    /// points (end, control, end, control, ... end) so if there's an even number, a control point
+    /// is inserted halfway along the last segment to make it straight.
+    pub fn encoded_points(&self) -> Vec<TubePoint> {
+        let mut points = self.points.clone();
+        if self.path == TubePath::QuadraticBezier && points.len() >= 2 && points.len() % 2 == 0 {
+            let last = points[points.len() - 1];
+            let second_last = points[points.len() - 2];
+            let midpoint = TubePoint::new(
+                (last.position + second_last.position) / 2.,
+                (last.radius + second_last.radius) / 2.,
+            );
+            points.insert(points.len() - 1, midpoint);
+        }
+        points
+    }
+
+    pub fn max_radius(&self) -> f32 {
+        self.points
+            .iter()
+            .fold(0_f32, |max_radius, point| max_radius.max(point.radius))
+    }
+}
+
+impl Default for Tube {
+    fn default() -> Self {
+        Self::new(TubePath::QuadraticBezier, Self::default_points())
+    }
+}
+
+impl EncodablePrimitive for Tube {
+    fn type_name(&self) -> &'static str {
+        primitive_names::TUBE
+    }
+
+    fn type_code(&self) -> PrimitiveOpBufferUnit {
+        primitive_codes::TUBE
+    }
+
+    fn encoded_props(&self) -> PrimitivePropsSlice {
+        [0, 0, 0, 0, 0, 0]
+    }
+
+    /// Path code, point count then position (vec3) and radius (f32) of each point.
+    fn encoded_side_data(&self) -> Vec<PrimitiveOpBufferUnit> {
+        let points = self.encoded_points();
+        let mut encoded = Vec::with_capacity(2 + points.len() * TUBE_POINT_LEN);
+        encoded.push(self.path.code());
+        encoded.push(points.len() as PrimitiveOpBufferUnit);
+        for point in points {
+            encoded.push(point.position.x.to_bits());
+            encoded.push(point.position.y.to_bits());
+            encoded.push(point.position.z.to_bits());
+            encoded.push(point.radius.to_bits());
+        }
+        encoded
+    }
+
+    fn aabb(&self, primitive_transform: PrimitiveTransform) -> Aabb {
+        let transform = primitive_transform.linear_transform();
+        // bezier curves are contained by the convex hull of their control points and the
+        // interpolated radius never exceeds the largest point radius
+        let point_dimensions = sphere_half_extents(self.max_radius(), transform) * 2.;
+
+        let mut point_aabbs = self.points.iter().map(|point| {
+            let center = primitive_transform.center + transform * point.position;
+            Aabb::new(center, point_dimensions)
+        });
+        let Some(mut aabb) = point_aabbs.next() else {
+            return Aabb::new(primitive_transform.center, Vec3::ZERO);
+        };
+        for point_aabb in point_aabbs {
+            aabb.union(point_aabb);
+        }
+        aabb
+    }
+}
+
+// ~~ Tube Point ~~
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TubePoint {
+    pub position: Vec3,
+    pub radius: f32,
+}
+
+impl TubePoint {
+    pub const fn new(position: Vec3, radius: f32) -> Self {
+        Self { position, radius }
+    }
+}
+
+// ~~ Tube Path ~~
+
+/// How the tube points are connected
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TubePath {
+    /// Straight segments between consecutive points
+    Polyline,
+    /// Quadratic bezier segments. Every second point is a control point that the path doesn't
+    /// pass through.
+    QuadraticBezier,
+}
+
+impl TubePath {
+    pub const VARIANTS: &'static [TubePath] = &[Self::Polyline, Self::QuadraticBezier];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Polyline => "Polyline",
+            Self::QuadraticBezier => "Quadratic Bezier",
+        }
+    }
+
+    pub fn code(&self) -> PrimitiveOpBufferUnit {
+        match self {
+            Self::Polyline => tube_path_codes::POLYLINE,
+            Self::QuadraticBezier => tube_path_codes::QUADRATIC_BEZIER,
+        }
+    }
+}
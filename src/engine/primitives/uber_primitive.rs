@@ -1,7 +1,9 @@
 use super::{primitive::EncodablePrimitive, primitive_transform::PrimitiveTransform};
 use crate::{
     engine::{aabb::Aabb, config_engine::primitive_names},
-    renderer::shader_interfaces::primitive_op_buffer::PrimitivePropsSlice,
+    renderer::shader_interfaces::primitive_op_buffer::{
+        primitive_codes, PrimitiveOpBufferUnit, PrimitivePropsSlice,
+    },
 };
 use glam::{Vec2, Vec3, Vec4};
 use serde::{Deserialize, Serialize};
@@ -25,6 +27,26 @@ impl UberPrimitive {
         dimensions: Vec4::ZERO,
         corner_radius: Vec2::ZERO,
     };
+
+    /// Furthest distance the surface reaches along each local axis.
+    ///
+    /// Working through `sdf_uber_primitive` in `scene_geometry.frag`: the surface never extends
+    /// past the box `dimensions.xyz` in z. In x and y it only reaches past the box by the
+    /// thickness when the thickness isn't cut off by a negative x corner radius.
+    pub fn half_extents(&self) -> Vec3 {
+        let thickness = self.dimensions.w;
+        let thickness_extent = if thickness >= -self.corner_radius.x {
+            thickness.max(0.)
+        } else {
+            0.
+        };
+        let half_extents = Vec3::new(
+            self.dimensions.x + thickness_extent,
+            self.dimensions.y + thickness_extent,
+            self.dimensions.z,
+        );
+        half_extents.max(Vec3::ZERO)
+    }
 }
 
 impl Default for UberPrimitive {
@@ -38,6 +60,10 @@ impl EncodablePrimitive for UberPrimitive {
         primitive_names::UBER_PRIMITIVE
     }
 
+    fn type_code(&self) -> PrimitiveOpBufferUnit {
+        primitive_codes::UBER_PRIMITIVE
+    }
+
     fn encoded_props(&self) -> PrimitivePropsSlice {
         [
             self.dimensions.x.to_bits(),
@@ -51,8 +77,10 @@ impl EncodablePrimitive for UberPrimitive {
 
     fn aabb(&self, primitive_transform: PrimitiveTransform) -> Aabb {
         // todo calculate only when props/transform changed?
-        //todo "dimensions need to be adjusted for rotation!
-        let max_dimensions = Vec3::new(5., 5., 5.);
-        Aabb::new(primitive_transform.center, max_dimensions)
+        Aabb::new_transformed_box(
+            primitive_transform.center,
+            self.half_extents() * 2.,
+            primitive_transform.linear_transform(),
+        )
     }
 }
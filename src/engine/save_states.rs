@@ -1,13 +1,18 @@
 use super::{
     config_engine::{LOCAL_STORAGE_DIR, SAVE_STATE_FILENAME_CAMERA, SAVE_STATE_FILENAME_OBJECTS},
-    object::{object::Object, object_collection::ObjectCollection},
+    object::{
+        layer::{Layer, LayerId},
+        material::{Material, MaterialId},
+        object::{Object, ObjectId},
+        object_collection::ObjectCollection,
+    },
 };
 use crate::{
-    config::{PRECURSOR_BYTES, PRECURSOR_BYTE_COUNT},
+    config::{MAGIC_BYTE, PRECURSOR_BYTES, PRECURSOR_BYTE_COUNT},
     helper::more_errors::IoError,
     user_interface::camera::Camera,
 };
-use serde::{de::DeserializeOwned, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::{fs, path::PathBuf};
 
 // ~~ Public ~~
@@ -20,13 +25,41 @@ pub fn load_state_camera() -> Result<Camera, IoError> {
     load_state::<Camera>(SAVE_STATE_FILENAME_CAMERA)
 }
 
+/// Contents of the objects save file. Ids are saved too so that parent, layer and material links
+/// can be restored, see [`ObjectCollection::push_scene`].
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SavedScene {
+    pub layers: Vec<(LayerId, Layer)>,
+    pub materials: Vec<(MaterialId, Material)>,
+    pub objects: Vec<(ObjectId, Object)>,
+}
+
 pub fn save_all_objects(object_collection: &ObjectCollection) -> Result<(), IoError> {
-    let object_list: Vec<Object> = object_collection.objects().values().cloned().collect();
-    save_state(&object_list, SAVE_STATE_FILENAME_OBJECTS)
+    let layers = object_collection
+        .layers()
+        .iter()
+        .map(|(&layer_id, layer)| (layer_id, layer.clone()))
+        .collect();
+    let materials = object_collection
+        .materials()
+        .iter()
+        .map(|(&material_id, material)| (material_id, material.clone()))
+        .collect();
+    let objects = object_collection
+        .objects()
+        .iter()
+        .map(|(&object_id, object)| (object_id, object.clone()))
+        .collect();
+    let saved_scene = SavedScene {
+        layers,
+        materials,
+        objects,
+    };
+    save_state(&saved_scene, SAVE_STATE_FILENAME_OBJECTS)
 }
 
-pub fn load_objects() -> Result<Vec<Object>, IoError> {
-    load_state::<Vec<Object>>(SAVE_STATE_FILENAME_OBJECTS)
+pub fn load_objects() -> Result<SavedScene, IoError> {
+    load_state::<SavedScene>(SAVE_STATE_FILENAME_OBJECTS)
 }
 
 // ~~ Private ~~
@@ -70,8 +103,23 @@ fn load_state_bytes(file_name: &str) -> Result<Vec<u8>, IoError> {
         }
     };
 
-    // ignore engine info for now
-    let _read_precursor_bytes: Vec<u8> = read_bytes.drain(0..PRECURSOR_BYTE_COUNT).collect();
+    let file_path_string = || file_path.to_str().unwrap_or(file_name).to_string();
+    if read_bytes.len() < PRECURSOR_BYTE_COUNT || read_bytes[0] != MAGIC_BYTE {
+        return Err(IoError::NotAnEngineFile(file_path_string()));
+    }
+    let read_precursor_bytes: Vec<u8> = read_bytes.drain(0..PRECURSOR_BYTE_COUNT).collect();
+    // the save format only changes with the major or minor version
+    let saved_version = [
+        read_precursor_bytes[1],
+        read_precursor_bytes[2],
+        read_precursor_bytes[3],
+    ];
+    if saved_version[..2] != PRECURSOR_BYTES[1..3] {
+        return Err(IoError::IncompatibleVersion(
+            file_path_string(),
+            saved_version,
+        ));
+    }
     return Ok(read_bytes);
 }
 
@@ -107,4 +155,30 @@ mod tests {
         let loaded_camera = load_state(TEST_FILE_NAME).unwrap();
         assert_eq!(saved_camera, loaded_camera);
     }
+
+    #[test]
+    fn load_rejects_other_versions() {
+        let file_name = "_testing_version.gsave";
+        let file_path = validated_file_path(file_name).unwrap();
+        let mut saved_bytes = PRECURSOR_BYTES.to_vec();
+        saved_bytes[2] = saved_bytes[2].wrapping_sub(1);
+        saved_bytes.append(&mut bincode::serialize(&Camera::default()).unwrap());
+        fs::write(&file_path, &saved_bytes).unwrap();
+        assert!(matches!(
+            load_state::<Camera>(file_name),
+            Err(IoError::IncompatibleVersion(_, saved_version)) if saved_version[1] == saved_bytes[2]
+        ));
+
+        // patch versions can be loaded
+        saved_bytes[2] = PRECURSOR_BYTES[2];
+        saved_bytes[3] = saved_bytes[3].wrapping_add(1);
+        fs::write(&file_path, &saved_bytes).unwrap();
+        assert!(load_state::<Camera>(file_name).is_ok());
+
+        fs::write(&file_path, b"not").unwrap();
+        assert!(matches!(
+            load_state::<Camera>(file_name),
+            Err(IoError::NotAnEngineFile(_))
+        ));
+    }
 }
@@ -0,0 +1,1816 @@
+//! CPU-side evaluation of an object's signed distance field. Mirrors `map()` and the functions it
+//! calls in `scene_geometry.frag` by decoding the same primitive op packets that get uploaded to
+//! the gpu, so the results here should match what gets rendered.
+
+use super::object::{
+    object::{Object, ObjectId},
+    object_transform::ObjectTransform,
+    surface_modifier::fbm_amplitude_sum,
+};
+use crate::renderer::shader_interfaces::primitive_op_buffer::{
+    domain_modifier_codes, encode_primitive_ops, op_codes, primitive_codes, profile_codes,
+    surface_modifier_codes, tube_path_codes, PrimitiveOpBufferUnit, DOMAIN_MODIFIER_LEN,
+    FBM_OCTAVES, MAX_GROUP_DEPTH, PACKET_ALBEDO_OFFSET, PACKET_BLEND_OFFSET,
+    PACKET_MODIFIERS_OFFSET, PACKET_OP_CODE_OFFSET, PACKET_OP_PARAM_OFFSET,
+    PACKET_PRIMITIVE_TYPE_OFFSET, PACKET_PROPS_OFFSET, PACKET_SIDE_DATA_OFFSET,
+    PACKET_SPECULAR_OFFSET, PACKET_SURFACE_MODIFIERS_OFFSET, PACKET_TRANSFORM_OFFSET,
+    PRIMITIVE_ID_BACKGROUND, PRIMITIVE_ID_BLEND, PRIMITIVE_PACKET_LEN, SURFACE_MODIFIER_LEN,
+    TUBE_POINT_LEN,
+};
+use glam::{Mat3, Vec2, Vec3, Vec4};
+
+/// Offset used for calculating normals. Matches `NORMAL_EPSILON` in `scene_geometry.frag`.
+pub const NORMAL_EPSILON: f32 = 0.001;
+
+/// Distance returned when there is no geometry. The shader uses the camera far plane for this.
+pub const EMPTY_DISTANCE: f32 = f32::MAX;
+
+/// Maximum number of ray marching steps before confirming a miss. Matches `MAX_STEPS` in
+/// `scene_geometry.frag`.
+pub const MAX_STEPS: usize = 100;
+
+/// Distance required to confirm a hit. Matches `MIN_MARCH_STEP` in `scene_geometry.frag`.
+pub const MIN_MARCH_STEP: f32 = 0.001;
+
+/// Represents a signed distance field result
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SdfResult {
+    pub d: f32,
+    /// Index of the closest primitive op, [`PRIMITIVE_ID_BLEND`] in blended areas or
+    /// [`PRIMITIVE_ID_BACKGROUND`] when there is no geometry.
+    pub op_index: PrimitiveOpBufferUnit,
+    pub albedo: Vec3,
+    pub specular: f32,
+}
+
+impl SdfResult {
+    /// Index of the closest primitive op, or `None` in blended areas and empty space.
+    pub fn primitive_op_index(&self) -> Option<usize> {
+        match self.op_index {
+            PRIMITIVE_ID_BLEND | PRIMITIVE_ID_BACKGROUND => None,
+            op_index => Some(op_index as usize),
+        }
+    }
+
+    pub const EMPTY: Self = Self {
+        d: EMPTY_DISTANCE,
+        op_index: PRIMITIVE_ID_BACKGROUND,
+        albedo: Vec3::ZERO,
+        specular: 0.,
+    };
+}
+
+/// Snapshot of an [`Object`]'s encoded primitive ops that can be queried for distances.
+/// Needs to be recreated when the object changes.
+#[derive(Clone, Debug)]
+pub struct ObjectSdf {
+    /// Equivalent to the `primitive_ops` array of the object buffer in the shader
+    primitive_ops: Vec<PrimitiveOpBufferUnit>,
+    /// Number of packets, including group begin/end markers
+    op_count: usize,
+}
+
+impl ObjectSdf {
+    pub fn new(object: &Object) -> Self {
+        Self::with_transform(object, &object.world_transform())
+    }
+
+    /// Uses the primitive ops of `object` placed with `transform` instead of the object's own
+    /// transform. Useful for instances, which use the primitive ops of their definition.
+    pub fn with_transform(object: &Object, transform: &ObjectTransform) -> Self {
+        let steps = object.evaluation_steps();
+        Self {
+            primitive_ops: encode_primitive_ops(&steps, transform),
+            op_count: steps.len(),
+        }
+    }
+
+    /// Calculates the distance to the closest primitive from world space `pos`. Equivalent to
+    /// `map()` in `scene_geometry.frag`.
+    pub fn map(&self, pos: Vec3) -> SdfResult {
+        // results of the open groups with the object result at the bottom
+        let mut group_stack = [SdfResult::EMPTY; MAX_GROUP_DEPTH + 1];
+        let mut depth = 0;
+        // index into `Object::primitive_ops` which skips the group packets
+        let mut primitive_op_index = 0;
+
+        for packet_index in 0..self.op_count {
+            let packet = &self.primitive_ops[packet_index * PRIMITIVE_PACKET_LEN..];
+
+            let primitive_res = match packet[PACKET_PRIMITIVE_TYPE_OFFSET] {
+                primitive_codes::GROUP_BEGIN => {
+                    depth = (depth + 1).min(MAX_GROUP_DEPTH);
+                    group_stack[depth] = SdfResult::EMPTY;
+                    continue;
+                }
+                primitive_codes::GROUP_END => {
+                    let group_res = group_stack[depth];
+                    depth = depth.saturating_sub(1);
+                    group_res
+                }
+                _ => {
+                    let primitive_res = process_primitive(
+                        packet_index,
+                        primitive_op_index,
+                        &self.primitive_ops,
+                        pos,
+                    );
+                    primitive_op_index += 1;
+                    primitive_res
+                }
+            };
+
+            let op = packet[PACKET_OP_CODE_OFFSET];
+            let blend = f32::from_bits(packet[PACKET_BLEND_OFFSET]);
+            let op_param = f32::from_bits(packet[PACKET_OP_PARAM_OFFSET]);
+
+            group_stack[depth] = process_op(op, blend, op_param, group_stack[depth], primitive_res);
+        }
+
+        group_stack[0]
+    }
+
+    #[inline]
+    pub fn distance(&self, pos: Vec3) -> f32 {
+        self.map(pos).d
+    }
+
+    /// Surface normal using the same tetrahedron technique as `calcNormal()` in
+    /// `scene_geometry.frag`. https://iquilezles.org/articles/normalsSDF
+    pub fn normal(&self, pos: Vec3) -> Vec3 {
+        let xyy = Vec3::new(NORMAL_EPSILON, -NORMAL_EPSILON, -NORMAL_EPSILON);
+        let yyx = Vec3::new(-NORMAL_EPSILON, -NORMAL_EPSILON, NORMAL_EPSILON);
+        let yxy = Vec3::new(-NORMAL_EPSILON, NORMAL_EPSILON, -NORMAL_EPSILON);
+        let xxx = Vec3::splat(NORMAL_EPSILON);
+        (xyy * self.distance(pos + xyy)
+            + yyx * self.distance(pos + yyx)
+            + yxy * self.distance(pos + yxy)
+            + xxx * self.distance(pos + xxx))
+        .normalize_or_zero()
+    }
+
+    /// Sphere traces along the normalized `direction` between `min_dist` and `max_dist`.
+    /// Returns the distance along the ray and the result at the hit. Equivalent to `ray_march()`
+    /// in `scene_geometry.frag`.
+    pub fn ray_march(
+        &self,
+        origin: Vec3,
+        direction: Vec3,
+        min_dist: f32,
+        max_dist: f32,
+    ) -> Option<(f32, SdfResult)> {
+        let mut dist = min_dist;
+        for _ in 0..MAX_STEPS {
+            if dist >= max_dist {
+                break;
+            }
+            let closest_primitive = self.map(origin + direction * dist);
+            if closest_primitive.d < MIN_MARCH_STEP {
+                return Some((dist, closest_primitive));
+            }
+            dist += closest_primitive.d;
+        }
+        None
+    }
+}
+
+/// Closest surface along a ray, see
+/// [`ObjectCollection::ray_cast`](super::object::object_collection::ObjectCollection::ray_cast).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RayHit {
+    pub object_id: ObjectId,
+    /// Index into the primitive ops of the object (or its definition for instances). `None` in
+    /// areas where primitive ops are blended together.
+    pub primitive_op_index: Option<usize>,
+    /// Distance along the ray from its origin
+    pub distance: f32,
+    pub position: Vec3,
+    pub normal: Vec3,
+}
+
+// ~~ Primitive-Op Processing ~~
+
+/// `packet_index` is the position of the packet in the buffer and `op_index` is the index of the
+/// primitive op in the object which gets returned in the result.
+fn process_primitive(
+    packet_index: usize,
+    op_index: usize,
+    primitive_ops: &[PrimitiveOpBufferUnit],
+    pos: Vec3,
+) -> SdfResult {
+    let packet = &primitive_ops[packet_index * PRIMITIVE_PACKET_LEN..];
+    let f = |i: usize| f32::from_bits(packet[i]);
+    let vec3_at = |i: usize| Vec3::new(f(i), f(i + 1), f(i + 2));
+
+    let t = PACKET_TRANSFORM_OFFSET;
+    let center = vec3_at(t);
+    let rotation = Mat3::from_cols(vec3_at(t + 3), vec3_at(t + 6), vec3_at(t + 9));
+    let distance_scale = f(t + 12);
+    let p = PACKET_PROPS_OFFSET;
+    let s = Vec4::new(f(p), f(p + 1), f(p + 2), f(p + 3));
+    let r = Vec2::new(f(p + 4), f(p + 5));
+    let albedo = vec3_at(PACKET_ALBEDO_OFFSET);
+    let specular = f(PACKET_SPECULAR_OFFSET);
+    let primitive_type = packet[PACKET_PRIMITIVE_TYPE_OFFSET];
+    let side_data = &primitive_ops[packet[PACKET_SIDE_DATA_OFFSET] as usize..];
+    let modifiers_index = packet[PACKET_MODIFIERS_OFFSET] as usize;
+    let surface_modifiers_index = packet[PACKET_SURFACE_MODIFIERS_OFFSET] as usize;
+
+    // glsl `pos * rotation` multiplies by the transpose
+    let pos = rotation.transpose() * (pos - center);
+    let (pos, modifier_distance_scale) = if modifiers_index != 0 {
+        apply_domain_modifiers(pos, &primitive_ops[modifiers_index..])
+    } else {
+        (pos, 1.)
+    };
+
+    let mut d = match primitive_type {
+        primitive_codes::SPHERE => sdf_sphere(pos, s.x),
+        primitive_codes::CUBE => sdf_box(pos, s.truncate()),
+        primitive_codes::TORUS => sdf_torus(pos, s.x, s.y),
+        primitive_codes::CAPPED_TORUS => sdf_capped_torus(pos, Vec2::new(s.z, s.w), s.x, s.y),
+        primitive_codes::BOX_FRAME => sdf_box_frame(pos, s.truncate(), s.w),
+        primitive_codes::CYLINDER => sdf_cylinder(pos, s.x, s.y),
+        primitive_codes::CONE => sdf_cone(pos, s.x, s.y, s.z),
+        primitive_codes::CAPSULE => sdf_capsule(pos, s.x, s.y),
+        primitive_codes::EXTRUSION => sdf_extrusion(pos, s.x, side_data),
+        primitive_codes::REVOLUTION => sdf_revolution(pos, s.x, Vec2::new(s.y, s.z), side_data),
+        primitive_codes::TUBE => sdf_tube(pos, side_data),
+        _ => sdf_uber_primitive(pos, s, r),
+    };
+    let mut surface_distance_scale = 1.;
+    if surface_modifiers_index != 0 {
+        (d, surface_distance_scale) =
+            apply_surface_modifiers(d, pos, &primitive_ops[surface_modifiers_index..]);
+    }
+    let d = d * surface_distance_scale * modifier_distance_scale * distance_scale;
+
+    SdfResult {
+        d,
+        op_index: op_index as PrimitiveOpBufferUnit,
+        albedo,
+        specular,
+    }
+}
+
+/// Warps `pos` by the domain modifiers at the start of `modifiers` (count followed by the
+/// modifiers). Also returns a factor to scale distances by so that stretched space doesn't
+/// cause overshooting.
+fn apply_domain_modifiers(mut pos: Vec3, modifiers: &[PrimitiveOpBufferUnit]) -> (Vec3, f32) {
+    let modifier_count = modifiers[0] as usize;
+    let mut distance_scale = 1.;
+
+    for i in 0..modifier_count {
+        let modifier = &modifiers[1 + i * DOMAIN_MODIFIER_LEN..];
+        let f = |k: usize| f32::from_bits(modifier[1 + k]);
+        let a = Vec3::new(f(0), f(1), f(2));
+        let b = Vec3::new(f(3), f(4), f(5));
+
+        match modifier[0] {
+            domain_modifier_codes::REPEAT => pos = op_repeat(pos, a, b),
+            domain_modifier_codes::TWIST => {
+                distance_scale *= rotation_distance_scale(pos, a.x);
+                pos = rotate_xy(pos, a.x * pos.z);
+            }
+            domain_modifier_codes::BEND => {
+                distance_scale *= rotation_distance_scale(pos, a.x);
+                pos = rotate_xy(pos, a.x * pos.x);
+            }
+            domain_modifier_codes::ELONGATE => pos -= pos.clamp(-a, a),
+            domain_modifier_codes::SYMMETRY => {
+                pos = Vec3::select(a.cmpgt(Vec3::splat(0.5)), pos.abs(), pos)
+            }
+            _ => (),
+        }
+    }
+
+    (pos, distance_scale)
+}
+
+/// Repeats space every `spacing` units, `limit` times in each direction. Axes with a spacing of 0
+/// aren't repeated and a negative limit repeats forever.
+fn op_repeat(pos: Vec3, spacing: Vec3, limit: Vec3) -> Vec3 {
+    let repeat_axis = spacing.cmpgt(Vec3::ZERO);
+    let mut cell = Vec3::select(repeat_axis, (pos / spacing).round(), Vec3::ZERO);
+    let limited_axis = limit.cmpge(Vec3::ZERO);
+    cell = Vec3::select(
+        limited_axis,
+        cell.clamp(-limit.max(Vec3::ZERO), limit.max(Vec3::ZERO)),
+        cell,
+    );
+    pos - spacing * cell
+}
+
+fn rotate_xy(pos: Vec3, angle: f32) -> Vec3 {
+    let (s, c) = angle.sin_cos();
+    Vec3::new(c * pos.x - s * pos.y, s * pos.x + c * pos.y, pos.z)
+}
+
+/// Twisting and bending rotate around the z axis at `rate` radians per unit, stretching space
+/// more further away from the axis.
+fn rotation_distance_scale(pos: Vec3, rate: f32) -> f32 {
+    let stretch = rate * pos.truncate().length();
+    1. / (1. + stretch * stretch).sqrt()
+}
+
+/// Changes the primitive distance `d` at `pos` by the surface modifiers at the start of
+/// `modifiers` (count followed by the modifiers). Also returns a factor to scale the distance by
+/// so that the ray marcher doesn't overshoot.
+fn apply_surface_modifiers(
+    mut d: f32,
+    pos: Vec3,
+    modifiers: &[PrimitiveOpBufferUnit],
+) -> (f32, f32) {
+    let modifier_count = modifiers[0] as usize;
+    let mut distance_scale = 1.;
+
+    for i in 0..modifier_count {
+        let modifier = &modifiers[1 + i * SURFACE_MODIFIER_LEN..];
+        let a = f32::from_bits(modifier[1]);
+
+        d = match modifier[0] {
+            surface_modifier_codes::ROUND => d - a,
+            surface_modifier_codes::SHELL => (d + a / 2.).abs() - a / 2.,
+            surface_modifier_codes::ONION => {
+                let mut thickness = a;
+                for _ in 0..modifier[2] {
+                    d = d.abs() - thickness;
+                    thickness /= 2.;
+                }
+                d
+            }
+            surface_modifier_codes::SURFACE_ONLY => d.abs(),
+            code => {
+                let frequency = f32::from_bits(modifier[2]);
+                let seed = modifier[3];
+                let p = pos * frequency;
+                let pattern = match code {
+                    surface_modifier_codes::DISPLACE_VALUE_NOISE => value_noise(p, seed),
+                    surface_modifier_codes::DISPLACE_FBM => fbm_noise(p, seed),
+                    surface_modifier_codes::DISPLACE_VORONOI => voronoi_noise(p, seed),
+                    surface_modifier_codes::DISPLACE_SINE => sine_pattern(p, seed),
+                    _ => 0.,
+                };
+                d + a * pattern
+            }
+        };
+        distance_scale *= f32::from_bits(modifier[4]);
+    }
+
+    (d, distance_scale)
+}
+
+fn process_op(
+    op: PrimitiveOpBufferUnit,
+    blend: f32,
+    op_param: f32,
+    lhs: SdfResult,
+    rhs: SdfResult,
+) -> SdfResult {
+    match op {
+        op_codes::UNION => op_union(lhs, rhs, blend),
+        op_codes::INTERSECTION => op_intersection(lhs, rhs, blend),
+        op_codes::SUBTRACTION => op_subtraction(lhs, rhs, blend),
+        op_codes::CHAMFER_UNION
+        | op_codes::STAIRS_UNION
+        | op_codes::COLUMNS_UNION
+        | op_codes::CHAMFER_SUBTRACTION
+        | op_codes::STAIRS_SUBTRACTION
+        | op_codes::COLUMNS_SUBTRACTION => op_hard_blend(op, lhs, rhs, blend, op_param),
+        op_codes::GROOVE => op_groove(lhs, rhs, blend, op_param),
+        op_codes::TONGUE => op_tongue(lhs, rhs, blend, op_param),
+        op_codes::MORPH => op_morph(lhs, rhs, op_param),
+        op_codes::XOR => op_xor(lhs, rhs),
+        _ => lhs, // else do nothing e.g. NOP
+    }
+}
+
+// ~~ Combination Ops ~~
+
+/// Results in the union (min) of 2 primitives
+fn op_union(p1: SdfResult, p2: SdfResult, blend: f32) -> SdfResult {
+    let d_delta = p2.d - p1.d;
+    if d_delta.abs() >= blend {
+        return if p1.d < p2.d { p1 } else { p2 };
+    }
+    let h = 0.5 + 0.5 * d_delta / blend;
+    let d = mix(p2.d, p1.d, h) - blend * h * (1. - h);
+
+    SdfResult {
+        d,
+        op_index: PRIMITIVE_ID_BLEND,
+        albedo: p2.albedo.lerp(p1.albedo, h),
+        specular: mix(p2.specular, p1.specular, h),
+    }
+}
+
+/// Results in the intersection (max) of 2 primitives
+fn op_intersection(p1: SdfResult, p2: SdfResult, blend: f32) -> SdfResult {
+    let d_delta = p2.d - p1.d;
+    if d_delta.abs() >= blend {
+        return if p1.d > p2.d { p1 } else { p2 };
+    }
+    let h = 0.5 - 0.5 * d_delta / blend;
+    let d = mix(p2.d, p1.d, h) + blend * h * (1. - h);
+
+    SdfResult {
+        d,
+        op_index: PRIMITIVE_ID_BLEND,
+        albedo: p2.albedo.lerp(p1.albedo, h),
+        specular: mix(p2.specular, p1.specular, h),
+    }
+}
+
+/// Subtracts the volume of primitive 2 (max) from primitive 1 (max inverted)
+fn op_subtraction(p1: SdfResult, p2: SdfResult, blend: f32) -> SdfResult {
+    let p2_neg = SdfResult { d: -p2.d, ..p2 };
+    op_intersection(p1, p2_neg, blend)
+}
+
+/// Linear interpolation from primitive 1 (`factor` = 0) to primitive 2 (`factor` = 1)
+fn op_morph(p1: SdfResult, p2: SdfResult, factor: f32) -> SdfResult {
+    if factor <= 0. {
+        return p1;
+    }
+    if factor >= 1. {
+        return p2;
+    }
+    SdfResult {
+        d: mix(p1.d, p2.d, factor),
+        op_index: PRIMITIVE_ID_BLEND,
+        albedo: p1.albedo.lerp(p2.albedo, factor),
+        specular: mix(p1.specular, p2.specular, factor),
+    }
+}
+
+/// Results in the volume inside exactly one of the 2 primitives. Each surface keeps the
+/// attributes of its primitive.
+fn op_xor(p1: SdfResult, p2: SdfResult) -> SdfResult {
+    let (closer, further) = if p1.d < p2.d { (p1, p2) } else { (p2, p1) };
+    // outside of both the closer surface wins, inside the overlap it's the further one
+    if closer.d > -further.d {
+        closer
+    } else {
+        SdfResult {
+            d: -further.d,
+            ..further
+        }
+    }
+}
+
+// ~~ Hard-Edged Combination Ops ~~
+// http://mercury.sexy/hg_sdf/
+
+/// Chamfer, stairs and columns unions/subtractions. The edge where the primitives meet is
+/// replaced by a shape `blend` units across. `steps` is the number of stairs or columns.
+fn op_hard_blend(
+    op: PrimitiveOpBufferUnit,
+    p1: SdfResult,
+    p2: SdfResult,
+    blend: f32,
+    steps: f32,
+) -> SdfResult {
+    let subtraction = matches!(
+        op,
+        op_codes::CHAMFER_SUBTRACTION
+            | op_codes::STAIRS_SUBTRACTION
+            | op_codes::COLUMNS_SUBTRACTION
+    );
+    if blend <= 0. {
+        return if subtraction {
+            op_subtraction(p1, p2, 0.)
+        } else {
+            op_union(p1, p2, 0.)
+        };
+    }
+    let steps = steps.max(1.);
+
+    let (a, b) = (p1.d, p2.d);
+    let d = match op {
+        op_codes::CHAMFER_UNION => chamfer_union(a, b, blend),
+        op_codes::CHAMFER_SUBTRACTION => -chamfer_union(-a, b, blend),
+        op_codes::STAIRS_UNION => stairs_union(a, b, blend, steps),
+        op_codes::STAIRS_SUBTRACTION => -stairs_union(-a, b, blend, steps),
+        op_codes::COLUMNS_UNION => columns_union(a, b, blend, steps),
+        _ => columns_subtraction(a, b, blend, steps),
+    };
+
+    // the closest primitive gets precedence with the attributes blended in between
+    if d == a {
+        return p1;
+    }
+    let p2 = if subtraction {
+        SdfResult { d: -b, ..p2 }
+    } else {
+        p2
+    };
+    if d == p2.d {
+        return p2;
+    }
+    let d_delta = if subtraction { a - p2.d } else { p2.d - a };
+    let h = (0.5 + 0.5 * d_delta / blend).clamp(0., 1.);
+    SdfResult {
+        d,
+        op_index: PRIMITIVE_ID_BLEND,
+        albedo: p2.albedo.lerp(p1.albedo, h),
+        specular: mix(p2.specular, p1.specular, h),
+    }
+}
+
+/// Cuts a groove `depth` deep and `width` wide either side of the surface of `p2` into `p1`.
+/// The groove walls take on the attributes of `p2`.
+fn op_groove(p1: SdfResult, p2: SdfResult, depth: f32, width: f32) -> SdfResult {
+    let walls = width - p2.d.abs();
+    let d = p1.d.max((p1.d + depth).min(walls));
+    if d == walls {
+        SdfResult { d, ..p2 }
+    } else {
+        SdfResult { d, ..p1 }
+    }
+}
+
+/// Raises a tongue `height` high and `width` wide either side of the surface of `p2` out of
+/// `p1`. The sides of the tongue take on the attributes of `p2`.
+fn op_tongue(p1: SdfResult, p2: SdfResult, height: f32, width: f32) -> SdfResult {
+    let sides = p2.d.abs() - width;
+    let d = p1.d.min((p1.d - height).max(sides));
+    if d == sides {
+        SdfResult { d, ..p2 }
+    } else {
+        SdfResult { d, ..p1 }
+    }
+}
+
+fn chamfer_union(a: f32, b: f32, r: f32) -> f32 {
+    a.min(b).min((a - r + b) * std::f32::consts::FRAC_1_SQRT_2)
+}
+
+fn stairs_union(a: f32, b: f32, r: f32, n: f32) -> f32 {
+    let s = r / n;
+    let u = b - r;
+    a.min(b)
+        .min(0.5 * (u + a + ((u - a + s).rem_euclid(2. * s) - s).abs()))
+}
+
+/// Rotates `p` by 45 degrees
+fn rotate_45(p: Vec2) -> Vec2 {
+    (p + Vec2::new(p.y, -p.x)) * std::f32::consts::FRAC_1_SQRT_2
+}
+
+/// Repeats `p` every `size` units centered around 0
+fn repeat_1d(p: f32, size: f32) -> f32 {
+    let half_size = size / 2.;
+    (p + half_size).rem_euclid(size) - half_size
+}
+
+fn columns_union(a: f32, b: f32, r: f32, n: f32) -> f32 {
+    if a >= r || b >= r {
+        return a.min(b);
+    }
+    let sqrt_2 = std::f32::consts::SQRT_2;
+    let column_radius = r * sqrt_2 / ((n - 1.) * 2. + sqrt_2);
+    let mut p = rotate_45(Vec2::new(a, b));
+    p.x -= sqrt_2 / 2. * r;
+    p.x += column_radius * sqrt_2;
+    if n % 2. == 1. {
+        p.y += column_radius;
+    }
+    // place a circle every column along the diagonal between the surfaces
+    p.y = repeat_1d(p.y, column_radius * 2.);
+    (p.length() - column_radius).min(p.x).min(a).min(b)
+}
+
+/// Subtracts `b` from `a`
+fn columns_subtraction(a: f32, b: f32, r: f32, n: f32) -> f32 {
+    let a = -a;
+    if a >= r || b >= r {
+        return -a.min(b);
+    }
+    let sqrt_2 = std::f32::consts::SQRT_2;
+    let column_radius = r * sqrt_2 / ((n - 1.) * 2. + sqrt_2);
+    let mut p = rotate_45(Vec2::new(a, b));
+    p.y += column_radius;
+    p.x -= sqrt_2 / 2. * r;
+    p.x -= column_radius * sqrt_2 / 2.;
+    if n % 2. == 1. {
+        p.y += column_radius;
+    }
+    p.y = repeat_1d(p.y, column_radius * 2.);
+    -(column_radius - p.length()).max(p.x).min(a).min(b)
+}
+
+#[inline]
+fn mix(x: f32, y: f32, a: f32) -> f32 {
+    x + (y - x) * a
+}
+
+// ~~ Signed Distance Fields ~~
+
+/// https://www.shadertoy.com/view/MsVGWG
+fn sdf_uber_primitive(pos: Vec3, s: Vec4, r: Vec2) -> f32 {
+    let d = pos.abs() - s.truncate();
+    let q_1 = (d.truncate() + r.x).max(Vec2::ZERO).length();
+    let q_2 = (-r.x).min(d.x.max(d.y) + s.w);
+    let q = (q_1 + q_2).abs() - s.w;
+    let ret_1 = (Vec2::new(q, d.z) + r.y).max(Vec2::ZERO);
+    let ret_2 = (-r.y).min(q.max(d.z));
+    ret_1.length() + ret_2
+}
+
+fn sdf_sphere(pos: Vec3, radius: f32) -> f32 {
+    pos.length() - radius
+}
+
+fn sdf_box(pos: Vec3, half_dimensions: Vec3) -> f32 {
+    let q = pos.abs() - half_dimensions;
+    q.max(Vec3::ZERO).length() + q.max_element().min(0.)
+}
+
+fn sdf_torus(pos: Vec3, radius_major: f32, radius_minor: f32) -> f32 {
+    let q = Vec2::new(pos.truncate().length() - radius_major, pos.z);
+    q.length() - radius_minor
+}
+
+fn sdf_capped_torus(pos: Vec3, sc: Vec2, radius_major: f32, radius_minor: f32) -> f32 {
+    let pos = Vec3::new(pos.x.abs(), pos.y, pos.z);
+    let k = if sc.y * pos.x > sc.x * pos.y {
+        pos.truncate().dot(sc)
+    } else {
+        pos.truncate().length()
+    };
+    (pos.dot(pos) + radius_major * radius_major - 2. * radius_major * k).sqrt() - radius_minor
+}
+
+fn sdf_box_frame(pos: Vec3, half_dimensions: Vec3, e: f32) -> f32 {
+    let p = pos.abs() - half_dimensions;
+    let q = (p + e).abs() - e;
+    let edge = |v: Vec3| v.max(Vec3::ZERO).length() + v.max_element().min(0.);
+    edge(Vec3::new(p.x, q.y, q.z))
+        .min(edge(Vec3::new(q.x, p.y, q.z)))
+        .min(edge(Vec3::new(q.x, q.y, p.z)))
+}
+
+fn sdf_cylinder(pos: Vec3, radius: f32, half_height: f32) -> f32 {
+    let d = Vec2::new(pos.truncate().length(), pos.z).abs() - Vec2::new(radius, half_height);
+    d.x.max(d.y).min(0.) + d.max(Vec2::ZERO).length()
+}
+
+fn sdf_cone(pos: Vec3, radius_bottom: f32, radius_top: f32, half_height: f32) -> f32 {
+    let q = Vec2::new(pos.truncate().length(), pos.z);
+    let k1 = Vec2::new(radius_top, half_height);
+    let k2 = Vec2::new(radius_top - radius_bottom, 2. * half_height);
+    let ca = Vec2::new(
+        q.x - q.x.min(if q.y < 0. { radius_bottom } else { radius_top }),
+        q.y.abs() - half_height,
+    );
+    let cb = q - k1 + k2 * ((k1 - q).dot(k2) / k2.dot(k2)).clamp(0., 1.);
+    let s = if cb.x < 0. && ca.y < 0. { -1. } else { 1. };
+    s * ca.dot(ca).min(cb.dot(cb)).sqrt()
+}
+
+fn sdf_capsule(pos: Vec3, radius: f32, half_segment: f32) -> f32 {
+    let pos = Vec3::new(
+        pos.x,
+        pos.y,
+        pos.z - pos.z.clamp(-half_segment, half_segment),
+    );
+    pos.length() - radius
+}
+
+fn sdf_extrusion(pos: Vec3, half_depth: f32, profile: &[PrimitiveOpBufferUnit]) -> f32 {
+    let w = Vec2::new(
+        sdf_profile(pos.truncate(), profile),
+        pos.z.abs() - half_depth,
+    );
+    w.x.max(w.y).min(0.) + w.max(Vec2::ZERO).length()
+}
+
+fn sdf_revolution(pos: Vec3, offset: f32, sc: Vec2, profile: &[PrimitiveOpBufferUnit]) -> f32 {
+    let pos = Vec3::new(pos.x.abs(), pos.y, pos.z);
+    if sc.y * pos.x <= sc.x * pos.y {
+        // within the revolved arc
+        let q = Vec2::new(pos.truncate().length() - offset, pos.z);
+        return sdf_profile(q, profile);
+    }
+    // past the end of the arc -> distance to the flat end cap
+    let radial = pos.truncate().dot(sc);
+    let to_cap_plane = (sc.y * pos.x - sc.x * pos.y).abs();
+    let cap_distance = sdf_profile(Vec2::new(radial - offset, pos.z), profile).max(0.);
+    (cap_distance * cap_distance + to_cap_plane * to_cap_plane).sqrt()
+}
+
+/// `side_data` contains the path code, point count then the position and radius of each point.
+fn sdf_tube(pos: Vec3, side_data: &[PrimitiveOpBufferUnit]) -> f32 {
+    let path = side_data[0];
+    let point_count = side_data[1] as usize;
+    let point = |i: usize| {
+        let j = 2 + i * TUBE_POINT_LEN;
+        let f = |k: usize| f32::from_bits(side_data[j + k]);
+        (Vec3::new(f(0), f(1), f(2)), f(3))
+    };
+
+    if point_count == 0 {
+        return EMPTY_DISTANCE;
+    }
+    if point_count == 1 {
+        let (p0, r0) = point(0);
+        return sdf_sphere(pos - p0, r0);
+    }
+
+    let mut d = EMPTY_DISTANCE;
+    if path == tube_path_codes::QUADRATIC_BEZIER {
+        for i in (0..point_count - 2).step_by(2) {
+            let (p0, r0) = point(i);
+            let (p1, r1) = point(i + 1);
+            let (p2, r2) = point(i + 2);
+            d = d.min(sdf_bezier_segment(pos, p0, p1, p2, Vec3::new(r0, r1, r2)));
+        }
+    } else {
+        for i in 0..point_count - 1 {
+            let (a, ra) = point(i);
+            let (b, rb) = point(i + 1);
+            d = d.min(sdf_tapered_segment(pos, a, b, ra, rb));
+        }
+    }
+    d
+}
+
+/// Segment from `a` to `b` with the radius linearly interpolated from `ra` to `rb`
+fn sdf_tapered_segment(pos: Vec3, a: Vec3, b: Vec3, ra: f32, rb: f32) -> f32 {
+    let pa = pos - a;
+    let ba = b - a;
+    let h = (pa.dot(ba) / ba.dot(ba).max(f32::EPSILON)).clamp(0., 1.);
+    (pa - ba * h).length() - mix(ra, rb, h)
+}
+
+/// Quadratic bezier from `a` to `c` with control point `b`. The radii of the three points are
+/// interpolated with the same weights as the positions.
+/// https://iquilezles.org/articles/distfunctions2d
+fn sdf_bezier_segment(pos: Vec3, a: Vec3, b: Vec3, c: Vec3, radii: Vec3) -> f32 {
+    let t = bezier_closest_t(pos, a, b, c);
+    let weights = Vec3::new((1. - t) * (1. - t), 2. * t * (1. - t), t * t);
+    let curve_point = a * weights.x + b * weights.y + c * weights.z;
+    (pos - curve_point).length() - radii.dot(weights)
+}
+
+/// Returns the bezier parameter of the point on the curve closest to `pos`
+fn bezier_closest_t(pos: Vec3, a: Vec3, b: Vec3, c: Vec3) -> f32 {
+    let ab = b - a;
+    let curvature = a - 2. * b + c;
+    if curvature.dot(curvature) < 1e-8 {
+        // control point is halfway between the ends -> straight line
+        let ac = c - a;
+        return ((pos - a).dot(ac) / ac.dot(ac).max(f32::EPSILON)).clamp(0., 1.);
+    }
+    let to_start = a - pos;
+    let offset_at = |t: f32| to_start + (2. * ab + curvature * t) * t;
+
+    let kk = 1. / curvature.dot(curvature);
+    let kx = kk * ab.dot(curvature);
+    let ky = kk * (2. * ab.dot(ab) + to_start.dot(curvature)) / 3.;
+    let kz = kk * to_start.dot(ab);
+    let p = ky - kx * kx;
+    let q = kx * (2. * kx * kx - 3. * ky) + kz;
+    let h = q * q + 4. * p * p * p;
+    if h >= 0. {
+        let h = h.sqrt();
+        let uv = Vec2::new((h - q) / 2., (-h - q) / 2.);
+        (uv.x.cbrt() + uv.y.cbrt() - kx).clamp(0., 1.)
+    } else {
+        let z = (-p).sqrt();
+        let v = (q / (p * z * 2.)).clamp(-1., 1.).acos() / 3.;
+        let m = v.cos();
+        let n = v.sin() * 3_f32.sqrt();
+        let t1 = ((m + m) * z - kx).clamp(0., 1.);
+        let t2 = ((-n - m) * z - kx).clamp(0., 1.);
+        // the third root can't be the closest
+        if offset_at(t1).length_squared() < offset_at(t2).length_squared() {
+            t1
+        } else {
+            t2
+        }
+    }
+}
+
+// ~~ Noise ~~
+// Integer hashing so that the gpu and cpu produce the same patterns.
+
+/// https://nullprogram.com/blog/2018/07/31/
+fn hash_u32(mut x: u32) -> u32 {
+    x ^= x >> 16;
+    x = x.wrapping_mul(0x7feb352d);
+    x ^= x >> 15;
+    x = x.wrapping_mul(0x846ca68b);
+    x ^= x >> 16;
+    x
+}
+
+/// `cell` must be a whole number vector
+fn hash_cell(cell: Vec3, seed: u32) -> u32 {
+    let cell = cell.as_ivec3();
+    let h = hash_u32((cell.z as u32).wrapping_add(hash_u32(seed)));
+    let h = hash_u32((cell.y as u32).wrapping_add(h));
+    hash_u32((cell.x as u32).wrapping_add(h))
+}
+
+/// Maps a hash to [0, 1)
+fn hash_to_unit(h: u32) -> f32 {
+    (h >> 8) as f32 / 16777216.
+}
+
+/// Smoothly interpolated random values in [-1, 1] at whole number positions
+fn value_noise(p: Vec3, seed: u32) -> f32 {
+    let cell = p.floor();
+    let f = p - cell;
+    let w = f * f * (3. - 2. * f);
+    let corner =
+        |x: f32, y: f32, z: f32| hash_to_unit(hash_cell(cell + Vec3::new(x, y, z), seed)) * 2. - 1.;
+
+    let x00 = mix(corner(0., 0., 0.), corner(1., 0., 0.), w.x);
+    let x10 = mix(corner(0., 1., 0.), corner(1., 1., 0.), w.x);
+    let x01 = mix(corner(0., 0., 1.), corner(1., 0., 1.), w.x);
+    let x11 = mix(corner(0., 1., 1.), corner(1., 1., 1.), w.x);
+    mix(mix(x00, x10, w.y), mix(x01, x11, w.y), w.z)
+}
+
+fn fbm_noise(p: Vec3, seed: u32) -> f32 {
+    let mut value = 0.;
+    let mut amplitude = 1.;
+    let mut frequency = 1.;
+    for octave in 0..FBM_OCTAVES {
+        value += amplitude * value_noise(p * frequency, seed.wrapping_add(octave));
+        amplitude *= 0.5;
+        frequency *= 2.;
+    }
+    value / fbm_amplitude_sum()
+}
+
+/// 1 at randomly scattered points (one per unit cell) falling to -1 a unit away from them
+fn voronoi_noise(p: Vec3, seed: u32) -> f32 {
+    let cell = p.floor();
+    let mut nearest = 1_f32;
+    for z in -1..=1 {
+        for y in -1..=1 {
+            for x in -1..=1 {
+                let neighbour = cell + Vec3::new(x as f32, y as f32, z as f32);
+                let h = hash_cell(neighbour, seed);
+                let h2 = hash_u32(h);
+                let jitter = Vec3::new(
+                    hash_to_unit(h),
+                    hash_to_unit(h2),
+                    hash_to_unit(hash_u32(h2)),
+                );
+                nearest = nearest.min((neighbour + jitter - p).length());
+            }
+        }
+    }
+    1. - 2. * nearest
+}
+
+/// Product of one wave per unit along each axis with a random phase per axis
+fn sine_pattern(p: Vec3, seed: u32) -> f32 {
+    let h = hash_u32(seed);
+    let h2 = hash_u32(h);
+    let phase = Vec3::new(
+        hash_to_unit(h),
+        hash_to_unit(h2),
+        hash_to_unit(hash_u32(h2)),
+    ) * std::f32::consts::TAU;
+    let waves = p * std::f32::consts::TAU + phase;
+    waves.x.sin() * waves.y.sin() * waves.z.sin()
+}
+
+// ~~ 2D Profiles ~~
+
+fn sdf_profile(p: Vec2, profile: &[PrimitiveOpBufferUnit]) -> f32 {
+    let f = |i: usize| f32::from_bits(profile[i]);
+    match profile[0] {
+        profile_codes::CIRCLE => p.length() - f(1),
+        profile_codes::ROUNDED_RECT => sdf_rounded_rect(p, Vec2::new(f(1), f(2)), f(3)),
+        profile_codes::POLYGON => sdf_polygon(p, profile[1] as usize, &profile[2..]),
+        _ => EMPTY_DISTANCE,
+    }
+}
+
+fn sdf_rounded_rect(p: Vec2, half_dimensions: Vec2, corner_radius: f32) -> f32 {
+    let q = p.abs() - half_dimensions + corner_radius;
+    q.x.max(q.y).min(0.) + q.max(Vec2::ZERO).length() - corner_radius
+}
+
+fn sdf_polygon(p: Vec2, vertex_count: usize, vertices: &[PrimitiveOpBufferUnit]) -> f32 {
+    if vertex_count == 0 {
+        return EMPTY_DISTANCE;
+    }
+    let vertex = |i: usize| {
+        Vec2::new(
+            f32::from_bits(vertices[i * 2]),
+            f32::from_bits(vertices[i * 2 + 1]),
+        )
+    };
+
+    let v0 = vertex(0);
+    let mut d = (p - v0).dot(p - v0);
+    let mut s = 1.;
+    let mut j = vertex_count - 1;
+    for i in 0..vertex_count {
+        let (vi, vj) = (vertex(i), vertex(j));
+        let e = vj - vi;
+        let w = p - vi;
+        let b = w - e * (w.dot(e) / e.dot(e)).clamp(0., 1.);
+        d = d.min(b.dot(b));
+        let c = [p.y >= vi.y, p.y < vj.y, e.x * w.y > e.y * w.x];
+        if c.iter().all(|x| *x) || c.iter().all(|x| !*x) {
+            s *= -1.;
+        }
+        j = i;
+    }
+    s * d.sqrt()
+}
+
+// ~~ Tests ~~
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use crate::engine::{
+        object::{
+            domain_modifier::DomainModifier,
+            operation::Operation,
+            primitive_op_group::EvaluationStep,
+            surface_modifier::{NoisePattern, SurfaceModifier},
+        },
+        primitives::{
+            cone::Cone,
+            cube::Cube,
+            extrusion::Extrusion,
+            primitive::Primitive,
+            primitive_transform::PrimitiveTransform,
+            profile::Profile,
+            revolution::Revolution,
+            sphere::Sphere,
+            torus::Torus,
+            tube::{Tube, TubePath, TubePoint},
+        },
+    };
+    use crate::helper::angle::Angle;
+    use glam::{BVec3, Quat, UVec3};
+
+    const EPSILON: f32 = 1e-4;
+
+    fn push(object: &mut Object, primitive: Primitive, center: Vec3, op: Operation, blend: f32) {
+        object
+            .push_primitive_op(
+                primitive,
+                PrimitiveTransform::new(center, Quat::IDENTITY),
+                op,
+                blend,
+                Vec3::ONE,
+                0.5,
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn empty_object() {
+        let object = Object::new("test".to_string(), Vec3::ZERO);
+        let res = ObjectSdf::new(&object).map(Vec3::ZERO);
+        assert_eq!(res.op_index, PRIMITIVE_ID_BACKGROUND);
+    }
+
+    #[test]
+    fn sphere_distance() {
+        let mut object = Object::new("test".to_string(), Vec3::new(1., 0., 0.));
+        push(
+            &mut object,
+            Sphere::new(0.5).into(),
+            Vec3::ZERO,
+            Operation::Union,
+            0.,
+        );
+        let sdf = ObjectSdf::new(&object);
+
+        assert!((sdf.distance(Vec3::new(3., 0., 0.)) - 1.5).abs() < EPSILON);
+        assert!((sdf.distance(Vec3::new(1., 0., 0.)) + 0.5).abs() < EPSILON);
+        assert_eq!(sdf.map(Vec3::new(3., 0., 0.)).op_index, 0);
+    }
+
+    #[test]
+    fn rotated_cube_distance() {
+        let mut object = Object::new("test".to_string(), Vec3::ZERO);
+        object
+            .push_primitive_op(
+                Cube::new(Vec3::new(2., 1., 1.)).into(),
+                PrimitiveTransform::new(Vec3::ZERO, Quat::from_rotation_z(90_f32.to_radians())),
+                Operation::Union,
+                0.,
+                Vec3::ONE,
+                0.5,
+            )
+            .unwrap();
+        let sdf = ObjectSdf::new(&object);
+
+        // long side now lies along y
+        assert!((sdf.distance(Vec3::new(0., 2., 0.)) - 1.).abs() < EPSILON);
+        assert!((sdf.distance(Vec3::new(2., 0., 0.)) - 1.5).abs() < EPSILON);
+    }
+
+    #[test]
+    fn torus_distance() {
+        let mut object = Object::new("test".to_string(), Vec3::ZERO);
+        push(
+            &mut object,
+            Torus::new(1., 0.25).into(),
+            Vec3::ZERO,
+            Operation::Union,
+            0.,
+        );
+        let sdf = ObjectSdf::new(&object);
+
+        assert!((sdf.distance(Vec3::ZERO) - 0.75).abs() < EPSILON);
+        assert!((sdf.distance(Vec3::new(0., 1., 1.)) - 0.75).abs() < EPSILON);
+    }
+
+    #[test]
+    fn scaled_sphere_is_conservative() {
+        let mut object = Object::new("test".to_string(), Vec3::ZERO);
+        let mut transform = PrimitiveTransform::new(Vec3::ZERO, Quat::IDENTITY);
+        transform.scale = Vec3::new(2., 1., 1.);
+        object
+            .push_primitive_op(
+                Sphere::new(1.).into(),
+                transform,
+                Operation::Union,
+                0.,
+                Vec3::ONE,
+                0.5,
+            )
+            .unwrap();
+        let sdf = ObjectSdf::new(&object);
+
+        assert!(sdf.distance(Vec3::new(1.9, 0., 0.)) < 0.);
+        let outside = sdf.distance(Vec3::new(2.2, 0., 0.));
+        assert!(outside > 0. && outside <= 0.2);
+        assert!((sdf.distance(Vec3::new(0., 1.2, 0.)) - 0.2).abs() < EPSILON);
+    }
+
+    #[test]
+    fn mirrored_cone() {
+        let mut object = Object::new("test".to_string(), Vec3::ZERO);
+        let mut transform = PrimitiveTransform::new(Vec3::ZERO, Quat::IDENTITY);
+        transform.mirror.z = true;
+        object
+            .push_primitive_op(
+                Cone::new(0.5, 1., None).into(),
+                transform,
+                Operation::Union,
+                0.,
+                Vec3::ONE,
+                0.5,
+            )
+            .unwrap();
+        let sdf = ObjectSdf::new(&object);
+
+        // base is now at +z
+        assert!((sdf.distance(Vec3::new(0.5, 0., 0.6)) - 0.1).abs() < EPSILON);
+    }
+
+    #[test]
+    fn extruded_polygon() {
+        let mut object = Object::new("test".to_string(), Vec3::ZERO);
+        let square = Profile::Polygon {
+            vertices: vec![
+                Vec2::new(-0.5, -0.5),
+                Vec2::new(0.5, -0.5),
+                Vec2::new(0.5, 0.5),
+                Vec2::new(-0.5, 0.5),
+            ],
+        };
+        push(
+            &mut object,
+            Sphere::new(0.1).into(),
+            Vec3::new(5., 0., 0.),
+            Operation::Union,
+            0.,
+        );
+        push(
+            &mut object,
+            Extrusion::new(square, 2.).into(),
+            Vec3::ZERO,
+            Operation::Union,
+            0.,
+        );
+        let sdf = ObjectSdf::new(&object);
+
+        assert!((sdf.distance(Vec3::new(0.7, 0., 0.)) - 0.2).abs() < EPSILON);
+        assert!((sdf.distance(Vec3::new(0., 0., 1.5)) - 0.5).abs() < EPSILON);
+        assert!((sdf.distance(Vec3::ZERO) + 0.5).abs() < EPSILON);
+    }
+
+    #[test]
+    fn revolved_circle_is_torus() {
+        let mut object = Object::new("test".to_string(), Vec3::ZERO);
+        let revolution =
+            Revolution::new(Profile::Circle { radius: 0.25 }, 1., Angle::Degrees(360.));
+        push(
+            &mut object,
+            revolution.into(),
+            Vec3::ZERO,
+            Operation::Union,
+            0.,
+        );
+        let sdf = ObjectSdf::new(&object);
+
+        assert!((sdf.distance(Vec3::ZERO) - 0.75).abs() < EPSILON);
+        assert!((sdf.distance(Vec3::new(0., -1., 1.)) - 0.75).abs() < EPSILON);
+    }
+
+    #[test]
+    fn half_revolution_end_cap() {
+        let mut object = Object::new("test".to_string(), Vec3::ZERO);
+        let revolution =
+            Revolution::new(Profile::Circle { radius: 0.25 }, 1., Angle::Degrees(180.));
+        push(
+            &mut object,
+            revolution.into(),
+            Vec3::ZERO,
+            Operation::Union,
+            0.,
+        );
+        let sdf = ObjectSdf::new(&object);
+
+        // the arc covers +y, the end caps lie on the x axis
+        assert!((sdf.distance(Vec3::new(0., 1., 0.)) + 0.25).abs() < EPSILON);
+        assert!((sdf.distance(Vec3::new(1., -0.5, 0.)) - 0.5).abs() < EPSILON);
+    }
+
+    #[test]
+    fn tapered_polyline_tube() {
+        let mut object = Object::new("test".to_string(), Vec3::ZERO);
+        let points = vec![
+            TubePoint::new(Vec3::new(-1., 0., 0.), 0.2),
+            TubePoint::new(Vec3::new(1., 0., 0.), 0.4),
+            TubePoint::new(Vec3::new(1., 1., 0.), 0.4),
+        ];
+        let tube = Tube::new(TubePath::Polyline, points);
+        push(&mut object, tube.into(), Vec3::ZERO, Operation::Union, 0.);
+        let sdf = ObjectSdf::new(&object);
+
+        assert!((sdf.distance(Vec3::new(-1., 0., 1.)) - 0.8).abs() < EPSILON);
+        assert!((sdf.distance(Vec3::new(0., 0., 1.)) - 0.7).abs() < EPSILON);
+        assert!((sdf.distance(Vec3::new(2., 0.5, 0.)) - 0.6).abs() < EPSILON);
+    }
+
+    #[test]
+    fn bezier_tube() {
+        let mut object = Object::new("test".to_string(), Vec3::ZERO);
+        // parabola y = 1 - x^2 between x = -1 and 1
+        let points = vec![
+            TubePoint::new(Vec3::new(-1., 0., 0.), 0.1),
+            TubePoint::new(Vec3::new(0., 2., 0.), 0.1),
+            TubePoint::new(Vec3::new(1., 0., 0.), 0.1),
+        ];
+        let tube = Tube::new(TubePath::QuadraticBezier, points);
+        push(&mut object, tube.into(), Vec3::ZERO, Operation::Union, 0.);
+        let sdf = ObjectSdf::new(&object);
+
+        assert!((sdf.distance(Vec3::new(0., 2., 0.)) - 0.9).abs() < EPSILON);
+        assert!((sdf.distance(Vec3::ZERO) - (0.75_f32.sqrt() - 0.1)).abs() < EPSILON);
+        assert!((sdf.distance(Vec3::new(-2., 0., 0.)) - 0.9).abs() < EPSILON);
+    }
+
+    #[test]
+    fn bezier_tube_even_point_count() {
+        let mut object = Object::new("test".to_string(), Vec3::ZERO);
+        // last segment becomes a straight line
+        let points = vec![
+            TubePoint::new(Vec3::new(-1., 0., 0.), 0.1),
+            TubePoint::new(Vec3::new(0., 2., 0.), 0.1),
+            TubePoint::new(Vec3::new(1., 0., 0.), 0.1),
+            TubePoint::new(Vec3::new(3., 0., 0.), 0.1),
+        ];
+        let tube = Tube::new(TubePath::QuadraticBezier, points);
+        push(&mut object, tube.into(), Vec3::ZERO, Operation::Union, 0.);
+        let sdf = ObjectSdf::new(&object);
+
+        assert!((sdf.distance(Vec3::new(2., 1., 0.)) - 0.9).abs() < EPSILON);
+    }
+
+    #[test]
+    fn finite_repetition() {
+        let mut object = Object::new("test".to_string(), Vec3::ZERO);
+        push(
+            &mut object,
+            Sphere::new(0.25).into(),
+            Vec3::ZERO,
+            Operation::Union,
+            0.,
+        );
+        object.primitive_ops[0].modifiers = vec![DomainModifier::Repeat {
+            spacing: Vec3::new(1., 0., 0.),
+            limit: Some(UVec3::ONE),
+        }];
+        let sdf = ObjectSdf::new(&object);
+
+        assert!((sdf.distance(Vec3::new(-1., 0., 0.)) + 0.25).abs() < EPSILON);
+        assert!((sdf.distance(Vec3::new(3., 0., 0.)) - 1.75).abs() < EPSILON);
+        // not repeated along y
+        assert!((sdf.distance(Vec3::new(0., 1., 0.)) - 0.75).abs() < EPSILON);
+
+        let aabb = object.aabb();
+        assert!(aabb.min.x < -1.25 && aabb.max.x > 1.25);
+        assert!(aabb.max.y < 0.5);
+    }
+
+    #[test]
+    fn infinite_repetition() {
+        let mut object = Object::new("test".to_string(), Vec3::ZERO);
+        push(
+            &mut object,
+            Sphere::new(0.25).into(),
+            Vec3::ZERO,
+            Operation::Union,
+            0.,
+        );
+        object.primitive_ops[0].modifiers = vec![DomainModifier::Repeat {
+            spacing: Vec3::splat(2.),
+            limit: None,
+        }];
+        let sdf = ObjectSdf::new(&object);
+
+        assert!((sdf.distance(Vec3::new(10., -4., 6.)) + 0.25).abs() < EPSILON);
+        assert!((sdf.distance(Vec3::new(11., -4., 6.)) - 0.75).abs() < EPSILON);
+    }
+
+    #[test]
+    fn elongate_then_symmetry() {
+        let mut object = Object::new("test".to_string(), Vec3::ZERO);
+        push(
+            &mut object,
+            Sphere::new(0.5).into(),
+            Vec3::ZERO,
+            Operation::Union,
+            0.,
+        );
+        object.primitive_ops[0].modifiers = vec![
+            DomainModifier::Symmetry {
+                axes: BVec3::new(true, false, false),
+            },
+            DomainModifier::Elongate {
+                amount: Vec3::new(2., 0., 0.),
+            },
+        ];
+        let sdf = ObjectSdf::new(&object);
+
+        assert!((sdf.distance(Vec3::new(1.5, 0., 0.))).abs() < EPSILON);
+        assert!((sdf.distance(Vec3::new(-2., 0., 0.)) - 0.5).abs() < EPSILON);
+        assert!((sdf.distance(Vec3::new(0.5, 1., 0.)) - 0.5).abs() < EPSILON);
+    }
+
+    #[test]
+    fn twist_is_conservative() {
+        let mut object = Object::new("test".to_string(), Vec3::ZERO);
+        push(
+            &mut object,
+            Cube::new(Vec3::new(1., 0.2, 4.)).into(),
+            Vec3::ZERO,
+            Operation::Union,
+            0.,
+        );
+        object.primitive_ops[0].modifiers = vec![DomainModifier::Twist { rate: 1. }];
+        let sdf = ObjectSdf::new(&object);
+
+        // a quarter turn at z = pi/2 swaps the long side of the bar to the y axis
+        let z = std::f32::consts::FRAC_PI_2;
+        assert!(sdf.distance(Vec3::new(0., 0.4, z)) < 0.);
+        assert!(sdf.distance(Vec3::new(0.4, 0., z)) > 0.);
+
+        // distances must never overshoot the surface
+        let aabb = object.aabb();
+        for i in 0..200 {
+            let t = i as f32 * 0.1;
+            let pos = Vec3::new(t.sin() * 2., t.cos() * 2., (t * 0.37).sin() * 2.);
+            let d = sdf.distance(pos);
+            let step = pos - sdf.normal(pos) * d;
+            assert!(sdf.distance(step) >= -0.05, "overshot at {:?}", pos);
+            if d < 0. {
+                assert!(pos.cmpge(aabb.min).all() && pos.cmple(aabb.max).all());
+            }
+        }
+    }
+
+    #[test]
+    fn noise_ranges() {
+        for i in 0..500 {
+            let t = i as f32 * 0.173;
+            let p = Vec3::new(t.sin() * 7.3, t * 0.31 - 20., (t * 1.7).cos() * 3.1);
+            for noise in [value_noise, fbm_noise, voronoi_noise, sine_pattern] {
+                let value = noise(p, i);
+                assert!((-1. ..=1.).contains(&value), "{} out of range", value);
+            }
+        }
+        // same inputs -> same outputs, different seed -> different outputs
+        let p = Vec3::new(0.3, -1.6, 2.2);
+        assert_eq!(value_noise(p, 7), value_noise(p, 7));
+        assert_ne!(value_noise(p, 7), value_noise(p, 8));
+    }
+
+    #[test]
+    fn displacement() {
+        let mut object = Object::new("test".to_string(), Vec3::ZERO);
+        push(
+            &mut object,
+            Sphere::new(1.).into(),
+            Vec3::ZERO,
+            Operation::Union,
+            0.,
+        );
+        let displace = SurfaceModifier::Displace {
+            pattern: NoisePattern::Fbm,
+            amplitude: 0.2,
+            frequency: 3.,
+            seed: 5,
+        };
+        object.primitive_ops[0].surface_modifiers = vec![displace];
+        let sdf = ObjectSdf::new(&object);
+        // the bounding box grows by the amplitude
+        let aabb = object.aabb();
+        assert!(aabb.max.x >= 1.2 && aabb.min.x <= -1.2);
+
+        for i in 0..200 {
+            let t = i as f32 * 0.1;
+            let pos = Vec3::new(t.sin() * 1.5, t.cos() * 1.5, (t * 0.37).sin() * 1.5);
+            let d = sdf.distance(pos);
+            // the surface only moves by the amplitude
+            let sphere_d = pos.length() - 1.;
+            assert!(d.signum() == sphere_d.signum() || sphere_d.abs() <= 0.2);
+            // scaled distances never overshoot the surface
+            let step = pos - sdf.normal(pos) * d;
+            assert!(
+                sdf.distance(step) * d.signum() >= -0.01,
+                "overshot at {:?}",
+                pos
+            );
+        }
+    }
+
+    #[test]
+    fn round_shell_and_surface_only() {
+        let mut object = Object::new("test".to_string(), Vec3::ZERO);
+        push(
+            &mut object,
+            Sphere::new(1.).into(),
+            Vec3::ZERO,
+            Operation::Union,
+            0.,
+        );
+        object.primitive_ops[0].surface_modifiers = vec![SurfaceModifier::Round { radius: 0.25 }];
+        let sdf = ObjectSdf::new(&object);
+        assert!((sdf.distance(Vec3::new(2., 0., 0.)) - 0.75).abs() < EPSILON);
+        assert!(object.aabb().max.x >= 1.25);
+
+        // wall between radius 0.8 and 1
+        object.primitive_ops[0].surface_modifiers = vec![SurfaceModifier::Shell { thickness: 0.2 }];
+        let sdf = ObjectSdf::new(&object);
+        assert!((sdf.distance(Vec3::ZERO) - 0.8).abs() < EPSILON);
+        assert!((sdf.distance(Vec3::new(0.9, 0., 0.)) + 0.1).abs() < EPSILON);
+        assert!((sdf.distance(Vec3::new(1.5, 0., 0.)) - 0.5).abs() < EPSILON);
+        assert!(object.aabb().max.x >= 1.);
+
+        object.primitive_ops[0].surface_modifiers = vec![SurfaceModifier::SurfaceOnly];
+        let sdf = ObjectSdf::new(&object);
+        assert!((sdf.distance(Vec3::ZERO) - 1.).abs() < EPSILON);
+        assert!((sdf.distance(Vec3::new(0., 0., 1.5)) - 0.5).abs() < EPSILON);
+    }
+
+    #[test]
+    fn onion_layers() {
+        let mut object = Object::new("test".to_string(), Vec3::ZERO);
+        push(
+            &mut object,
+            Sphere::new(1.).into(),
+            Vec3::ZERO,
+            Operation::Union,
+            0.,
+        );
+        object.primitive_ops[0].surface_modifiers = vec![SurfaceModifier::Onion {
+            thickness: 0.2,
+            layers: 2,
+        }];
+        let sdf = ObjectSdf::new(&object);
+        // two shells around radius 0.8 and 1.2, each 0.2 thick
+        for radius in [0.8, 1.2] {
+            assert!((sdf.distance(Vec3::new(radius, 0., 0.)) + 0.1).abs() < EPSILON);
+        }
+        assert!((sdf.distance(Vec3::X) - 0.1).abs() < EPSILON);
+        assert!((sdf.distance(Vec3::ZERO) - 0.7).abs() < EPSILON);
+        assert!(object.aabb().max.x >= 1.3);
+
+        // stacking onions is the same as adding layers
+        let mut stacked_object = object.clone();
+        stacked_object.primitive_ops[0].surface_modifiers = vec![
+            SurfaceModifier::Onion {
+                thickness: 0.2,
+                layers: 1,
+            },
+            SurfaceModifier::Onion {
+                thickness: 0.1,
+                layers: 1,
+            },
+        ];
+        let stacked_sdf = ObjectSdf::new(&stacked_object);
+        for x in [0., 0.75, 0.9, 1., 1.25, 2.] {
+            let pos = Vec3::new(x, 0., 0.);
+            assert!((sdf.distance(pos) - stacked_sdf.distance(pos)).abs() < EPSILON);
+        }
+    }
+
+    #[test]
+    fn smooth_intersection_and_subtraction() {
+        let blend = 0.4;
+        for op in [Operation::Intersection, Operation::Subtraction] {
+            let mut sharp = Object::new("sharp".to_string(), Vec3::ZERO);
+            let mut smooth = Object::new("smooth".to_string(), Vec3::ZERO);
+            for (object, blend) in [(&mut sharp, 0.), (&mut smooth, blend)] {
+                push(
+                    object,
+                    Sphere::new(1.).into(),
+                    Vec3::ZERO,
+                    Operation::Union,
+                    0.,
+                );
+                push(
+                    object,
+                    Sphere::new(1.).into(),
+                    Vec3::new(1., 0., 0.),
+                    op,
+                    blend,
+                );
+            }
+            let sharp_sdf = ObjectSdf::new(&sharp);
+            let smooth_sdf = ObjectSdf::new(&smooth);
+
+            // where the two surfaces meet, the edge gets rounded off by blend / 4
+            let seam = Vec3::new(0.5, 0.75_f32.sqrt(), 0.);
+            let smooth_res = smooth_sdf.map(seam);
+            assert!(sharp_sdf.distance(seam).abs() < EPSILON);
+            assert!((smooth_res.d - blend / 4.).abs() < EPSILON);
+            assert_eq!(smooth_res.op_index, PRIMITIVE_ID_BLEND);
+
+            // far from the seam smoothing does nothing
+            let pos = Vec3::new(-0.9, 0., 0.);
+            assert!((smooth_sdf.distance(pos) - sharp_sdf.distance(pos)).abs() < EPSILON);
+            assert_ne!(smooth_sdf.map(pos).op_index, PRIMITIVE_ID_BLEND);
+        }
+    }
+
+    #[test]
+    fn hard_edged_blends() {
+        let r = 0.3;
+        // the corner where two surfaces meet at right angles is cut back by r / sqrt(2)
+        assert!((chamfer_union(0., 0., r) + r * std::f32::consts::FRAC_1_SQRT_2).abs() < EPSILON);
+
+        for i in 0..400 {
+            let a = (i % 20) as f32 * 0.05 - 0.5;
+            let b = (i / 20) as f32 * 0.05 - 0.5;
+            let min = a.min(b);
+            let unions = [
+                chamfer_union(a, b, r),
+                stairs_union(a, b, r, 3.),
+                columns_union(a, b, r, 3.),
+            ];
+            for d in unions {
+                // unions only add material within r of the surfaces
+                assert!(d <= min + EPSILON, "{} > {} at ({}, {})", d, min, a, b);
+                assert!(d >= 0. || min < r, "{} inside at ({}, {})", d, a, b);
+            }
+            // subtractions only remove material
+            let max = a.max(-b);
+            assert!(-chamfer_union(-a, b, r) >= max - EPSILON);
+            assert!(-stairs_union(-a, b, r, 3.) >= max - EPSILON);
+            assert!(columns_subtraction(a, b, r, 3.) >= max - EPSILON);
+        }
+    }
+
+    #[test]
+    fn groove_and_tongue() {
+        // positions next to the plane where the groove walls/tongue sides are closest
+        for (op, pos, expected_d) in [
+            (Operation::Groove { width: 0.1 }, Vec3::new(0., 1., 0.), 0.1),
+            (
+                Operation::Tongue { width: 0.1 },
+                Vec3::new(0.08, 1.05, 0.),
+                -0.02,
+            ),
+        ] {
+            let mut object = Object::new("test".to_string(), Vec3::ZERO);
+            push(
+                &mut object,
+                Sphere::new(1.).into(),
+                Vec3::ZERO,
+                Operation::Union,
+                0.,
+            );
+            // large cube with a face on the x = 0 plane
+            push(
+                &mut object,
+                Cube::new(Vec3::splat(10.)).into(),
+                Vec3::new(-5., 0., 0.),
+                op,
+                0.2,
+            );
+            let sdf = ObjectSdf::new(&object);
+
+            let res = sdf.map(pos);
+            assert!((res.d - expected_d).abs() < EPSILON, "{:?}: {}", op, res.d);
+            assert_eq!(res.op_index, 1);
+
+            // away from the plane the sphere is untouched
+            let pos = Vec3::new(0.5, 0.75_f32.sqrt(), 0.);
+            let res = sdf.map(pos);
+            assert!(res.d.abs() < EPSILON);
+            assert_eq!(res.op_index, 0);
+        }
+    }
+
+    #[test]
+    fn subtraction() {
+        let mut object = Object::new("test".to_string(), Vec3::ZERO);
+        push(
+            &mut object,
+            Sphere::new(1.).into(),
+            Vec3::ZERO,
+            Operation::Union,
+            0.,
+        );
+        push(
+            &mut object,
+            Sphere::new(0.5).into(),
+            Vec3::ZERO,
+            Operation::Subtraction,
+            0.,
+        );
+        let sdf = ObjectSdf::new(&object);
+
+        let res = sdf.map(Vec3::ZERO);
+        assert!((res.d - 0.5).abs() < EPSILON);
+        assert_eq!(res.op_index, 1);
+    }
+
+    #[test]
+    fn blended_union() {
+        let mut object = Object::new("test".to_string(), Vec3::ZERO);
+        push(
+            &mut object,
+            Sphere::new(0.5).into(),
+            Vec3::new(-0.6, 0., 0.),
+            Operation::Union,
+            0.,
+        );
+        push(
+            &mut object,
+            Sphere::new(0.5).into(),
+            Vec3::new(0.6, 0., 0.),
+            Operation::Union,
+            0.4,
+        );
+        let sdf = ObjectSdf::new(&object);
+
+        // both spheres are 0.1 away from the origin, smooth min pulls the surface in by blend/4
+        let res = sdf.map(Vec3::ZERO);
+        assert!((res.d - (0.1 - 0.4 / 4.)).abs() < EPSILON);
+        assert_eq!(res.op_index, PRIMITIVE_ID_BLEND);
+    }
+
+    #[test]
+    fn normal_points_outwards() {
+        let mut object = Object::new("test".to_string(), Vec3::ZERO);
+        push(
+            &mut object,
+            Sphere::new(1.).into(),
+            Vec3::ZERO,
+            Operation::Union,
+            0.,
+        );
+        let sdf = ObjectSdf::new(&object);
+
+        let normal = sdf.normal(Vec3::new(0., 0., 1.));
+        assert!((normal - Vec3::Z).length() < EPSILON);
+    }
+
+    #[test]
+    fn morph_and_xor() {
+        let object_with_op = |op: Operation| {
+            let mut object = Object::new("test".to_string(), Vec3::ZERO);
+            push(
+                &mut object,
+                Sphere::new(1.).into(),
+                Vec3::ZERO,
+                Operation::Union,
+                0.,
+            );
+            push(
+                &mut object,
+                Cube::new(Vec3::splat(1.)).into(),
+                Vec3::new(1., 0., 0.),
+                op,
+                0.,
+            );
+            ObjectSdf::new(&object)
+        };
+
+        // the ends of a morph are the original shapes
+        let pos = Vec3::new(-1.5, 0., 0.);
+        let res = object_with_op(Operation::Morph { factor: 0. }).map(pos);
+        assert!((res.d - 0.5).abs() < EPSILON);
+        assert_eq!(res.op_index, 0);
+        let res = object_with_op(Operation::Morph { factor: 1. }).map(pos);
+        assert!((res.d - 2.).abs() < EPSILON);
+        assert_eq!(res.op_index, 1);
+        let res = object_with_op(Operation::Morph { factor: 0.25 }).map(pos);
+        assert!((res.d - 0.875).abs() < EPSILON);
+        assert_eq!(res.op_index, PRIMITIVE_ID_BLEND);
+
+        let sdf = object_with_op(Operation::Xor);
+        // inside the sphere only
+        let res = sdf.map(Vec3::new(-0.5, 0., 0.));
+        assert!((res.d - -0.5).abs() < EPSILON);
+        assert_eq!(res.op_index, 0);
+        // inside the cube only
+        let res = sdf.map(Vec3::new(1.3, 0., 0.));
+        assert!((res.d - -0.2).abs() < EPSILON);
+        assert_eq!(res.op_index, 1);
+        // inside both is outside, closest to the cube face at x = 0.5
+        let res = sdf.map(Vec3::new(0.6, 0., 0.));
+        assert!((res.d - 0.1).abs() < EPSILON);
+        assert_eq!(res.op_index, 1);
+    }
+
+    #[test]
+    fn rotated_and_scaled_object() {
+        let mut object = Object::new("test".to_string(), Vec3::new(0., 0., 1.));
+        object.rotation = Quat::from_rotation_z(std::f32::consts::FRAC_PI_2);
+        object.scale = 2.;
+        push(
+            &mut object,
+            Cube::new(Vec3::ONE).into(),
+            Vec3::new(1., 0., 0.),
+            Operation::Union,
+            0.,
+        );
+        let sdf = ObjectSdf::new(&object);
+
+        // the cube ends up 2 units wide around (0, 2, 1)
+        assert!((sdf.distance(Vec3::new(0., 4., 1.)) - 1.).abs() < EPSILON);
+        assert!((sdf.distance(Vec3::new(0., 2., 1.)) - -1.).abs() < EPSILON);
+        assert!((sdf.distance(Vec3::new(3., 2., 1.)) - 2.).abs() < EPSILON);
+
+        let aabb = object.aabb();
+        assert!(aabb.min.cmple(Vec3::new(-1., 1., 0.)).all());
+        assert!(aabb.max.cmpge(Vec3::new(1., 3., 2.)).all());
+        assert!(aabb.min.cmpgt(Vec3::new(-1.1, 0.9, -0.1)).all());
+    }
+
+    #[test]
+    fn grouped_subtraction() {
+        // sphere A with spheres B and C either side of it
+        let mut object = Object::new("test".to_string(), Vec3::ZERO);
+        push(
+            &mut object,
+            Sphere::new(1.).into(),
+            Vec3::ZERO,
+            Operation::Union,
+            0.,
+        );
+        push(
+            &mut object,
+            Sphere::new(0.3).into(),
+            Vec3::new(1., 0., 0.),
+            Operation::Subtraction,
+            0.,
+        );
+        push(
+            &mut object,
+            Sphere::new(0.3).into(),
+            Vec3::new(-1., 0., 0.),
+            Operation::Union,
+            0.,
+        );
+
+        // A - B ∪ C: C gets added after subtracting B
+        let sdf = ObjectSdf::new(&object);
+        assert!((sdf.distance(Vec3::new(-1.2, 0., 0.)) - -0.1).abs() < EPSILON);
+
+        // A - (B ∪ C): B and C are both subtracted from A
+        let group_id = object.push_group(None).unwrap();
+        object
+            .set_group(group_id, None, Some(Operation::Subtraction), None)
+            .unwrap();
+        let group_ids: Vec<_> = object.primitive_ops[1..].iter().map(|p| p.id()).collect();
+        for primitive_op_id in group_ids {
+            object
+                .set_primitive_op_id(
+                    primitive_op_id,
+                    None,
+                    None,
+                    Some(Operation::Union),
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .unwrap();
+            object
+                .move_primitive_op(primitive_op_id, Some(group_id), None)
+                .unwrap();
+        }
+        let sdf = ObjectSdf::new(&object);
+        assert!((sdf.distance(Vec3::new(-1.2, 0., 0.)) - 0.2).abs() < EPSILON);
+        assert!((sdf.distance(Vec3::new(1.2, 0., 0.)) - 0.2).abs() < EPSILON);
+
+        // the result refers to B by its index in the object, not the packet index
+        let res = sdf.map(Vec3::new(0.5, 0., 0.));
+        assert!((res.d - -0.2).abs() < EPSILON);
+        assert_eq!(res.op_index, 1);
+
+        // subtracting the group can't grow the bounds past A
+        let sphere_aabb = object.primitive_ops[0].aabb();
+        let aabb = object.aabb();
+        assert!((aabb.min - sphere_aabb.min).length() < EPSILON);
+        assert!((aabb.max - sphere_aabb.max).length() < EPSILON);
+    }
+
+    #[test]
+    fn primitive_op_groups() {
+        let mut object = Object::new("test".to_string(), Vec3::ZERO);
+        for x in 0..4 {
+            push(
+                &mut object,
+                Sphere::new(0.1).into(),
+                Vec3::new(x as f32, 0., 0.),
+                Operation::Union,
+                0.,
+            );
+        }
+        let ids: Vec<_> = object.primitive_ops.iter().map(|p| p.id()).collect();
+
+        let group_id = object.push_group(None).unwrap();
+        let sub_group_id = object.push_group(Some(group_id)).unwrap();
+        object
+            .move_primitive_op(ids[3], Some(sub_group_id), None)
+            .unwrap();
+        object
+            .move_primitive_op(ids[0], Some(group_id), None)
+            .unwrap();
+
+        // members of a group stay next to each other, starting where the group first appeared
+        let order: Vec<_> = object.primitive_ops.iter().map(|p| p.id()).collect();
+        assert_eq!(order, vec![ids[1], ids[2], ids[3], ids[0]]);
+
+        let steps: Vec<_> = object
+            .evaluation_steps()
+            .iter()
+            .map(|step| match step {
+                EvaluationStep::GroupBegin(group) => format!("begin {}", group.id()),
+                EvaluationStep::Primitive(primitive_op) => format!("{}", primitive_op.id()),
+                EvaluationStep::GroupEnd(group) => format!("end {}", group.id()),
+            })
+            .collect();
+        let expected_steps = [
+            ids[1].to_string(),
+            ids[2].to_string(),
+            format!("begin {}", group_id),
+            format!("begin {}", sub_group_id),
+            ids[3].to_string(),
+            format!("end {}", sub_group_id),
+            ids[0].to_string(),
+            format!("end {}", group_id),
+        ];
+        assert_eq!(steps, expected_steps);
+
+        // a group can't be moved into its own sub-group
+        assert!(object
+            .move_group(group_id, Some(sub_group_id), None)
+            .is_err());
+
+        // removing a group moves its contents up a level
+        object.remove_group(group_id).unwrap();
+        assert_eq!(object.get_group(sub_group_id).unwrap().parent(), None);
+        assert_eq!(object.get_primitive_op(ids[0]).unwrap().group(), None);
+
+        // grouping doesn't change the shape of unions
+        let sdf = ObjectSdf::new(&object);
+        assert!((sdf.distance(Vec3::new(3., 0.2, 0.)) - 0.1).abs() < EPSILON);
+    }
+
+    #[test]
+    fn max_group_depth() {
+        let mut object = Object::new("test".to_string(), Vec3::ZERO);
+        let mut parent = None;
+        for _ in 0..MAX_GROUP_DEPTH {
+            parent = Some(object.push_group(parent).unwrap());
+        }
+        assert!(object.push_group(parent).is_err());
+    }
+}
@@ -0,0 +1,222 @@
+use super::object::{object::ObjectId, primitive_op::PrimitiveOpId};
+
+/// Selected objects and primitive ops, in the order they were selected. The last selected object
+/// is the active object shown in the object editor, and the last selected primitive op of the
+/// active object is the active primitive op. Selected primitive ops always belong to a selected
+/// object.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Selection {
+    objects: Vec<ObjectId>,
+    primitive_ops: Vec<(ObjectId, PrimitiveOpId)>,
+}
+
+impl Selection {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn active_object_id(&self) -> Option<ObjectId> {
+        self.objects.last().copied()
+    }
+
+    pub fn active_primitive_op_id(&self) -> Option<PrimitiveOpId> {
+        let active_object_id = self.active_object_id()?;
+        self.primitive_ops
+            .iter()
+            .rev()
+            .find(|(object_id, _)| *object_id == active_object_id)
+            .map(|&(_, primitive_op_id)| primitive_op_id)
+    }
+
+    pub fn objects(&self) -> &[ObjectId] {
+        &self.objects
+    }
+
+    pub fn primitive_ops(&self) -> &[(ObjectId, PrimitiveOpId)] {
+        &self.primitive_ops
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+
+    pub fn contains_object(&self, object_id: ObjectId) -> bool {
+        self.objects.contains(&object_id)
+    }
+
+    pub fn contains_primitive_op(
+        &self,
+        object_id: ObjectId,
+        primitive_op_id: PrimitiveOpId,
+    ) -> bool {
+        self.primitive_ops.contains(&(object_id, primitive_op_id))
+    }
+
+    /// Replaces the selection with the object. Selected primitive ops of the object stay selected.
+    pub fn select_object(&mut self, object_id: ObjectId) {
+        self.objects = vec![object_id];
+        self.primitive_ops
+            .retain(|&(primitive_op_object_id, _)| primitive_op_object_id == object_id);
+    }
+
+    /// Adds the object to the selection and makes it the active object.
+    pub fn add_object(&mut self, object_id: ObjectId) {
+        self.objects.retain(|&selected_id| selected_id != object_id);
+        self.objects.push(object_id);
+    }
+
+    /// Deselects the object and its primitive ops.
+    pub fn remove_object(&mut self, object_id: ObjectId) {
+        self.objects.retain(|&selected_id| selected_id != object_id);
+        self.primitive_ops
+            .retain(|&(primitive_op_object_id, _)| primitive_op_object_id != object_id);
+    }
+
+    pub fn toggle_object(&mut self, object_id: ObjectId) {
+        if self.contains_object(object_id) {
+            self.remove_object(object_id);
+        } else {
+            self.add_object(object_id);
+        }
+    }
+
+    /// Replaces the selection with the primitive op and its object.
+    pub fn select_primitive_op(&mut self, object_id: ObjectId, primitive_op_id: PrimitiveOpId) {
+        self.objects = vec![object_id];
+        self.primitive_ops = vec![(object_id, primitive_op_id)];
+    }
+
+    /// Adds the primitive op and its object to the selection and makes them active.
+    pub fn add_primitive_op(&mut self, object_id: ObjectId, primitive_op_id: PrimitiveOpId) {
+        self.add_object(object_id);
+        self.remove_primitive_op(object_id, primitive_op_id);
+        self.primitive_ops.push((object_id, primitive_op_id));
+    }
+
+    /// The object stays selected.
+    pub fn remove_primitive_op(&mut self, object_id: ObjectId, primitive_op_id: PrimitiveOpId) {
+        self.primitive_ops
+            .retain(|&selected| selected != (object_id, primitive_op_id));
+    }
+
+    pub fn toggle_primitive_op(&mut self, object_id: ObjectId, primitive_op_id: PrimitiveOpId) {
+        if self.contains_primitive_op(object_id, primitive_op_id) {
+            self.remove_primitive_op(object_id, primitive_op_id);
+        } else {
+            self.add_primitive_op(object_id, primitive_op_id);
+        }
+    }
+
+    /// Objects stay selected.
+    pub fn clear_primitive_ops(&mut self) {
+        self.primitive_ops.clear();
+    }
+
+    pub fn clear(&mut self) {
+        self.objects.clear();
+        self.primitive_ops.clear();
+    }
+
+    /// Selects `object_ids` in order, keeping the active object active if it's one of them.
+    pub fn select_all(&mut self, object_ids: impl IntoIterator<Item = ObjectId>) {
+        let previous_active_object_id = self.active_object_id();
+        for object_id in object_ids {
+            if !self.contains_object(object_id) {
+                self.objects.push(object_id);
+            }
+        }
+        if let Some(active_object_id) = previous_active_object_id {
+            self.add_object(active_object_id);
+        }
+    }
+
+    /// Selects the objects in `object_ids` that aren't selected and deselects the ones that are.
+    pub fn invert(&mut self, object_ids: impl IntoIterator<Item = ObjectId>) {
+        for object_id in object_ids {
+            self.toggle_object(object_id);
+        }
+    }
+
+    /// Deselects objects and primitive ops that `is_valid` returns false for, e.g. after they
+    /// have been removed.
+    pub fn retain(
+        &mut self,
+        mut is_valid_object: impl FnMut(ObjectId) -> bool,
+        mut is_valid_primitive_op: impl FnMut(ObjectId, PrimitiveOpId) -> bool,
+    ) {
+        self.objects.retain(|&object_id| is_valid_object(object_id));
+        let objects = &self.objects;
+        self.primitive_ops.retain(|&(object_id, primitive_op_id)| {
+            objects.contains(&object_id) && is_valid_primitive_op(object_id, primitive_op_id)
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids() -> (ObjectId, ObjectId, PrimitiveOpId, PrimitiveOpId) {
+        (
+            ObjectId::from(1),
+            ObjectId::from(2),
+            PrimitiveOpId::from(1),
+            PrimitiveOpId::from(2),
+        )
+    }
+
+    #[test]
+    fn active_element_is_last_selected() {
+        let (object_a, object_b, primitive_op_a, primitive_op_b) = ids();
+        let mut selection = Selection::new();
+        selection.select_primitive_op(object_a, primitive_op_a);
+        selection.add_primitive_op(object_b, primitive_op_b);
+        assert_eq!(selection.objects(), &[object_a, object_b]);
+        assert_eq!(selection.active_object_id(), Some(object_b));
+        assert_eq!(selection.active_primitive_op_id(), Some(primitive_op_b));
+
+        // the active object has no selected primitive op
+        selection.add_object(object_a);
+        selection.remove_primitive_op(object_a, primitive_op_a);
+        assert_eq!(selection.active_object_id(), Some(object_a));
+        assert_eq!(selection.active_primitive_op_id(), None);
+
+        // deselecting an object deselects its primitive ops
+        selection.toggle_object(object_b);
+        assert_eq!(selection.objects(), &[object_a]);
+        assert!(selection.primitive_ops().is_empty());
+    }
+
+    #[test]
+    fn select_all_and_invert() {
+        let (object_a, object_b, primitive_op_a, _) = ids();
+        let object_c = ObjectId::from(3);
+        let mut selection = Selection::new();
+        selection.select_primitive_op(object_b, primitive_op_a);
+
+        selection.select_all([object_a, object_b, object_c]);
+        assert_eq!(selection.objects(), &[object_a, object_c, object_b]);
+        assert_eq!(selection.active_primitive_op_id(), Some(primitive_op_a));
+
+        selection.select_object(object_a);
+        selection.invert([object_a, object_b, object_c]);
+        assert_eq!(selection.objects(), &[object_b, object_c]);
+        assert!(selection.primitive_ops().is_empty());
+    }
+
+    #[test]
+    fn retain_valid() {
+        let (object_a, object_b, primitive_op_a, primitive_op_b) = ids();
+        let mut selection = Selection::new();
+        selection.select_primitive_op(object_a, primitive_op_a);
+        selection.add_primitive_op(object_a, primitive_op_b);
+        selection.add_primitive_op(object_b, primitive_op_a);
+
+        selection.retain(
+            |object_id| object_id == object_a,
+            |_, primitive_op_id| primitive_op_id == primitive_op_a,
+        );
+        assert_eq!(selection.objects(), &[object_a]);
+        assert_eq!(selection.primitive_ops(), &[(object_a, primitive_op_a)]);
+    }
+}
@@ -1,4 +1,7 @@
 use super::unique_id_gen::{UniqueId, UniqueIdError};
+use crate::config::{
+    ENGINE_NAME, ENGINE_VERSION_MAJOR, ENGINE_VERSION_MINOR, ENGINE_VERSION_PATCH,
+};
 use egui_dnd::utils::ShiftSliceError;
 use std::{error, fmt, io};
 
@@ -8,6 +11,8 @@ use std::{error, fmt, io};
 pub enum CollectionError {
     OutOfBounds { index: usize, size: usize },
     InvalidId { raw_id: UniqueId },
+    InvalidParent { raw_id: UniqueId },
+    MaxDepthReached { max_depth: usize },
     UniqueIdError(UniqueIdError),
     ShiftSliceError(ShiftSliceError),
 }
@@ -19,6 +24,10 @@ impl fmt::Display for CollectionError {
                 write!(f, "index {} out of bounds. size = {}", index, size)
             }
             Self::InvalidId { raw_id } => write!(f, "invalid id {}", raw_id),
+            Self::InvalidParent { raw_id } => write!(f, "invalid parent id {}", raw_id),
+            Self::MaxDepthReached { max_depth } => {
+                write!(f, "max nesting depth of {} reached", max_depth)
+            }
             Self::UniqueIdError(e) => e.fmt(f),
             Self::ShiftSliceError(e) => e.fmt(f),
         }
@@ -58,6 +67,10 @@ pub enum IoError {
     FileDoesntExist(String, io::Error),
     ReadExistingFileFailed(String, io::Error),
     ReadBufferFailed(io::Error),
+    /// File name. The file doesn't start with the precursor bytes.
+    NotAnEngineFile(String),
+    /// File name and the major, minor and patch version that saved it
+    IncompatibleVersion(String, [u8; 3]),
 }
 
 impl std::fmt::Display for IoError {
@@ -89,6 +102,20 @@ impl std::fmt::Display for IoError {
             Self::ReadBufferFailed(e) => {
                 write!(f, "failed to read from a file buffer due to: {}", e)
             }
+            Self::NotAnEngineFile(file_name) => {
+                write!(f, "\"{}\" wasn't saved by {}", file_name, ENGINE_NAME)
+            }
+            Self::IncompatibleVersion(file_name, [major, minor, patch]) => write!(
+                f,
+                "\"{}\" was saved by version {}.{}.{} and can't be loaded by version {}.{}.{}",
+                file_name,
+                major,
+                minor,
+                patch,
+                ENGINE_VERSION_MAJOR,
+                ENGINE_VERSION_MINOR,
+                ENGINE_VERSION_PATCH
+            ),
         }
     }
 }
@@ -103,6 +130,7 @@ impl std::error::Error for IoError {
             Self::FileDoesntExist(_, e) => Some(e),
             Self::ReadExistingFileFailed(_, e) => Some(e),
             Self::ReadBufferFailed(e) => Some(e),
+            Self::NotAnEngineFile(_) | Self::IncompatibleVersion(..) => None,
         }
     }
 }
@@ -1,6 +1,6 @@
 use super::{
     object_resource_manager::ObjectResourceManager,
-    shader_interfaces::vertex_inputs::BoundingBoxVertex,
+    shader_interfaces::{push_constants::GeometryPushConstant, vertex_inputs::BoundingBoxVertex},
     vulkan_init::{
         create_camera_descriptor_set_with_binding, render_pass_indices, write_camera_descriptor_set,
     },
@@ -172,9 +172,15 @@ fn create_pipeline_layout(
     desc_set_layout_camera: Arc<DescriptorSetLayout>,
     desc_set_layout_primitive_ops: Arc<DescriptorSetLayout>,
 ) -> anyhow::Result<Arc<PipelineLayout>> {
+    let push_constant_range = vk::PushConstantRange::builder()
+        .stage_flags(vk::ShaderStageFlags::FRAGMENT)
+        .offset(0)
+        .size(std::mem::size_of::<GeometryPushConstant>() as u32)
+        .build();
+
     let pipeline_layout_props = PipelineLayoutProperties::new(
         vec![desc_set_layout_camera, desc_set_layout_primitive_ops],
-        Vec::new(),
+        vec![push_constant_range],
     );
 
     let pipeline_layout = PipelineLayout::new(device, pipeline_layout_props)
@@ -1,4 +1,4 @@
-use super::geometry_pass::descriptor;
+use super::{geometry_pass::descriptor, shader_interfaces::push_constants::GeometryPushConstant};
 use crate::engine::{
     aabb::AABB_VERTEX_COUNT,
     object::{
@@ -87,7 +87,18 @@ impl ObjectResourceManager {
 
         transfer_operation_resources.begin_command_buffers()?;
 
-        for (object_id, object_delta) in objects_delta {
+        // instances are processed last so that they can share the primitive ops buffers of their
+        // definitions after those have been updated
+        let (instance_deltas, object_deltas): (Vec<_>, Vec<_>) = objects_delta
+            .into_iter()
+            .partition(|(_, object_delta)| match object_delta {
+                ObjectDeltaOperation::Add(object) | ObjectDeltaOperation::Update(object) => {
+                    object.instance_of().is_some()
+                }
+                ObjectDeltaOperation::Remove => false,
+            });
+
+        for (object_id, object_delta) in object_deltas.into_iter().chain(instance_deltas) {
             match object_delta {
                 ObjectDeltaOperation::Add(object) => {
                     trace!("adding object id = {:?} to gpu buffer", object_id);
@@ -122,7 +133,7 @@ impl ObjectResourceManager {
     }
 
     pub fn draw_commands(&self, command_buffer: &CommandBuffer, pipeline: &GraphicsPipeline) {
-        for per_object_buffers in self.objects_buffers.iter() {
+        for per_object_buffers in self.visible_objects_buffers() {
             command_buffer.bind_descriptor_sets(
                 vk::PipelineBindPoint::GRAPHICS,
                 &pipeline.pipeline_layout(),
@@ -130,13 +141,19 @@ impl ObjectResourceManager {
                 [per_object_buffers.primitive_ops_descriptor_set.as_ref()],
                 &[],
             );
+            command_buffer.push_constants(
+                pipeline.pipeline_layout().as_ref(),
+                vk::ShaderStageFlags::FRAGMENT,
+                0,
+                bytemuck::bytes_of(&per_object_buffers.push_constant),
+            );
             command_buffer.bind_vertex_buffers(0, [&per_object_buffers.bounding_mesh_buffer], &[0]);
             command_buffer.draw(per_object_buffers.bounding_mesh_vertex_count, 1, 0, 0);
         }
     }
 
     pub fn draw_bounding_box_commands(&self, command_buffer: &CommandBuffer) {
-        for per_object_buffers in self.objects_buffers.iter() {
+        for per_object_buffers in self.visible_objects_buffers() {
             command_buffer.bind_vertex_buffers(0, [&per_object_buffers.bounding_mesh_buffer], &[0]);
             command_buffer.draw(per_object_buffers.bounding_mesh_vertex_count, 1, 0, 0);
         }
@@ -171,6 +188,13 @@ impl Drop for ObjectResourceManager {
 // Private functions
 
 impl ObjectResourceManager {
+    /// Hidden objects keep their resources so that instances can still share them, but aren't drawn.
+    fn visible_objects_buffers(&self) -> impl Iterator<Item = &PerObjectResources> {
+        self.objects_buffers
+            .iter()
+            .filter(|per_object_buffers| per_object_buffers.visible)
+    }
+
     fn allocate_primitive_ops_descriptor_set(&mut self) -> anyhow::Result<Arc<DescriptorSet>> {
         let descriptor_pool = self.descriptor_pools[self.descriptor_pools.len() - 1].clone();
 
@@ -247,37 +271,61 @@ impl ObjectResourceManager {
         object: &Object,
         transfer_resources: &mut BufferUploadResources,
     ) -> anyhow::Result<()> {
-        let primitive_ops_buffer = self
-            .upload_primitive_ops(object_id, &object, transfer_resources)
-            .context("initial upload object to buffer")?;
+        let bounding_mesh_buffer =
+            self.upload_bounding_mesh(object_id, &object, transfer_resources)?;
+        let push_constant = GeometryPushConstant::new(&object.world_transform());
+
+        // instances share the primitive ops of their definition if it's on the gpu
+        let definition_resources = object
+            .instance_of()
+            .and_then(|definition_id| self.get_index(definition_id))
+            .map(|definition_index| &self.objects_buffers[definition_index]);
+
+        let (definition_id, primitive_ops_buffer, primitive_ops_descriptor_set) =
+            if let Some(definition_resources) = definition_resources {
+                (
+                    Some(definition_resources.object_id),
+                    definition_resources.primitive_ops_buffer.clone(),
+                    definition_resources.primitive_ops_descriptor_set.clone(),
+                )
+            } else {
+                let primitive_ops_buffer = Arc::new(
+                    self.upload_primitive_ops(object_id, &object, transfer_resources)
+                        .context("initial upload object to buffer")?,
+                );
 
-        if let Some(index) = self.get_index(object_id) {
-            let bounding_mesh_buffer =
-                self.upload_bounding_mesh(object_id, &object, transfer_resources)?;
+                // rewriting the existing descriptor set updates the instances sharing it too
+                let existing_descriptor_set = self
+                    .get_index(object_id)
+                    .map(|index| &self.objects_buffers[index])
+                    .filter(|existing_resources| existing_resources.definition_id.is_none())
+                    .map(|existing_resources| {
+                        existing_resources.primitive_ops_descriptor_set.clone()
+                    });
+                let primitive_ops_descriptor_set = match existing_descriptor_set {
+                    Some(descriptor_set) => descriptor_set,
+                    None => self.allocate_primitive_ops_descriptor_set()?,
+                };
+                write_desc_set_primitive_ops(&primitive_ops_descriptor_set, &primitive_ops_buffer)?;
+
+                (None, primitive_ops_buffer, primitive_ops_descriptor_set)
+            };
 
-            write_desc_set_primitive_ops(
-                &self.objects_buffers[index].primitive_ops_descriptor_set,
-                &primitive_ops_buffer,
-            )?;
+        let new_resources = PerObjectResources {
+            object_id,
+            definition_id,
+            bounding_mesh_buffer,
+            bounding_mesh_vertex_count: AABB_VERTEX_COUNT as u32,
+            primitive_ops_buffer,
+            primitive_ops_descriptor_set,
+            push_constant,
+            visible: object.visible,
+        };
 
-            self.objects_buffers[index].bounding_mesh_buffer = bounding_mesh_buffer;
-            self.objects_buffers[index].bounding_mesh_vertex_count = AABB_VERTEX_COUNT as u32;
-            self.objects_buffers[index].primitive_ops_buffer = primitive_ops_buffer;
+        if let Some(index) = self.get_index(object_id) {
+            self.objects_buffers[index] = new_resources;
         } else {
-            let bounding_mesh_buffer =
-                self.upload_bounding_mesh(object_id, &object, transfer_resources)?;
-
-            let primitive_ops_descriptor_set = self.allocate_primitive_ops_descriptor_set()?;
-            write_desc_set_primitive_ops(&primitive_ops_descriptor_set, &primitive_ops_buffer)?;
-
-            let new_object = PerObjectResources {
-                object_id,
-                bounding_mesh_buffer,
-                bounding_mesh_vertex_count: AABB_VERTEX_COUNT as u32,
-                primitive_ops_buffer,
-                primitive_ops_descriptor_set,
-            };
-            self.objects_buffers.push(new_object);
+            self.objects_buffers.push(new_resources);
         }
 
         Ok(())
@@ -530,10 +578,14 @@ fn write_desc_set_primitive_ops(
 
 struct PerObjectResources {
     pub object_id: ObjectId,
+    /// Set if this is an instance sharing the primitive ops resources of another object.
+    pub definition_id: Option<ObjectId>,
     pub bounding_mesh_buffer: Buffer,
     pub bounding_mesh_vertex_count: u32,
-    pub primitive_ops_buffer: Buffer,
+    pub primitive_ops_buffer: Arc<Buffer>,
     pub primitive_ops_descriptor_set: Arc<DescriptorSet>,
+    pub push_constant: GeometryPushConstant,
+    pub visible: bool,
 }
 
 struct BufferUploadResources {
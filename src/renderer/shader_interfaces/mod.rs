@@ -1,4 +1,7 @@
 pub mod camera_uniform_buffer;
 pub mod primitive_op_buffer;
+mod primitive_op_layout;
 pub mod push_constants;
+#[cfg(test)]
+mod shader_source_hash;
 pub mod vertex_inputs;
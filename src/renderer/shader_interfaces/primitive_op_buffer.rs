@@ -1,7 +1,15 @@
-use crate::engine::{object::primitive_op::PrimitiveOp, primitives::primitive::EncodablePrimitive};
-use glam::Vec3;
+use crate::engine::{
+    object::{
+        object_transform::ObjectTransform,
+        primitive_op::PrimitiveOp,
+        primitive_op_group::{EvaluationStep, PrimitiveOpGroup},
+    },
+    primitives::primitive::EncodablePrimitive,
+};
 
-pub type PrimitiveOpBufferUnit = u32;
+// the packet layout and codes are defined in `primitive_op_layout.rs` so that they can be shared
+// with the shaders
+pub use super::primitive_op_layout::*;
 
 // this is because the shaders store the primitive op index in the lower 16 bits of a u32
 pub const MAX_PRIMITIVE_OP_COUNT: usize = u16::MAX as usize;
@@ -12,27 +20,6 @@ pub const PRIMITIVE_ID_BLEND: PrimitiveOpBufferUnit = 0xFFFE;
 pub const PRIMITIVE_ID_BACKGROUND: PrimitiveOpBufferUnit = 0xFFFFFFFF;
 pub const PRIMITIVE_ID_GIZMO: PrimitiveOpBufferUnit = 0xFFFFFFFE;
 
-#[rustfmt::skip]
-#[allow(dead_code)]
-pub mod op_codes {
-    use super::PrimitiveOpBufferUnit;
-    pub const NOP: 		    PrimitiveOpBufferUnit = 0x00000000;
-    pub const UNION: 		PrimitiveOpBufferUnit = 0x00000001; // OR
-    pub const INTERSECTION: PrimitiveOpBufferUnit = 0x00000002; // AND
-    pub const SUBTRACTION: 	PrimitiveOpBufferUnit = 0x00000003;
-    pub const INVALID:      PrimitiveOpBufferUnit = 0xFFFFFFFF;
-}
-
-/// Number of 32-bit values to store an op_code and a primitive. Note that this value should equal
-/// to 1 + `TRANSFORM_DATA_LEN` + `PRIMITIVE_DATA_LEN` for the total primitive data packet.
-///
-/// _Must match value defined in `confg.glsl`_
-pub const PRIMITIVE_PACKET_LEN: usize = 24;
-/// Each primitive has a 3x3 matrix associated with it for transformations. This defines that size.
-pub const PRIMITIVE_TRANSFORM_LEN: usize = 12;
-/// Each primitive type has unique properties encoded into an array of this length.
-pub const PRIMITIVE_PROPS_LEN: usize = 6;
-
 /// Array for data describing a primitive operation.
 /// Corresponds to decoding logic in `scene_geometry.frag`.
 pub type PrimitiveOpPacket = [PrimitiveOpBufferUnit; PRIMITIVE_PACKET_LEN];
@@ -43,73 +30,160 @@ pub type PrimitiveTransformSlice = [PrimitiveOpBufferUnit; PRIMITIVE_TRANSFORM_L
 /// Corresponds to decoding logic in `scene_geometry.frag`.
 pub type PrimitivePropsSlice = [PrimitiveOpBufferUnit; PRIMITIVE_PROPS_LEN];
 
+/// Encodes the evaluation `steps` of an object into packets (one per step) followed by the
+/// side-data region. This is the contents of the `primitive_ops` array of the object buffer in
+/// `scene_geometry.frag`.
+pub fn encode_primitive_ops(
+    steps: &[EvaluationStep],
+    object_transform: &ObjectTransform,
+) -> Vec<PrimitiveOpBufferUnit> {
+    // having no primitive ops would probably break something on the gpu side so lets put a NOP here...
+    let packet_count = steps.len().max(1);
+
+    let mut packets = Vec::<PrimitiveOpPacket>::with_capacity(packet_count);
+    let mut side_data = Vec::<PrimitiveOpBufferUnit>::new();
+    for step in steps {
+        let primitive_op = match *step {
+            EvaluationStep::Primitive(primitive_op) => primitive_op,
+            EvaluationStep::GroupBegin(_) => {
+                packets.push(group_begin_packet());
+                continue;
+            }
+            EvaluationStep::GroupEnd(group) => {
+                packets.push(group_end_packet(group));
+                continue;
+            }
+        };
+
+        let modifiers_offset = if primitive_op.modifiers.is_empty() {
+            0
+        } else {
+            (packet_count * PRIMITIVE_PACKET_LEN + side_data.len()) as PrimitiveOpBufferUnit
+        };
+        if !primitive_op.modifiers.is_empty() {
+            side_data.push(primitive_op.modifiers.len() as PrimitiveOpBufferUnit);
+            for modifier in &primitive_op.modifiers {
+                side_data.extend_from_slice(&modifier.encoded());
+            }
+        }
+
+        let surface_modifiers_offset = if primitive_op.surface_modifiers.is_empty() {
+            0
+        } else {
+            (packet_count * PRIMITIVE_PACKET_LEN + side_data.len()) as PrimitiveOpBufferUnit
+        };
+        if !primitive_op.surface_modifiers.is_empty() {
+            side_data.push(primitive_op.surface_modifiers.len() as PrimitiveOpBufferUnit);
+            for modifier in &primitive_op.surface_modifiers {
+                side_data.extend_from_slice(&modifier.encoded());
+            }
+        }
+
+        let primitive_side_data = primitive_op.primitive.encoded_side_data();
+        let side_data_offset = if primitive_side_data.is_empty() {
+            0
+        } else {
+            (packet_count * PRIMITIVE_PACKET_LEN + side_data.len()) as PrimitiveOpBufferUnit
+        };
+        side_data.extend(primitive_side_data);
+
+        packets.push(create_primitive_op_packet(
+            primitive_op,
+            object_transform,
+            side_data_offset,
+            modifiers_offset,
+            surface_modifiers_offset,
+        ));
+    }
+    if steps.is_empty() {
+        packets.push(nop_primitive_op_packet());
+    }
+
+    let mut encoded: Vec<PrimitiveOpBufferUnit> = packets.into_iter().flatten().collect();
+    encoded.extend(side_data);
+    encoded
+}
+
+/// `side_data_offset`, `modifiers_offset` and `surface_modifiers_offset` are the indices of the
+/// primitive's side data and the op's domain and surface modifiers in the encoded primitive ops.
+/// See [`encode_primitive_ops`].
 pub fn create_primitive_op_packet(
     primitive_op: &PrimitiveOp,
-    object_origin: Vec3,
+    object_transform: &ObjectTransform,
+    side_data_offset: PrimitiveOpBufferUnit,
+    modifiers_offset: PrimitiveOpBufferUnit,
+    surface_modifiers_offset: PrimitiveOpBufferUnit,
 ) -> PrimitiveOpPacket {
-    let encoded_op_code = primitive_op.op.op_code();
-    let encoded_transform = primitive_op.transform.gpu_encoded(object_origin);
+    let encoded_transform = primitive_op.transform.gpu_encoded(object_transform);
     let encoded_props = primitive_op.primitive.encoded_props();
-    let encoded_blend = primitive_op.blend.to_bits();
     let encoded_albedo = [
         primitive_op.albedo.x.to_bits(),
         primitive_op.albedo.y.to_bits(),
         primitive_op.albedo.z.to_bits(),
     ];
-    let encoded_specular = primitive_op.specular.to_bits();
-    [
-        encoded_transform[0],
-        encoded_transform[1],
-        encoded_transform[2],
-        encoded_transform[3],
-        encoded_transform[4],
-        encoded_transform[5],
-        encoded_transform[6],
-        encoded_transform[7],
-        encoded_transform[8],
-        encoded_transform[9],
-        encoded_transform[10],
-        encoded_transform[11],
-        encoded_props[0],
-        encoded_props[1],
-        encoded_props[2],
-        encoded_props[3],
-        encoded_props[4],
-        encoded_props[5],
-        encoded_albedo[0],
-        encoded_albedo[1],
-        encoded_albedo[2],
-        encoded_specular,
-        encoded_op_code,
-        encoded_blend,
-    ]
+
+    let mut packet = nop_primitive_op_packet();
+    packet[PACKET_OP_CODE_OFFSET] = primitive_op.op.op_code();
+    packet[PACKET_PRIMITIVE_TYPE_OFFSET] = primitive_op.primitive.type_code();
+    packet[PACKET_BLEND_OFFSET] = primitive_op.blend.to_bits();
+    packet[PACKET_TRANSFORM_OFFSET..PACKET_TRANSFORM_OFFSET + PRIMITIVE_TRANSFORM_LEN]
+        .copy_from_slice(&encoded_transform);
+    packet[PACKET_PROPS_OFFSET..PACKET_PROPS_OFFSET + PRIMITIVE_PROPS_LEN]
+        .copy_from_slice(&encoded_props);
+    packet[PACKET_ALBEDO_OFFSET..PACKET_ALBEDO_OFFSET + 3].copy_from_slice(&encoded_albedo);
+    packet[PACKET_SPECULAR_OFFSET] = primitive_op.specular.to_bits();
+    packet[PACKET_SIDE_DATA_OFFSET] = side_data_offset;
+    packet[PACKET_MODIFIERS_OFFSET] = modifiers_offset;
+    packet[PACKET_SURFACE_MODIFIERS_OFFSET] = surface_modifiers_offset;
+    packet[PACKET_OP_PARAM_OFFSET] = primitive_op.op.encoded_param();
+    packet
+}
+
+/// Marks the start of a primitive op group. The following packets up to the matching
+/// [`group_end_packet`] get combined separately.
+pub fn group_begin_packet() -> PrimitiveOpPacket {
+    let mut packet = nop_primitive_op_packet();
+    packet[PACKET_PRIMITIVE_TYPE_OFFSET] = primitive_codes::GROUP_BEGIN;
+    packet
+}
+
+/// Marks the end of a primitive op group. The result of the group gets combined with the
+/// previous results using the op of the group, like a primitive.
+pub fn group_end_packet(group: &PrimitiveOpGroup) -> PrimitiveOpPacket {
+    let mut packet = nop_primitive_op_packet();
+    packet[PACKET_OP_CODE_OFFSET] = group.op.op_code();
+    packet[PACKET_PRIMITIVE_TYPE_OFFSET] = primitive_codes::GROUP_END;
+    packet[PACKET_BLEND_OFFSET] = group.blend.to_bits();
+    packet[PACKET_OP_PARAM_OFFSET] = group.op.encoded_param();
+    packet
 }
 
 pub fn nop_primitive_op_packet() -> PrimitiveOpPacket {
-    [
-        op_codes::NOP,
-        0,
-        0,
-        0,
-        0,
-        0,
-        0,
-        0,
-        0,
-        0,
-        0,
-        0,
-        0,
-        0,
-        0,
-        0,
-        0,
-        0,
-        0,
-        0,
-        0,
-        0,
-        0,
-        0,
-    ]
+    let mut packet = [0; PRIMITIVE_PACKET_LEN];
+    packet[PACKET_OP_CODE_OFFSET] = op_codes::NOP;
+    packet
+}
+
+// ~~ Tests ~~
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn primitive_op_layout_glsl_up_to_date() {
+        let checked_in = include_str!("../shader_source/primitive_op_layout.glsl");
+        let generated = glsl_source();
+        assert!(
+            checked_in == generated,
+            "primitive_op_layout.glsl is out of date. replace its contents with:\n{}",
+            generated
+        );
+    }
+
+    #[test]
+    fn packet_fields_fit() {
+        assert_eq!(PACKET_OP_PARAM_OFFSET + 1, PRIMITIVE_PACKET_LEN);
+    }
 }
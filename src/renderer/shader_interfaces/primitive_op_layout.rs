@@ -0,0 +1,271 @@
+// Layout of the primitive op packets shared between `primitive_op_buffer.rs` and the shaders.
+//
+// This is the single definition of the layout: `shader_source/primitive_op_layout.glsl` is
+// generated from `glsl_source()` below and checked in. When the
+// `primitive_op_layout_glsl_up_to_date` test fails, copy its output into the glsl file and
+// recompile the shaders.
+
+pub type PrimitiveOpBufferUnit = u32;
+
+/// Number of 32-bit values used to store an op and its primitive.
+pub const PRIMITIVE_PACKET_LEN: usize = 30;
+
+/// Op code, see [`op_codes`].
+pub const PACKET_OP_CODE_OFFSET: usize = 0;
+/// Primitive type code selecting the signed distance function, see [`primitive_codes`].
+pub const PACKET_PRIMITIVE_TYPE_OFFSET: usize = 1;
+/// Blend distance (f32) between this primitive op and the previous ops.
+pub const PACKET_BLEND_OFFSET: usize = 2;
+/// Center (vec3), inverse rotation/scale matrix columns (mat3) and distance scale (f32).
+pub const PACKET_TRANSFORM_OFFSET: usize = 3;
+/// Each primitive has a center, 3x3 matrix and distance correction associated with it for
+/// transformations. This defines that size.
+pub const PRIMITIVE_TRANSFORM_LEN: usize = 13;
+/// Properties specific to the primitive type.
+pub const PACKET_PROPS_OFFSET: usize = PACKET_TRANSFORM_OFFSET + PRIMITIVE_TRANSFORM_LEN;
+/// Each primitive type has unique properties encoded into an array of this length.
+pub const PRIMITIVE_PROPS_LEN: usize = 6;
+/// Albedo (vec3).
+pub const PACKET_ALBEDO_OFFSET: usize = PACKET_PROPS_OFFSET + PRIMITIVE_PROPS_LEN;
+/// Specular (f32).
+pub const PACKET_SPECULAR_OFFSET: usize = PACKET_ALBEDO_OFFSET + 3;
+/// Index into the object's primitive op array where this primitive's variable-length data
+/// starts. The side-data region follows the last packet.
+pub const PACKET_SIDE_DATA_OFFSET: usize = PACKET_SPECULAR_OFFSET + 1;
+/// Index into the object's primitive op array where this op's domain modifiers start or 0 if it
+/// has none. The modifiers are stored in the side-data region as a count followed by that many
+/// [`DOMAIN_MODIFIER_LEN`] long entries.
+pub const PACKET_MODIFIERS_OFFSET: usize = PACKET_SIDE_DATA_OFFSET + 1;
+/// Index into the object's primitive op array where this op's surface modifiers start or 0 if it
+/// has none. Stored like the domain modifiers, with [`SURFACE_MODIFIER_LEN`] long entries.
+pub const PACKET_SURFACE_MODIFIERS_OFFSET: usize = PACKET_MODIFIERS_OFFSET + 1;
+/// Extra op parameter (f32) used alongside the blend distance, e.g. the number of stairs.
+pub const PACKET_OP_PARAM_OFFSET: usize = PACKET_SURFACE_MODIFIERS_OFFSET + 1;
+
+/// Number of values used to encode a domain modifier: code followed by float parameters.
+pub const DOMAIN_MODIFIER_LEN: usize = 7;
+/// Number of values used to encode a surface modifier: code followed by its parameters.
+pub const SURFACE_MODIFIER_LEN: usize = 5;
+/// Number of value noise octaves summed by fBm displacement.
+pub const FBM_OCTAVES: u32 = 4;
+/// Maximum nesting depth of primitive op groups. The shaders keep a stack of one result per
+/// open group.
+pub const MAX_GROUP_DEPTH: usize = 8;
+
+#[rustfmt::skip]
+#[allow(dead_code)]
+pub mod op_codes {
+    use super::PrimitiveOpBufferUnit;
+    pub const NOP: 		    PrimitiveOpBufferUnit = 0x00000000;
+    pub const UNION: 		PrimitiveOpBufferUnit = 0x00000001; // OR
+    pub const INTERSECTION: PrimitiveOpBufferUnit = 0x00000002; // AND
+    pub const SUBTRACTION: 	PrimitiveOpBufferUnit = 0x00000003;
+    pub const CHAMFER_UNION:        PrimitiveOpBufferUnit = 0x00000004;
+    pub const CHAMFER_SUBTRACTION:  PrimitiveOpBufferUnit = 0x00000005;
+    pub const STAIRS_UNION:         PrimitiveOpBufferUnit = 0x00000006;
+    pub const STAIRS_SUBTRACTION:   PrimitiveOpBufferUnit = 0x00000007;
+    pub const COLUMNS_UNION:        PrimitiveOpBufferUnit = 0x00000008;
+    pub const COLUMNS_SUBTRACTION:  PrimitiveOpBufferUnit = 0x00000009;
+    pub const GROOVE:               PrimitiveOpBufferUnit = 0x0000000A;
+    pub const TONGUE:               PrimitiveOpBufferUnit = 0x0000000B;
+    pub const MORPH:                PrimitiveOpBufferUnit = 0x0000000C;
+    pub const XOR:                  PrimitiveOpBufferUnit = 0x0000000D;
+    pub const INVALID:      PrimitiveOpBufferUnit = 0xFFFFFFFF;
+}
+
+/// Selects which signed distance function the shaders use to evaluate a primitive.
+#[rustfmt::skip]
+#[allow(dead_code)]
+pub mod primitive_codes {
+    use super::PrimitiveOpBufferUnit;
+    pub const UBER_PRIMITIVE: PrimitiveOpBufferUnit = 0x00000000;
+    pub const TORUS:          PrimitiveOpBufferUnit = 0x00000001;
+    pub const CAPPED_TORUS:   PrimitiveOpBufferUnit = 0x00000002;
+    pub const BOX_FRAME:      PrimitiveOpBufferUnit = 0x00000003;
+    pub const CYLINDER:       PrimitiveOpBufferUnit = 0x00000004;
+    pub const CONE:           PrimitiveOpBufferUnit = 0x00000005;
+    pub const CAPSULE:        PrimitiveOpBufferUnit = 0x00000006;
+    pub const SPHERE:         PrimitiveOpBufferUnit = 0x00000007;
+    pub const CUBE:           PrimitiveOpBufferUnit = 0x00000008;
+    pub const EXTRUSION:      PrimitiveOpBufferUnit = 0x00000009;
+    pub const REVOLUTION:     PrimitiveOpBufferUnit = 0x0000000A;
+    pub const TUBE:           PrimitiveOpBufferUnit = 0x0000000B;
+    // not primitives: mark where the packets of a primitive op group start and end
+    pub const GROUP_BEGIN:    PrimitiveOpBufferUnit = 0x000000F0;
+    pub const GROUP_END:      PrimitiveOpBufferUnit = 0x000000F1;
+}
+
+/// First value of a 2D profile in the side-data region. Selects the 2D signed distance function.
+#[rustfmt::skip]
+#[allow(dead_code)]
+pub mod profile_codes {
+    use super::PrimitiveOpBufferUnit;
+    pub const CIRCLE:       PrimitiveOpBufferUnit = 0x00000000;
+    pub const ROUNDED_RECT: PrimitiveOpBufferUnit = 0x00000001;
+    pub const POLYGON:      PrimitiveOpBufferUnit = 0x00000002;
+}
+
+/// First value of a tube's side data. Selects how the tube points are connected.
+#[rustfmt::skip]
+#[allow(dead_code)]
+pub mod tube_path_codes {
+    use super::PrimitiveOpBufferUnit;
+    pub const POLYLINE:         PrimitiveOpBufferUnit = 0x00000000;
+    pub const QUADRATIC_BEZIER: PrimitiveOpBufferUnit = 0x00000001;
+}
+
+/// First value of an encoded domain modifier. Selects how the sample position is warped.
+#[rustfmt::skip]
+#[allow(dead_code)]
+pub mod domain_modifier_codes {
+    use super::PrimitiveOpBufferUnit;
+    pub const REPEAT:   PrimitiveOpBufferUnit = 0x00000000;
+    pub const TWIST:    PrimitiveOpBufferUnit = 0x00000001;
+    pub const BEND:     PrimitiveOpBufferUnit = 0x00000002;
+    pub const ELONGATE: PrimitiveOpBufferUnit = 0x00000003;
+    pub const SYMMETRY: PrimitiveOpBufferUnit = 0x00000004;
+}
+
+/// First value of an encoded surface modifier. Selects how the distance is changed.
+#[rustfmt::skip]
+#[allow(dead_code)]
+pub mod surface_modifier_codes {
+    use super::PrimitiveOpBufferUnit;
+    pub const DISPLACE_VALUE_NOISE: PrimitiveOpBufferUnit = 0x00000000;
+    pub const DISPLACE_FBM:         PrimitiveOpBufferUnit = 0x00000001;
+    pub const DISPLACE_VORONOI:     PrimitiveOpBufferUnit = 0x00000002;
+    pub const DISPLACE_SINE:        PrimitiveOpBufferUnit = 0x00000003;
+    pub const ROUND:                PrimitiveOpBufferUnit = 0x00000004;
+    pub const SHELL:                PrimitiveOpBufferUnit = 0x00000005;
+    pub const ONION:                PrimitiveOpBufferUnit = 0x00000006;
+    pub const SURFACE_ONLY:         PrimitiveOpBufferUnit = 0x00000007;
+}
+
+/// Number of values used to encode each tube point: position (vec3) and radius (f32).
+pub const TUBE_POINT_LEN: usize = 4;
+
+/// Name and value of each constant written to `primitive_op_layout.glsl`.
+pub const GLSL_CONSTANTS: &[(&str, PrimitiveOpBufferUnit)] = &[
+    (
+        "OP_UNIT_LENGTH",
+        PRIMITIVE_PACKET_LEN as PrimitiveOpBufferUnit,
+    ),
+    (
+        "PACKET_OP_CODE",
+        PACKET_OP_CODE_OFFSET as PrimitiveOpBufferUnit,
+    ),
+    (
+        "PACKET_PRIMITIVE_TYPE",
+        PACKET_PRIMITIVE_TYPE_OFFSET as PrimitiveOpBufferUnit,
+    ),
+    ("PACKET_BLEND", PACKET_BLEND_OFFSET as PrimitiveOpBufferUnit),
+    (
+        "PACKET_TRANSFORM",
+        PACKET_TRANSFORM_OFFSET as PrimitiveOpBufferUnit,
+    ),
+    ("PACKET_PROPS", PACKET_PROPS_OFFSET as PrimitiveOpBufferUnit),
+    (
+        "PACKET_ALBEDO",
+        PACKET_ALBEDO_OFFSET as PrimitiveOpBufferUnit,
+    ),
+    (
+        "PACKET_SPECULAR",
+        PACKET_SPECULAR_OFFSET as PrimitiveOpBufferUnit,
+    ),
+    (
+        "PACKET_SIDE_DATA",
+        PACKET_SIDE_DATA_OFFSET as PrimitiveOpBufferUnit,
+    ),
+    (
+        "PACKET_MODIFIERS",
+        PACKET_MODIFIERS_OFFSET as PrimitiveOpBufferUnit,
+    ),
+    (
+        "DOMAIN_MODIFIER_LEN",
+        DOMAIN_MODIFIER_LEN as PrimitiveOpBufferUnit,
+    ),
+    (
+        "PACKET_SURFACE_MODIFIERS",
+        PACKET_SURFACE_MODIFIERS_OFFSET as PrimitiveOpBufferUnit,
+    ),
+    (
+        "SURFACE_MODIFIER_LEN",
+        SURFACE_MODIFIER_LEN as PrimitiveOpBufferUnit,
+    ),
+    ("FBM_OCTAVES", FBM_OCTAVES),
+    ("MAX_GROUP_DEPTH", MAX_GROUP_DEPTH as PrimitiveOpBufferUnit),
+    (
+        "PACKET_OP_PARAM",
+        PACKET_OP_PARAM_OFFSET as PrimitiveOpBufferUnit,
+    ),
+    ("OP_NULL", op_codes::NOP),
+    ("OP_UNION", op_codes::UNION),
+    ("OP_INTERSECTION", op_codes::INTERSECTION),
+    ("OP_SUBTRACTION", op_codes::SUBTRACTION),
+    ("OP_CHAMFER_UNION", op_codes::CHAMFER_UNION),
+    ("OP_CHAMFER_SUBTRACTION", op_codes::CHAMFER_SUBTRACTION),
+    ("OP_STAIRS_UNION", op_codes::STAIRS_UNION),
+    ("OP_STAIRS_SUBTRACTION", op_codes::STAIRS_SUBTRACTION),
+    ("OP_COLUMNS_UNION", op_codes::COLUMNS_UNION),
+    ("OP_COLUMNS_SUBTRACTION", op_codes::COLUMNS_SUBTRACTION),
+    ("OP_GROOVE", op_codes::GROOVE),
+    ("OP_TONGUE", op_codes::TONGUE),
+    ("OP_MORPH", op_codes::MORPH),
+    ("OP_XOR", op_codes::XOR),
+    ("PRIMITIVE_UBER", primitive_codes::UBER_PRIMITIVE),
+    ("PRIMITIVE_TORUS", primitive_codes::TORUS),
+    ("PRIMITIVE_CAPPED_TORUS", primitive_codes::CAPPED_TORUS),
+    ("PRIMITIVE_BOX_FRAME", primitive_codes::BOX_FRAME),
+    ("PRIMITIVE_CYLINDER", primitive_codes::CYLINDER),
+    ("PRIMITIVE_CONE", primitive_codes::CONE),
+    ("PRIMITIVE_CAPSULE", primitive_codes::CAPSULE),
+    ("PRIMITIVE_SPHERE", primitive_codes::SPHERE),
+    ("PRIMITIVE_CUBE", primitive_codes::CUBE),
+    ("PRIMITIVE_EXTRUSION", primitive_codes::EXTRUSION),
+    ("PRIMITIVE_REVOLUTION", primitive_codes::REVOLUTION),
+    ("PRIMITIVE_TUBE", primitive_codes::TUBE),
+    ("PRIMITIVE_GROUP_BEGIN", primitive_codes::GROUP_BEGIN),
+    ("PRIMITIVE_GROUP_END", primitive_codes::GROUP_END),
+    ("PROFILE_CIRCLE", profile_codes::CIRCLE),
+    ("PROFILE_ROUNDED_RECT", profile_codes::ROUNDED_RECT),
+    ("PROFILE_POLYGON", profile_codes::POLYGON),
+    ("MODIFIER_REPEAT", domain_modifier_codes::REPEAT),
+    ("MODIFIER_TWIST", domain_modifier_codes::TWIST),
+    ("MODIFIER_BEND", domain_modifier_codes::BEND),
+    ("MODIFIER_ELONGATE", domain_modifier_codes::ELONGATE),
+    ("MODIFIER_SYMMETRY", domain_modifier_codes::SYMMETRY),
+    (
+        "SURFACE_DISPLACE_VALUE_NOISE",
+        surface_modifier_codes::DISPLACE_VALUE_NOISE,
+    ),
+    ("SURFACE_DISPLACE_FBM", surface_modifier_codes::DISPLACE_FBM),
+    (
+        "SURFACE_DISPLACE_VORONOI",
+        surface_modifier_codes::DISPLACE_VORONOI,
+    ),
+    (
+        "SURFACE_DISPLACE_SINE",
+        surface_modifier_codes::DISPLACE_SINE,
+    ),
+    ("SURFACE_ROUND", surface_modifier_codes::ROUND),
+    ("SURFACE_SHELL", surface_modifier_codes::SHELL),
+    ("SURFACE_ONION", surface_modifier_codes::ONION),
+    ("SURFACE_ONLY", surface_modifier_codes::SURFACE_ONLY),
+    ("TUBE_POINT_LEN", TUBE_POINT_LEN as PrimitiveOpBufferUnit),
+    ("TUBE_PATH_POLYLINE", tube_path_codes::POLYLINE),
+    (
+        "TUBE_PATH_QUADRATIC_BEZIER",
+        tube_path_codes::QUADRATIC_BEZIER,
+    ),
+];
+
+/// Contents of `shader_source/primitive_op_layout.glsl`
+pub fn glsl_source() -> String {
+    let mut source = String::from(
+        "// Generated from src/renderer/shader_interfaces/primitive_op_layout.rs. Do not edit!\n\n",
+    );
+    for (name, value) in GLSL_CONSTANTS {
+        source.push_str(&format!("const uint {} = {}u;\n", name, value));
+    }
+    source
+}
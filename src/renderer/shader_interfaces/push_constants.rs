@@ -1,3 +1,4 @@
+use crate::engine::object::object_transform::ObjectTransform;
 use bytemuck::{Pod, Zeroable};
 
 /// Should match definitions in `gui.vert` and `gui.frag`.
@@ -25,3 +26,94 @@ pub struct GizmosPushConstant {
     pub color: [f32; 3],
     pub object_id: u32,
 }
+
+/// Should match definition in `scene_geometry.frag`. World transform of the object being drawn.
+#[repr(C)]
+#[derive(Clone, Copy, Default, Pod, Zeroable)]
+pub struct GeometryPushConstant {
+    /// Quaternion rotating world space to object space.
+    pub inverse_rotation: [f32; 4],
+    pub origin: [f32; 3],
+    pub scale: f32,
+}
+impl GeometryPushConstant {
+    pub fn new(transform: &ObjectTransform) -> Self {
+        Self {
+            inverse_rotation: transform.rotation.inverse().to_array(),
+            origin: transform.origin.to_array(),
+            scale: transform.clamped_scale(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use memoffset::offset_of;
+
+    /// Member offsets of the push constant block declared in a SPIR-V binary
+    fn push_constant_member_offsets(spirv: &[u8]) -> Vec<u32> {
+        const OP_TYPE_POINTER: u32 = 32;
+        const OP_VARIABLE: u32 = 59;
+        const OP_MEMBER_DECORATE: u32 = 72;
+        const STORAGE_CLASS_PUSH_CONSTANT: u32 = 9;
+        const DECORATION_OFFSET: u32 = 35;
+
+        let words: Vec<u32> = spirv
+            .chunks_exact(4)
+            .map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap()))
+            .collect();
+        // each instruction starts with its word count and opcode, after a 5 word header
+        let mut instructions = Vec::<&[u32]>::new();
+        let mut word_index = 5;
+        while word_index < words.len() {
+            let word_count = (words[word_index] >> 16) as usize;
+            instructions.push(&words[word_index..word_index + word_count]);
+            word_index += word_count;
+        }
+        let opcode = |instruction: &[u32]| instruction[0] & 0xFFFF;
+
+        let pointer_type_id = instructions
+            .iter()
+            .find(|instruction| {
+                opcode(instruction) == OP_VARIABLE && instruction[3] == STORAGE_CLASS_PUSH_CONSTANT
+            })
+            .map(|instruction| instruction[1])
+            .expect("no push constant block in the shader");
+        let block_type_id = instructions
+            .iter()
+            .find(|instruction| {
+                opcode(instruction) == OP_TYPE_POINTER && instruction[1] == pointer_type_id
+            })
+            .map(|instruction| instruction[3])
+            .unwrap();
+
+        let mut member_offsets: Vec<(u32, u32)> = instructions
+            .iter()
+            .filter(|instruction| {
+                opcode(instruction) == OP_MEMBER_DECORATE
+                    && instruction[1] == block_type_id
+                    && instruction[3] == DECORATION_OFFSET
+            })
+            .map(|instruction| (instruction[2], instruction[4]))
+            .collect();
+        member_offsets.sort();
+        member_offsets
+            .into_iter()
+            .map(|(_, offset)| offset)
+            .collect()
+    }
+
+    #[test]
+    fn geometry_push_constant_matches_shader() {
+        let spirv = include_bytes!("../../../assets/shader_binaries/scene_geometry.frag.spv");
+        assert_eq!(
+            push_constant_member_offsets(spirv),
+            vec![
+                offset_of!(GeometryPushConstant, inverse_rotation) as u32,
+                offset_of!(GeometryPushConstant, origin) as u32,
+                offset_of!(GeometryPushConstant, scale) as u32,
+            ]
+        );
+    }
+}
@@ -0,0 +1,61 @@
+// Hash of the shader sources that the checked in SPIR-V binaries were compiled from. `build.rs`
+// writes it to `SHADER_SOURCE_HASH_PATH` next to the binaries with the `shader-compile` feature,
+// and the `shader_binaries_up_to_date` test compares it against the current sources so that
+// shader or packet layout changes don't get committed without recompiled binaries.
+//
+// Note: this file is also included by `build.rs` so it can't depend on anything else in the crate.
+
+use std::path::Path;
+
+/// Relative to the crate root
+pub const SHADER_SOURCE_HASH_PATH: &str = "assets/shader_binaries/shader_source.hash";
+
+/// FNV-1a hash of the names and contents of the files in `shader_dir` as a hex string. Carriage
+/// returns are skipped so that line ending conversions on checkout don't change the hash.
+pub fn shader_source_hash(shader_dir: &Path) -> std::io::Result<String> {
+    let mut dir_entries = std::fs::read_dir(shader_dir)?.collect::<Result<Vec<_>, _>>()?;
+    dir_entries.sort_by_key(|dir_entry| dir_entry.file_name());
+
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for dir_entry in dir_entries {
+        if !dir_entry.file_type()?.is_file() {
+            continue;
+        }
+        let file_name = dir_entry.file_name().to_string_lossy().into_owned();
+        let contents = std::fs::read(dir_entry.path())?;
+        // zero bytes separate the name from the contents
+        let bytes = file_name
+            .as_bytes()
+            .iter()
+            .chain([0].iter())
+            .chain(contents.iter())
+            .chain([0].iter());
+        for &byte in bytes.filter(|&&byte| byte != b'\r') {
+            hash = (hash ^ byte as u64).wrapping_mul(0x100000001b3);
+        }
+    }
+    Ok(format!("{:016x}", hash))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn shader_binaries_up_to_date() {
+        let crate_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        let current_hash =
+            shader_source_hash(&crate_dir.join("src/renderer/shader_source")).unwrap();
+        let compiled_hash =
+            std::fs::read_to_string(crate_dir.join(SHADER_SOURCE_HASH_PATH)).unwrap_or_default();
+        assert!(
+            compiled_hash.trim() == current_hash,
+            "the shader sources changed since assets/shader_binaries was compiled. recompile \
+            them with the `shader-compile` feature, which also updates {}, or with \
+            compile_shaders.sh and replace its contents with: {}",
+            SHADER_SOURCE_HASH_PATH,
+            current_hash
+        );
+    }
+}
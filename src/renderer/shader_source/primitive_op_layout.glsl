@@ -0,0 +1,65 @@
+// Generated from src/renderer/shader_interfaces/primitive_op_layout.rs. Do not edit!
+
+const uint OP_UNIT_LENGTH = 30u;
+const uint PACKET_OP_CODE = 0u;
+const uint PACKET_PRIMITIVE_TYPE = 1u;
+const uint PACKET_BLEND = 2u;
+const uint PACKET_TRANSFORM = 3u;
+const uint PACKET_PROPS = 16u;
+const uint PACKET_ALBEDO = 22u;
+const uint PACKET_SPECULAR = 25u;
+const uint PACKET_SIDE_DATA = 26u;
+const uint PACKET_MODIFIERS = 27u;
+const uint DOMAIN_MODIFIER_LEN = 7u;
+const uint PACKET_SURFACE_MODIFIERS = 28u;
+const uint SURFACE_MODIFIER_LEN = 5u;
+const uint FBM_OCTAVES = 4u;
+const uint MAX_GROUP_DEPTH = 8u;
+const uint PACKET_OP_PARAM = 29u;
+const uint OP_NULL = 0u;
+const uint OP_UNION = 1u;
+const uint OP_INTERSECTION = 2u;
+const uint OP_SUBTRACTION = 3u;
+const uint OP_CHAMFER_UNION = 4u;
+const uint OP_CHAMFER_SUBTRACTION = 5u;
+const uint OP_STAIRS_UNION = 6u;
+const uint OP_STAIRS_SUBTRACTION = 7u;
+const uint OP_COLUMNS_UNION = 8u;
+const uint OP_COLUMNS_SUBTRACTION = 9u;
+const uint OP_GROOVE = 10u;
+const uint OP_TONGUE = 11u;
+const uint OP_MORPH = 12u;
+const uint OP_XOR = 13u;
+const uint PRIMITIVE_UBER = 0u;
+const uint PRIMITIVE_TORUS = 1u;
+const uint PRIMITIVE_CAPPED_TORUS = 2u;
+const uint PRIMITIVE_BOX_FRAME = 3u;
+const uint PRIMITIVE_CYLINDER = 4u;
+const uint PRIMITIVE_CONE = 5u;
+const uint PRIMITIVE_CAPSULE = 6u;
+const uint PRIMITIVE_SPHERE = 7u;
+const uint PRIMITIVE_CUBE = 8u;
+const uint PRIMITIVE_EXTRUSION = 9u;
+const uint PRIMITIVE_REVOLUTION = 10u;
+const uint PRIMITIVE_TUBE = 11u;
+const uint PRIMITIVE_GROUP_BEGIN = 240u;
+const uint PRIMITIVE_GROUP_END = 241u;
+const uint PROFILE_CIRCLE = 0u;
+const uint PROFILE_ROUNDED_RECT = 1u;
+const uint PROFILE_POLYGON = 2u;
+const uint MODIFIER_REPEAT = 0u;
+const uint MODIFIER_TWIST = 1u;
+const uint MODIFIER_BEND = 2u;
+const uint MODIFIER_ELONGATE = 3u;
+const uint MODIFIER_SYMMETRY = 4u;
+const uint SURFACE_DISPLACE_VALUE_NOISE = 0u;
+const uint SURFACE_DISPLACE_FBM = 1u;
+const uint SURFACE_DISPLACE_VORONOI = 2u;
+const uint SURFACE_DISPLACE_SINE = 3u;
+const uint SURFACE_ROUND = 4u;
+const uint SURFACE_SHELL = 5u;
+const uint SURFACE_ONION = 6u;
+const uint SURFACE_ONLY = 7u;
+const uint TUBE_POINT_LEN = 4u;
+const uint TUBE_PATH_POLYLINE = 0u;
+const uint TUBE_PATH_QUADRATIC_BEZIER = 1u;
@@ -39,6 +39,14 @@ layout (set = 1, binding = 0, std430) readonly buffer Object {
 	uint primitive_ops[];
 } object;
 
+// world transform of the object being drawn, applied here so that instances can share the
+// primitive ops buffer. see `GeometryPushConstant` in push_constants.rs
+layout (push_constant) uniform ObjectTransform {
+	vec4 inverse_rotation; // quaternion
+	vec3 origin;
+	float scale;
+} object_transform;
+
 // ~~~ Signed Distance Fields ~~~
 // https://www.shadertoy.com/view/MsVGWG
 
@@ -53,6 +61,242 @@ float sdf_uber_primitive(vec3 pos, vec4 s, vec2 r)
 	return length(ret_1) + ret_2;
 }
 
+// https://iquilezles.org/articles/distfunctions
+
+float sdf_sphere(vec3 pos, float radius)
+{
+	return length(pos) - radius;
+}
+
+float sdf_box(vec3 pos, vec3 half_dimensions)
+{
+	vec3 q = abs(pos) - half_dimensions;
+	return length(max(q, 0.)) + min(max(q.x, max(q.y, q.z)), 0.);
+}
+
+// torus lies in the xy plane
+float sdf_torus(vec3 pos, float radius_major, float radius_minor)
+{
+	vec2 q = vec2(length(pos.xy) - radius_major, pos.z);
+	return length(q) - radius_minor;
+}
+
+// `sc` is the sin/cos of half the arc angle. arc is centered on the +y axis
+float sdf_capped_torus(vec3 pos, vec2 sc, float radius_major, float radius_minor)
+{
+	pos.x = abs(pos.x);
+	float k = (sc.y * pos.x > sc.x * pos.y) ? dot(pos.xy, sc) : length(pos.xy);
+	return sqrt(dot(pos, pos) + radius_major * radius_major - 2. * radius_major * k) - radius_minor;
+}
+
+// `half_dimensions` is half the outer box size, `e` is half the edge width
+float sdf_box_frame(vec3 pos, vec3 half_dimensions, float e)
+{
+	pos = abs(pos) - half_dimensions;
+	vec3 q = abs(pos + e) - e;
+	return min(min(
+		length(max(vec3(pos.x, q.y, q.z), 0.)) + min(max(pos.x, max(q.y, q.z)), 0.),
+		length(max(vec3(q.x, pos.y, q.z), 0.)) + min(max(q.x, max(pos.y, q.z)), 0.)),
+		length(max(vec3(q.x, q.y, pos.z), 0.)) + min(max(q.x, max(q.y, pos.z)), 0.));
+}
+
+// cylinder along the z axis
+float sdf_cylinder(vec3 pos, float radius, float half_height)
+{
+	vec2 d = abs(vec2(length(pos.xy), pos.z)) - vec2(radius, half_height);
+	return min(max(d.x, d.y), 0.) + length(max(d, 0.));
+}
+
+// cone along the z axis with `radius_bottom` at -z and `radius_top` at +z
+float sdf_cone(vec3 pos, float radius_bottom, float radius_top, float half_height)
+{
+	vec2 q = vec2(length(pos.xy), pos.z);
+	vec2 k1 = vec2(radius_top, half_height);
+	vec2 k2 = vec2(radius_top - radius_bottom, 2. * half_height);
+	vec2 ca = vec2(q.x - min(q.x, (q.y < 0.) ? radius_bottom : radius_top), abs(q.y) - half_height);
+	vec2 cb = q - k1 + k2 * clamp(dot(k1 - q, k2) / dot(k2, k2), 0., 1.);
+	float s = (cb.x < 0. && ca.y < 0.) ? -1. : 1.;
+	return s * sqrt(min(dot(ca, ca), dot(cb, cb)));
+}
+
+// capsule along the z axis. `half_segment` is the distance from the center to the end sphere centers
+float sdf_capsule(vec3 pos, float radius, float half_segment)
+{
+	pos.z -= clamp(pos.z, -half_segment, half_segment);
+	return length(pos) - radius;
+}
+
+// ~~~ 2D Profiles ~~~
+// Profiles are stored in the side-data region of `object.primitive_ops` starting at `side_index`.
+// See `Profile::encoded()` in profile.rs
+
+float load_float(uint index)
+{
+	return uintBitsToFloat(object.primitive_ops[index]);
+}
+
+vec2 load_vec2(uint index)
+{
+	return vec2(load_float(index), load_float(index + 1));
+}
+
+float sdf_rounded_rect(vec2 p, vec2 half_dimensions, float corner_radius)
+{
+	vec2 q = abs(p) - half_dimensions + corner_radius;
+	return min(max(q.x, q.y), 0.) + length(max(q, 0.)) - corner_radius;
+}
+
+// https://iquilezles.org/articles/distfunctions2d
+float sdf_polygon(vec2 p, uint vertices_index, uint vertex_count)
+{
+	if (vertex_count == 0) return cam.far;
+
+	vec2 v0 = load_vec2(vertices_index);
+	float d = dot(p - v0, p - v0);
+	float s = 1.;
+	for (uint i = 0, j = vertex_count - 1; i < vertex_count; j = i, i++) {
+		vec2 vi = load_vec2(vertices_index + 2 * i);
+		vec2 vj = load_vec2(vertices_index + 2 * j);
+		vec2 e = vj - vi;
+		vec2 w = p - vi;
+		vec2 b = w - e * clamp(dot(w, e) / dot(e, e), 0., 1.);
+		d = min(d, dot(b, b));
+		bvec3 c = bvec3(p.y >= vi.y, p.y < vj.y, e.x * w.y > e.y * w.x);
+		if (all(c) || all(not(c))) s *= -1.;
+	}
+	return s * sqrt(d);
+}
+
+float sdf_profile(vec2 p, uint side_index)
+{
+	uint profile_type = object.primitive_ops[side_index];
+	switch (profile_type)
+	{
+	case PROFILE_CIRCLE: 		return length(p) - load_float(side_index + 1);
+	case PROFILE_ROUNDED_RECT: 	return sdf_rounded_rect(p, load_vec2(side_index + 1), load_float(side_index + 3));
+	case PROFILE_POLYGON: 		return sdf_polygon(p, side_index + 2, object.primitive_ops[side_index + 1]);
+	}
+	return cam.far;
+}
+
+// profile in the xy plane extruded along the z axis
+float sdf_extrusion(vec3 pos, float half_depth, uint side_index)
+{
+	vec2 w = vec2(sdf_profile(pos.xy, side_index), abs(pos.z) - half_depth);
+	return min(max(w.x, w.y), 0.) + length(max(w, 0.));
+}
+
+// profile in the xz plane (x = distance from the z axis) revolved `offset` away from the z axis.
+// sc is the sin/cos of half the revolution angle, the arc being centered on +y
+float sdf_revolution(vec3 pos, float offset, vec2 sc, uint side_index)
+{
+	pos.x = abs(pos.x);
+	if (sc.y * pos.x <= sc.x * pos.y) {
+		// within the revolved arc
+		return sdf_profile(vec2(length(pos.xy) - offset, pos.z), side_index);
+	}
+	// past the end of the arc -> distance to the flat end cap
+	float radial = dot(pos.xy, sc);
+	float to_cap_plane = abs(sc.y * pos.x - sc.x * pos.y);
+	float cap_distance = max(sdf_profile(vec2(radial - offset, pos.z), side_index), 0.);
+	return length(vec2(cap_distance, to_cap_plane));
+}
+
+// ~~~ Tubes ~~~
+
+// segment from `a` to `b` with the radius linearly interpolated from `ra` to `rb`
+float sdf_tapered_segment(vec3 pos, vec3 a, vec3 b, float ra, float rb)
+{
+	vec3 pa = pos - a;
+	vec3 ba = b - a;
+	float h = clamp(dot(pa, ba) / max(dot(ba, ba), 1e-7), 0., 1.);
+	return length(pa - ba * h) - mix(ra, rb, h);
+}
+
+// returns the parameter of the point on the quadratic bezier (a, b, c) closest to `pos`
+// https://iquilezles.org/articles/distfunctions2d
+float bezier_closest_t(vec3 pos, vec3 a, vec3 b, vec3 c)
+{
+	vec3 ab = b - a;
+	vec3 curvature = a - 2. * b + c;
+	if (dot(curvature, curvature) < 1e-8) {
+		// control point is halfway between the ends -> straight line
+		vec3 ac = c - a;
+		return clamp(dot(pos - a, ac) / max(dot(ac, ac), 1e-7), 0., 1.);
+	}
+	vec3 to_start = a - pos;
+
+	float kk = 1. / dot(curvature, curvature);
+	float kx = kk * dot(ab, curvature);
+	float ky = kk * (2. * dot(ab, ab) + dot(to_start, curvature)) / 3.;
+	float kz = kk * dot(to_start, ab);
+	float p = ky - kx * kx;
+	float q = kx * (2. * kx * kx - 3. * ky) + kz;
+	float h = q * q + 4. * p * p * p;
+	if (h >= 0.) {
+		h = sqrt(h);
+		vec2 x = (vec2(h, -h) - q) / 2.;
+		vec2 uv = sign(x) * pow(abs(x), vec2(1. / 3.));
+		return clamp(uv.x + uv.y - kx, 0., 1.);
+	}
+	float z = sqrt(-p);
+	float v = acos(clamp(q / (p * z * 2.), -1., 1.)) / 3.;
+	float m = cos(v);
+	float n = sin(v) * 1.732050808;
+	vec2 t = clamp(vec2(m + m, -n - m) * z - kx, 0., 1.);
+	// the third root can't be the closest
+	vec3 offset_x = to_start + (2. * ab + curvature * t.x) * t.x;
+	vec3 offset_y = to_start + (2. * ab + curvature * t.y) * t.y;
+	return dot(offset_x, offset_x) < dot(offset_y, offset_y) ? t.x : t.y;
+}
+
+// quadratic bezier from `a` to `c` with control point `b`. radii are interpolated with the same
+// weights as the positions
+float sdf_bezier_segment(vec3 pos, vec3 a, vec3 b, vec3 c, vec3 radii)
+{
+	float t = bezier_closest_t(pos, a, b, c);
+	vec3 weights = vec3((1. - t) * (1. - t), 2. * t * (1. - t), t * t);
+	vec3 curve_point = a * weights.x + b * weights.y + c * weights.z;
+	return length(pos - curve_point) - dot(radii, weights);
+}
+
+vec4 load_tube_point(uint side_index, uint point_index)
+{
+	uint index = side_index + 2 + point_index * TUBE_POINT_LEN;
+	return vec4(load_float(index), load_float(index + 1), load_float(index + 2), load_float(index + 3));
+}
+
+// side data contains the path code, point count then the position and radius of each point.
+// see `Tube::encoded_side_data()` in tube.rs
+float sdf_tube(vec3 pos, uint side_index)
+{
+	uint path = object.primitive_ops[side_index];
+	uint point_count = object.primitive_ops[side_index + 1];
+
+	if (point_count == 0) return cam.far;
+	if (point_count == 1) {
+		vec4 p0 = load_tube_point(side_index, 0);
+		return sdf_sphere(pos - p0.xyz, p0.w);
+	}
+
+	float d = cam.far;
+	if (path == TUBE_PATH_QUADRATIC_BEZIER) {
+		for (uint i = 0; i + 2 < point_count; i += 2) {
+			vec4 p0 = load_tube_point(side_index, i);
+			vec4 p1 = load_tube_point(side_index, i + 1);
+			vec4 p2 = load_tube_point(side_index, i + 2);
+			d = min(d, sdf_bezier_segment(pos, p0.xyz, p1.xyz, p2.xyz, vec3(p0.w, p1.w, p2.w)));
+		}
+	} else {
+		for (uint i = 0; i + 1 < point_count; i++) {
+			vec4 a = load_tube_point(side_index, i);
+			vec4 b = load_tube_point(side_index, i + 1);
+			d = min(d, sdf_tapered_segment(pos, a.xyz, b.xyz, a.w, b.w));
+		}
+	}
+	return d;
+}
+
 // ~~~ Combination Ops ~~~
 
 // Represents a signed distance field result
@@ -83,7 +327,18 @@ SdfResult op_union(SdfResult p1, SdfResult p2, float blend)
 // Results in the intersection (max) of 2 primitives
 SdfResult op_intersection(SdfResult p1, SdfResult p2, float blend)
 {
-	return p1.d > p2.d ? p1 : p2;
+	float d_delta = p2.d - p1.d;
+	if (abs(d_delta) >= blend) {
+		return p1.d > p2.d ? p1 : p2;
+	}
+	float h = 0.5 - 0.5 * d_delta / blend; // don't need to clamp between [0, 1] because of the previous if statement
+	float d = mix(p2.d, p1.d, h) + blend * h * (1. - h);
+
+	vec3 albedo = mix(p2.albedo, p1.albedo, h);
+	float specular = mix(p2.specular, p1.specular, h);
+
+	SdfResult ret = { d, ID_BLEND, albedo, specular };
+	return ret;
 }
 
 // Subtracts the volume of primitive 2 (max) from primitive 1 (max inverted)
@@ -93,12 +348,380 @@ SdfResult op_subtraction(SdfResult p1, SdfResult p2, float blend)
 	return op_intersection(p1, p2_neg, blend);
 }
 
+// Linear interpolation from primitive 1 (factor = 0) to primitive 2 (factor = 1)
+SdfResult op_morph(SdfResult p1, SdfResult p2, float factor)
+{
+	if (factor <= 0.) {
+		return p1;
+	}
+	if (factor >= 1.) {
+		return p2;
+	}
+	float d = mix(p1.d, p2.d, factor);
+
+	vec3 albedo = mix(p1.albedo, p2.albedo, factor);
+	float specular = mix(p1.specular, p2.specular, factor);
+
+	SdfResult ret = { d, ID_BLEND, albedo, specular };
+	return ret;
+}
+
+// Results in the volume inside exactly one of the 2 primitives. Each surface keeps the
+// attributes of its primitive.
+SdfResult op_xor(SdfResult p1, SdfResult p2)
+{
+	SdfResult closer = p1.d < p2.d ? p1 : p2;
+	SdfResult further = p1.d < p2.d ? p2 : p1;
+	// outside of both the closer surface wins, inside the overlap it's the further one
+	if (closer.d > -further.d) {
+		return closer;
+	}
+	further.d = -further.d;
+	return further;
+}
+
+// ~~~ Hard-Edged Combination Ops ~~~
+// http://mercury.sexy/hg_sdf/
+
+float chamfer_union(float a, float b, float r)
+{
+	return min(min(a, b), (a - r + b) * sqrt(0.5));
+}
+
+float stairs_union(float a, float b, float r, float n)
+{
+	float s = r / n;
+	float u = b - r;
+	return min(min(a, b), 0.5 * (u + a + abs(mod(u - a + s, 2. * s) - s)));
+}
+
+// rotates `p` by 45 degrees
+vec2 rotate_45(vec2 p)
+{
+	return (p + vec2(p.y, -p.x)) * sqrt(0.5);
+}
+
+// repeats `p` every `size` units centered around 0
+float repeat_1d(float p, float size)
+{
+	float half_size = size / 2.;
+	return mod(p + half_size, size) - half_size;
+}
+
+float columns_union(float a, float b, float r, float n)
+{
+	if (a >= r || b >= r) {
+		return min(a, b);
+	}
+	float column_radius = r * sqrt(2.) / ((n - 1.) * 2. + sqrt(2.));
+	vec2 p = rotate_45(vec2(a, b));
+	p.x -= sqrt(2.) / 2. * r;
+	p.x += column_radius * sqrt(2.);
+	if (mod(n, 2.) == 1.) {
+		p.y += column_radius;
+	}
+	// place a circle every column along the diagonal between the surfaces
+	p.y = repeat_1d(p.y, column_radius * 2.);
+	return min(min(min(length(p) - column_radius, p.x), a), b);
+}
+
+// subtracts `b` from `a`
+float columns_subtraction(float a, float b, float r, float n)
+{
+	a = -a;
+	if (a >= r || b >= r) {
+		return -min(a, b);
+	}
+	float column_radius = r * sqrt(2.) / ((n - 1.) * 2. + sqrt(2.));
+	vec2 p = rotate_45(vec2(a, b));
+	p.y += column_radius;
+	p.x -= sqrt(2.) / 2. * r;
+	p.x -= column_radius * sqrt(2.) / 2.;
+	if (mod(n, 2.) == 1.) {
+		p.y += column_radius;
+	}
+	p.y = repeat_1d(p.y, column_radius * 2.);
+	return -min(min(max(column_radius - length(p), p.x), a), b);
+}
+
+// chamfer, stairs and columns unions/subtractions. the edge where the primitives meet is replaced
+// by a shape `blend` units across. `steps` is the number of stairs or columns
+SdfResult op_hard_blend(uint op, SdfResult p1, SdfResult p2, float blend, float steps)
+{
+	bool subtraction = op == OP_CHAMFER_SUBTRACTION || op == OP_STAIRS_SUBTRACTION || op == OP_COLUMNS_SUBTRACTION;
+	if (blend <= 0.) {
+		return subtraction ? op_subtraction(p1, p2, 0.) : op_union(p1, p2, 0.);
+	}
+	steps = max(steps, 1.);
+
+	float a = p1.d;
+	float b = p2.d;
+	float d;
+	switch (op)
+	{
+	case OP_CHAMFER_UNION: 			d = chamfer_union(a, b, blend); break;
+	case OP_CHAMFER_SUBTRACTION: 	d = -chamfer_union(-a, b, blend); break;
+	case OP_STAIRS_UNION: 			d = stairs_union(a, b, blend, steps); break;
+	case OP_STAIRS_SUBTRACTION: 	d = -stairs_union(-a, b, blend, steps); break;
+	case OP_COLUMNS_UNION: 			d = columns_union(a, b, blend, steps); break;
+	default: 						d = columns_subtraction(a, b, blend, steps);
+	}
+
+	// the closest primitive gets precedence with the attributes blended in between
+	if (d == a) {
+		return p1;
+	}
+	if (subtraction) {
+		p2.d = -b;
+	}
+	if (d == p2.d) {
+		return p2;
+	}
+	float d_delta = subtraction ? a - p2.d : p2.d - a;
+	float h = clamp(0.5 + 0.5 * d_delta / blend, 0., 1.);
+
+	vec3 albedo = mix(p2.albedo, p1.albedo, h);
+	float specular = mix(p2.specular, p1.specular, h);
+
+	SdfResult ret = { d, ID_BLEND, albedo, specular };
+	return ret;
+}
+
+// cuts a groove `depth` deep and `width` wide either side of the surface of `p2` into `p1`.
+// the groove walls take on the attributes of `p2`
+SdfResult op_groove(SdfResult p1, SdfResult p2, float depth, float width)
+{
+	float walls = width - abs(p2.d);
+	float d = max(p1.d, min(p1.d + depth, walls));
+	SdfResult ret = d == walls ? p2 : p1;
+	ret.d = d;
+	return ret;
+}
+
+// raises a tongue `height` high and `width` wide either side of the surface of `p2` out of `p1`.
+// the sides of the tongue take on the attributes of `p2`
+SdfResult op_tongue(SdfResult p1, SdfResult p2, float height, float width)
+{
+	float sides = abs(p2.d) - width;
+	float d = min(p1.d, max(p1.d - height, sides));
+	SdfResult ret = d == sides ? p2 : p1;
+	ret.d = d;
+	return ret;
+}
+
+// ~~~ Domain Modifiers ~~~
+// See `DomainModifier::encoded()` in domain_modifier.rs
+
+// repeats space every `spacing` units, `limit` times in each direction. axes with a spacing of 0
+// aren't repeated and a negative limit repeats forever
+vec3 op_repeat(vec3 pos, vec3 spacing, vec3 limit)
+{
+	bvec3 repeat_axis = greaterThan(spacing, vec3(0.));
+	vec3 cell = mix(vec3(0.), round(pos / max(spacing, 1e-7)), repeat_axis);
+	bvec3 limited_axis = greaterThanEqual(limit, vec3(0.));
+	cell = mix(cell, clamp(cell, -limit, limit), limited_axis);
+	return pos - spacing * cell;
+}
+
+vec3 rotate_xy(vec3 pos, float angle)
+{
+	float c = cos(angle);
+	float s = sin(angle);
+	return vec3(c * pos.x - s * pos.y, s * pos.x + c * pos.y, pos.z);
+}
+
+// twisting and bending rotate around the z axis at `rate` radians per unit, stretching space
+// more further away from the axis
+float rotation_distance_scale(vec3 pos, float rate)
+{
+	float stretch = rate * length(pos.xy);
+	return 1. / sqrt(1. + stretch * stretch);
+}
+
+// warps `pos` by the domain modifiers starting at `modifiers_index` (count followed by the
+// modifiers). `distance_scale` gets multiplied by a factor to stop stretched space overshooting
+vec3 apply_domain_modifiers(vec3 pos, uint modifiers_index, inout float distance_scale)
+{
+	uint modifier_count = object.primitive_ops[modifiers_index];
+	for (uint i = 0; i < modifier_count; i++) {
+		uint modifier_index = modifiers_index + 1 + i * DOMAIN_MODIFIER_LEN;
+		uint modifier = object.primitive_ops[modifier_index];
+		vec3 a = vec3(load_float(modifier_index + 1), load_float(modifier_index + 2), load_float(modifier_index + 3));
+		vec3 b = vec3(load_float(modifier_index + 4), load_float(modifier_index + 5), load_float(modifier_index + 6));
+
+		switch (modifier)
+		{
+		case MODIFIER_REPEAT:
+			pos = op_repeat(pos, a, b);
+			break;
+		case MODIFIER_TWIST:
+			distance_scale *= rotation_distance_scale(pos, a.x);
+			pos = rotate_xy(pos, a.x * pos.z);
+			break;
+		case MODIFIER_BEND:
+			distance_scale *= rotation_distance_scale(pos, a.x);
+			pos = rotate_xy(pos, a.x * pos.x);
+			break;
+		case MODIFIER_ELONGATE:
+			pos -= clamp(pos, -a, a);
+			break;
+		case MODIFIER_SYMMETRY:
+			pos = mix(pos, abs(pos), greaterThan(a, vec3(0.5)));
+			break;
+		}
+	}
+	return pos;
+}
+
+// ~~~ Noise ~~~
+// Integer hashing so that the gpu and cpu (sdf_evaluator.rs) produce the same patterns
+
+const float TAU = 6.28318530718;
+
+// https://nullprogram.com/blog/2018/07/31/
+uint hash_u32(uint x)
+{
+	x ^= x >> 16;
+	x *= 0x7feb352du;
+	x ^= x >> 15;
+	x *= 0x846ca68bu;
+	x ^= x >> 16;
+	return x;
+}
+
+// `cell` must be a whole number vector
+uint hash_cell(vec3 cell, uint seed)
+{
+	uvec3 c = uvec3(ivec3(cell));
+	uint h = hash_u32(c.z + hash_u32(seed));
+	h = hash_u32(c.y + h);
+	return hash_u32(c.x + h);
+}
+
+// maps a hash to [0, 1)
+float hash_to_unit(uint h)
+{
+	return float(h >> 8) / 16777216.;
+}
+
+float value_noise_corner(vec3 cell, vec3 offset, uint seed)
+{
+	return hash_to_unit(hash_cell(cell + offset, seed)) * 2. - 1.;
+}
+
+// smoothly interpolated random values in [-1, 1] at whole number positions
+float value_noise(vec3 p, uint seed)
+{
+	vec3 cell = floor(p);
+	vec3 f = p - cell;
+	vec3 w = f * f * (3. - 2. * f);
+
+	float x00 = mix(value_noise_corner(cell, vec3(0., 0., 0.), seed), value_noise_corner(cell, vec3(1., 0., 0.), seed), w.x);
+	float x10 = mix(value_noise_corner(cell, vec3(0., 1., 0.), seed), value_noise_corner(cell, vec3(1., 1., 0.), seed), w.x);
+	float x01 = mix(value_noise_corner(cell, vec3(0., 0., 1.), seed), value_noise_corner(cell, vec3(1., 0., 1.), seed), w.x);
+	float x11 = mix(value_noise_corner(cell, vec3(0., 1., 1.), seed), value_noise_corner(cell, vec3(1., 1., 1.), seed), w.x);
+	return mix(mix(x00, x10, w.y), mix(x01, x11, w.y), w.z);
+}
+
+float fbm_noise(vec3 p, uint seed)
+{
+	float value = 0.;
+	float amplitude = 1.;
+	float frequency = 1.;
+	for (uint octave = 0; octave < FBM_OCTAVES; octave++) {
+		value += amplitude * value_noise(p * frequency, seed + octave);
+		amplitude *= 0.5;
+		frequency *= 2.;
+	}
+	// divide by the sum of the octave amplitudes to stay in [-1, 1]
+	return value / (2. - pow(0.5, float(FBM_OCTAVES - 1)));
+}
+
+// 1 at randomly scattered points (one per unit cell) falling to -1 a unit away from them
+float voronoi_noise(vec3 p, uint seed)
+{
+	vec3 cell = floor(p);
+	float nearest = 1.;
+	for (int z = -1; z <= 1; z++) {
+		for (int y = -1; y <= 1; y++) {
+			for (int x = -1; x <= 1; x++) {
+				vec3 neighbour = cell + vec3(x, y, z);
+				uint h = hash_cell(neighbour, seed);
+				uint h2 = hash_u32(h);
+				vec3 jitter = vec3(hash_to_unit(h), hash_to_unit(h2), hash_to_unit(hash_u32(h2)));
+				nearest = min(nearest, length(neighbour + jitter - p));
+			}
+		}
+	}
+	return 1. - 2. * nearest;
+}
+
+// product of one wave per unit along each axis with a random phase per axis
+float sine_pattern(vec3 p, uint seed)
+{
+	uint h = hash_u32(seed);
+	uint h2 = hash_u32(h);
+	vec3 phase = vec3(hash_to_unit(h), hash_to_unit(h2), hash_to_unit(hash_u32(h2))) * TAU;
+	vec3 waves = sin(p * TAU + phase);
+	return waves.x * waves.y * waves.z;
+}
+
+// ~~~ Surface Modifiers ~~~
+// See `SurfaceModifier::encoded()` in surface_modifier.rs
+
+// changes the primitive distance `dist` at `pos` by the surface modifiers starting at
+// `modifiers_index` (count followed by the modifiers). `distance_scale` gets multiplied by a
+// factor to stop the ray marcher overshooting
+float apply_surface_modifiers(float dist, vec3 pos, uint modifiers_index, inout float distance_scale)
+{
+	uint modifier_count = object.primitive_ops[modifiers_index];
+	for (uint i = 0; i < modifier_count; i++) {
+		uint modifier_index = modifiers_index + 1 + i * SURFACE_MODIFIER_LEN;
+		uint modifier = object.primitive_ops[modifier_index];
+		float a = load_float(modifier_index + 1);
+
+		if (modifier == SURFACE_ROUND) {
+			dist -= a;
+		} else if (modifier == SURFACE_SHELL) {
+			dist = abs(dist + a / 2.) - a / 2.;
+		} else if (modifier == SURFACE_ONION) {
+			uint layers = object.primitive_ops[modifier_index + 2];
+			float thickness = a;
+			for (uint layer = 0; layer < layers; layer++) {
+				dist = abs(dist) - thickness;
+				thickness /= 2.;
+			}
+		} else if (modifier == SURFACE_ONLY) {
+			dist = abs(dist);
+		} else {
+			float frequency = load_float(modifier_index + 2);
+			uint seed = object.primitive_ops[modifier_index + 3];
+			vec3 p = pos * frequency;
+
+			float pattern = 0.;
+			switch (modifier)
+			{
+			case SURFACE_DISPLACE_VALUE_NOISE: 	pattern = value_noise(p, seed); break;
+			case SURFACE_DISPLACE_FBM: 			pattern = fbm_noise(p, seed); break;
+			case SURFACE_DISPLACE_VORONOI: 		pattern = voronoi_noise(p, seed); break;
+			case SURFACE_DISPLACE_SINE: 		pattern = sine_pattern(p, seed); break;
+			}
+			dist += a * pattern;
+		}
+		distance_scale *= load_float(modifier_index + 4);
+	}
+	return dist;
+}
+
 // ~~~ Primitive-Op Processing ~~~
 
-SdfResult process_primitive(uint op_index, vec3 pos)
+// `packet_number` is the position of the packet in the buffer and `op_index` is the index of the
+// primitive op in the object which gets returned in the result.
+SdfResult process_primitive(uint packet_number, uint op_index, vec3 pos)
 {
 	// todo perf comparison: load OP_UNIT_LENGTH values at once then decode below
-	uint buffer_index = op_index * OP_UNIT_LENGTH;
+	uint packet_index = packet_number * OP_UNIT_LENGTH;
+	uint buffer_index = packet_index + PACKET_TRANSFORM;
 
 	vec3 center = vec3(
 		uintBitsToFloat(object.primitive_ops[buffer_index++]),
@@ -122,35 +745,65 @@ SdfResult process_primitive(uint op_index, vec3 pos)
 		uintBitsToFloat(object.primitive_ops[buffer_index++]),
 		uintBitsToFloat(object.primitive_ops[buffer_index++])
 	); // column 3
+	// scale correction to keep distances from overshooting
+	float distance_scale = uintBitsToFloat(object.primitive_ops[buffer_index++]);
 
+	buffer_index = packet_index + PACKET_PROPS;
 	vec4 s = vec4(
 		uintBitsToFloat(object.primitive_ops[buffer_index++]),
 		uintBitsToFloat(object.primitive_ops[buffer_index++]),
 		uintBitsToFloat(object.primitive_ops[buffer_index++]),
 		uintBitsToFloat(object.primitive_ops[buffer_index++])
 	);
-
 	vec2 r = vec2(
 		uintBitsToFloat(object.primitive_ops[buffer_index++]),
 		uintBitsToFloat(object.primitive_ops[buffer_index++])
 	);
 
+	buffer_index = packet_index + PACKET_ALBEDO;
 	vec3 albedo = vec3(
 		uintBitsToFloat(object.primitive_ops[buffer_index++]),
 		uintBitsToFloat(object.primitive_ops[buffer_index++]),
 		uintBitsToFloat(object.primitive_ops[buffer_index++])
 	);
-	float specular = uintBitsToFloat(object.primitive_ops[buffer_index++]);
+	float specular = uintBitsToFloat(object.primitive_ops[packet_index + PACKET_SPECULAR]);
+
+	uint primitive_type = object.primitive_ops[packet_index + PACKET_PRIMITIVE_TYPE];
+	uint side_index = object.primitive_ops[packet_index + PACKET_SIDE_DATA];
+	uint modifiers_index = object.primitive_ops[packet_index + PACKET_MODIFIERS];
+	uint surface_modifiers_index = object.primitive_ops[packet_index + PACKET_SURFACE_MODIFIERS];
 
 	pos = pos - center;
 	pos = pos * rotation;
+	if (modifiers_index != 0) {
+		pos = apply_domain_modifiers(pos, modifiers_index, distance_scale);
+	}
 
-	float dist = sdf_uber_primitive(pos, s, r);
+	float dist;
+	switch(primitive_type)
+	{
+	case PRIMITIVE_SPHERE: 			dist = sdf_sphere(pos, s.x); break;
+	case PRIMITIVE_CUBE: 			dist = sdf_box(pos, s.xyz); break;
+	case PRIMITIVE_TORUS: 			dist = sdf_torus(pos, s.x, s.y); break;
+	case PRIMITIVE_CAPPED_TORUS: 	dist = sdf_capped_torus(pos, s.zw, s.x, s.y); break;
+	case PRIMITIVE_BOX_FRAME: 		dist = sdf_box_frame(pos, s.xyz, s.w); break;
+	case PRIMITIVE_CYLINDER: 		dist = sdf_cylinder(pos, s.x, s.y); break;
+	case PRIMITIVE_CONE: 			dist = sdf_cone(pos, s.x, s.y, s.z); break;
+	case PRIMITIVE_CAPSULE: 		dist = sdf_capsule(pos, s.x, s.y); break;
+	case PRIMITIVE_EXTRUSION: 		dist = sdf_extrusion(pos, s.x, side_index); break;
+	case PRIMITIVE_REVOLUTION: 		dist = sdf_revolution(pos, s.x, s.yz, side_index); break;
+	case PRIMITIVE_TUBE: 			dist = sdf_tube(pos, side_index); break;
+	default: 						dist = sdf_uber_primitive(pos, s, r);
+	}
+	if (surface_modifiers_index != 0) {
+		dist = apply_surface_modifiers(dist, pos, surface_modifiers_index, distance_scale);
+	}
+	dist *= distance_scale;
 
 	return SdfResult(dist, op_index, albedo, specular);
 }
 
-SdfResult process_op(uint op, float blend, SdfResult lhs, SdfResult rhs)
+SdfResult process_op(uint op, float blend, float op_param, SdfResult lhs, SdfResult rhs)
 {
 	SdfResult res;
 	
@@ -159,6 +812,16 @@ SdfResult process_op(uint op, float blend, SdfResult lhs, SdfResult rhs)
 	case OP_UNION: 			res = op_union(lhs, rhs, blend); break;
 	case OP_INTERSECTION: 	res = op_intersection(lhs, rhs, blend); break;
 	case OP_SUBTRACTION: 	res = op_subtraction(lhs, rhs, blend); break;
+	case OP_CHAMFER_UNION:
+	case OP_CHAMFER_SUBTRACTION:
+	case OP_STAIRS_UNION:
+	case OP_STAIRS_SUBTRACTION:
+	case OP_COLUMNS_UNION:
+	case OP_COLUMNS_SUBTRACTION: res = op_hard_blend(op, lhs, rhs, blend, op_param); break;
+	case OP_GROOVE: 		res = op_groove(lhs, rhs, blend, op_param); break;
+	case OP_TONGUE: 		res = op_tongue(lhs, rhs, blend, op_param); break;
+	case OP_MORPH: 			res = op_morph(lhs, rhs, op_param); break;
+	case OP_XOR: 			res = op_xor(lhs, rhs); break;
 	default:				res = lhs; // else do nothing e.g. OP_NULL
 	}
 
@@ -167,25 +830,54 @@ SdfResult process_op(uint op, float blend, SdfResult lhs, SdfResult rhs)
 
 // ~~~ Scene Traversal ~~~
 
-// Calculates the distance to the closest primitive in the scene from `pos`
-SdfResult map(vec3 pos)
+// Rotates `v` by the quaternion `q`
+vec3 rotate_quat(vec4 q, vec3 v)
 {
-	// the closest primitve and distance to pos p
-	SdfResult closest_res = { cam.far, ID_BACKGROUND, vec3(0), 0 };
+	return v + 2. * cross(q.xyz, cross(q.xyz, v) + q.w * v);
+}
+
+// Calculates the distance to the closest primitive in the scene from `world_pos`
+SdfResult map(vec3 world_pos)
+{
+	vec3 pos = rotate_quat(object_transform.inverse_rotation, world_pos - object_transform.origin)
+		/ object_transform.scale;
+
+	SdfResult empty_res = { cam.far, ID_BACKGROUND, vec3(0), 0 };
+
+	// the closest primitive of each open group with the object result at the bottom
+	SdfResult group_stack[MAX_GROUP_DEPTH + 1];
+	group_stack[0] = empty_res;
+	uint depth = 0u;
+	// index of the primitive op in the object, skipping the group packets
+	uint op_index = 0u;
 
 	// loop through the primitive operations
-	for (uint op_index = 0; op_index < object.op_count; op_index++) {
-		
-		SdfResult primitive_res = process_primitive(op_index, pos);
+	for (uint packet_number = 0; packet_number < object.op_count; packet_number++) {
+
+		uint packet_index = packet_number * OP_UNIT_LENGTH;
+		uint primitive_type = object.primitive_ops[packet_index + PACKET_PRIMITIVE_TYPE];
+
+		SdfResult primitive_res;
+		if (primitive_type == PRIMITIVE_GROUP_BEGIN) {
+			depth = min(depth + 1u, MAX_GROUP_DEPTH);
+			group_stack[depth] = empty_res;
+			continue;
+		} else if (primitive_type == PRIMITIVE_GROUP_END) {
+			primitive_res = group_stack[depth];
+			depth = depth == 0u ? 0u : depth - 1u;
+		} else {
+			primitive_res = process_primitive(packet_number, op_index++, pos);
+		}
 
-		uint buffer_index = op_index * OP_UNIT_LENGTH + 22;
-		uint op = object.primitive_ops[buffer_index++];
-		float blend = uintBitsToFloat(object.primitive_ops[buffer_index++]);
+		uint op = object.primitive_ops[packet_index + PACKET_OP_CODE];
+		float blend = uintBitsToFloat(object.primitive_ops[packet_index + PACKET_BLEND]);
+		float op_param = uintBitsToFloat(object.primitive_ops[packet_index + PACKET_OP_PARAM]);
 
-		closest_res = process_op(op, blend, closest_res, primitive_res);
+		group_stack[depth] = process_op(op, blend, op_param, group_stack[depth], primitive_res);
 	}
 
-	return closest_res;
+	group_stack[0].d *= object_transform.scale;
+	return group_stack[0];
 }
 
 // https://iquilezles.org/articles/normalsSDF
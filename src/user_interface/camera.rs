@@ -66,7 +66,7 @@ impl Camera {
         if let LookMode::TargetObject { object_id, .. } = self.look_mode() {
             if let Some(object) = object_collection.get_object(object_id) {
                 // update camera target positi on
-                self.set_lock_on_target_object(object_id, object.origin);
+                self.set_lock_on_target_object(object_id, object.world_transform().origin);
             } else {
                 // object dropped
                 self.unset_lock_on_target();
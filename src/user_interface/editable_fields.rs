@@ -2,9 +2,25 @@ use super::{gui::EditState, gui_state::DRAG_INC};
 use crate::{
     config,
     engine::{
-        object::{object::ObjectId, operation::Operation, primitive_op::PrimitiveOp},
+        object::{
+            domain_modifier::DomainModifier,
+            operation::Operation,
+            surface_modifier::{NoisePattern, SurfaceModifier},
+        },
         primitives::{
-            cube::Cube, primitive_transform::PrimitiveTransform, sphere::Sphere,
+            box_frame::BoxFrame,
+            capped_torus::CappedTorus,
+            capsule::Capsule,
+            cone::Cone,
+            cube::Cube,
+            cylinder::Cylinder,
+            extrusion::Extrusion,
+            primitive_transform::{PrimitiveTransform, MIN_SCALE},
+            profile::Profile,
+            revolution::Revolution,
+            sphere::Sphere,
+            torus::Torus,
+            tube::{Tube, TubePath, TubePoint},
             uber_primitive::UberPrimitive,
         },
     },
@@ -18,23 +34,26 @@ use egui::{
     epaint::Hsva,
     ComboBox, DragValue,
 };
-use egui_dnd::DragableItem;
-use glam::{Vec2, Vec3, Vec4};
+use glam::{BVec3, UVec3, Vec2, Vec3, Vec4};
 
 /// Returns a new operation if a different one is selected
 pub fn op_drop_down(
     ui: &mut egui::Ui,
     original_op: Operation,
-    object_id: ObjectId,
+    id_source: impl std::fmt::Debug,
 ) -> Option<Operation> {
     let mut new_op = original_op.clone();
 
-    ComboBox::from_id_source(format!("op drop down {:?}", object_id))
+    ComboBox::from_id_source(format!("op drop down {:?}", id_source))
         .width(0_f32)
         .selected_text(original_op.name())
         .show_ui(ui, |ui_op| {
             for (op, op_name) in Operation::variants_with_names() {
-                ui_op.selectable_value(&mut new_op, op, op_name);
+                // compare variants only so that selecting the current op keeps its parameters
+                let selected = std::mem::discriminant(&new_op) == std::mem::discriminant(&op);
+                if ui_op.selectable_label(selected, op_name).clicked() && !selected {
+                    new_op = op;
+                }
             }
         });
 
@@ -60,6 +79,256 @@ pub fn cube_editor_ui(ui: &mut egui::Ui, cube: &mut Cube) -> EditState {
     editable_dimensions_ui(ui, &mut cube.dimensions)
 }
 
+pub fn torus_editor_ui(ui: &mut egui::Ui, torus: &mut Torus) -> EditState {
+    let major_edit_state = editable_length_ui(ui, "Major radius:", &mut torus.radius_major);
+    let minor_edit_state = editable_length_ui(ui, "Minor radius:", &mut torus.radius_minor);
+    major_edit_state.combine(minor_edit_state)
+}
+
+pub fn capped_torus_editor_ui(ui: &mut egui::Ui, capped_torus: &mut CappedTorus) -> EditState {
+    let major_edit_state = editable_length_ui(ui, "Major radius:", &mut capped_torus.radius_major);
+    let minor_edit_state = editable_length_ui(ui, "Minor radius:", &mut capped_torus.radius_minor);
+
+    let mut arc_edit_state = EditState::NoChange;
+    if let Some(new_arc_angle) = editable_angle_ui(ui, capped_torus.arc_angle) {
+        capped_torus.arc_angle = new_arc_angle;
+        arc_edit_state = EditState::Modified;
+    }
+
+    major_edit_state
+        .combine(minor_edit_state)
+        .combine(arc_edit_state)
+}
+
+pub fn box_frame_editor_ui(ui: &mut egui::Ui, box_frame: &mut BoxFrame) -> EditState {
+    let dimensions_edit_state = editable_dimensions_ui(ui, &mut box_frame.dimensions);
+    let thickness_edit_state = editable_length_ui(ui, "Thickness:", &mut box_frame.thickness);
+    dimensions_edit_state.combine(thickness_edit_state)
+}
+
+pub fn cylinder_editor_ui(ui: &mut egui::Ui, cylinder: &mut Cylinder) -> EditState {
+    let radius_edit_state = editable_length_ui(ui, "Radius:", &mut cylinder.radius);
+    let height_edit_state = editable_length_ui(ui, "Height:", &mut cylinder.height);
+    radius_edit_state.combine(height_edit_state)
+}
+
+pub fn cone_editor_ui(ui: &mut egui::Ui, cone: &mut Cone) -> EditState {
+    let radius_edit_state = editable_length_ui(ui, "Radius:", &mut cone.radius);
+    let height_edit_state = editable_length_ui(ui, "Height:", &mut cone.height);
+
+    let original_cap_radius = cone.cap_radius;
+    let mut has_cap = cone.cap_radius.is_some();
+    ui.checkbox(&mut has_cap, "Flat top");
+    match (has_cap, &mut cone.cap_radius) {
+        (true, None) => cone.cap_radius = Some(cone.radius / 2.),
+        (false, Some(_)) => cone.cap_radius = None,
+        _ => (),
+    }
+    if let Some(cap_radius) = &mut cone.cap_radius {
+        editable_length_ui(ui, "Top radius:", cap_radius);
+    }
+    let cap_edit_state = if original_cap_radius != cone.cap_radius {
+        EditState::Modified
+    } else {
+        EditState::NoChange
+    };
+
+    radius_edit_state
+        .combine(height_edit_state)
+        .combine(cap_edit_state)
+}
+
+pub fn capsule_editor_ui(ui: &mut egui::Ui, capsule: &mut Capsule) -> EditState {
+    let radius_edit_state = editable_length_ui(ui, "Radius:", &mut capsule.radius);
+    let height_edit_state = editable_length_ui(ui, "Height:", &mut capsule.height);
+    radius_edit_state.combine(height_edit_state)
+}
+
+pub fn extrusion_editor_ui(ui: &mut egui::Ui, extrusion: &mut Extrusion) -> EditState {
+    let profile_edit_state = profile_editor_ui(ui, &mut extrusion.profile);
+    let depth_edit_state = editable_length_ui(ui, "Depth:", &mut extrusion.depth);
+    profile_edit_state.combine(depth_edit_state)
+}
+
+pub fn revolution_editor_ui(ui: &mut egui::Ui, revolution: &mut Revolution) -> EditState {
+    let profile_edit_state = profile_editor_ui(ui, &mut revolution.profile);
+    let offset_edit_state = editable_length_ui(ui, "Offset:", &mut revolution.offset);
+
+    let mut angle_edit_state = EditState::NoChange;
+    if let Some(new_angle) = editable_angle_ui(ui, revolution.angle) {
+        revolution.angle = new_angle;
+        angle_edit_state = EditState::Modified;
+    }
+
+    profile_edit_state
+        .combine(offset_edit_state)
+        .combine(angle_edit_state)
+}
+
+pub fn tube_editor_ui(ui: &mut egui::Ui, tube: &mut Tube) -> EditState {
+    let original_tube = tube.clone();
+
+    ComboBox::from_label("Path")
+        .selected_text(tube.path.name())
+        .show_ui(ui, |ui_p| {
+            for path in TubePath::VARIANTS {
+                ui_p.selectable_value(&mut tube.path, *path, path.name());
+            }
+        });
+    if tube.path == TubePath::QuadraticBezier {
+        ui.label("Every second point is a control point");
+    }
+
+    if tube.points.is_empty() {
+        tube.points = Tube::default_points();
+    }
+    editable_tube_points_ui(ui, &mut tube.points);
+
+    if *tube != original_tube {
+        EditState::Modified
+    } else {
+        EditState::NoChange
+    }
+}
+
+/// Edit, insert and remove tube points. At least 2 points are kept.
+fn editable_tube_points_ui(ui: &mut egui::Ui, points: &mut Vec<TubePoint>) {
+    let mut remove_index: Option<usize> = None;
+    let mut insert_index: Option<usize> = None;
+    let can_remove = points.len() > 2;
+
+    for (index, point) in points.iter_mut().enumerate() {
+        ui.horizontal(|ui_h| {
+            ui_h.label(format!("{}:", index));
+            ui_h.add(DragValue::new(&mut point.position.x).speed(DRAG_INC));
+            ui_h.add(DragValue::new(&mut point.position.y).speed(DRAG_INC));
+            ui_h.add(DragValue::new(&mut point.position.z).speed(DRAG_INC));
+            ui_h.label("r:");
+            ui_h.add(
+                DragValue::new(&mut point.radius)
+                    .speed(DRAG_INC)
+                    .clamp_range(0..=config::MAX_SPHERE_RADIUS),
+            );
+            if ui_h
+                .small_button("+")
+                .on_hover_text("Insert point after")
+                .clicked()
+            {
+                insert_index = Some(index + 1);
+            }
+            if ui_h
+                .add_enabled(can_remove, egui::Button::new("-").small())
+                .on_hover_text("Remove point")
+                .clicked()
+            {
+                remove_index = Some(index);
+            }
+        });
+    }
+
+    if let Some(index) = insert_index {
+        // place the new point halfway to the next point or continue on from the last point
+        let previous = points[index - 1];
+        let new_point = match points.get(index) {
+            Some(next) => TubePoint::new(
+                (previous.position + next.position) / 2.,
+                (previous.radius + next.radius) / 2.,
+            ),
+            None => {
+                let before_previous = points[index.saturating_sub(2)];
+                let direction = previous.position - before_previous.position;
+                TubePoint::new(previous.position + direction, previous.radius)
+            }
+        };
+        points.insert(index, new_point);
+    } else if let Some(index) = remove_index {
+        points.remove(index);
+    }
+}
+
+pub fn profile_editor_ui(ui: &mut egui::Ui, profile: &mut Profile) -> EditState {
+    let original_profile = profile.clone();
+
+    ComboBox::from_label("Profile")
+        .selected_text(profile.type_name())
+        .show_ui(ui, |ui_p| {
+            for (variant, name) in Profile::variants_with_names() {
+                let is_selected =
+                    std::mem::discriminant(profile) == std::mem::discriminant(&variant);
+                if ui_p.selectable_label(is_selected, name).clicked() && !is_selected {
+                    *profile = variant;
+                }
+            }
+        });
+
+    match profile {
+        Profile::Circle { radius } => {
+            editable_length_ui(ui, "Profile radius:", radius);
+        }
+        Profile::RoundedRect {
+            dimensions,
+            corner_radius,
+        } => {
+            ui.horizontal(|ui_h| {
+                ui_h.label("Profile dimensions:");
+                ui_h.add(DragValue::new(&mut dimensions.x).speed(DRAG_INC));
+                ui_h.add(DragValue::new(&mut dimensions.y).speed(DRAG_INC));
+            });
+            editable_length_ui(ui, "Corner radius:", corner_radius);
+        }
+        Profile::Polygon { vertices } => {
+            if vertices.is_empty() {
+                *vertices = Profile::default_polygon_vertices();
+            }
+            editable_polygon_vertices_ui(ui, vertices);
+        }
+    }
+
+    if *profile != original_profile {
+        EditState::Modified
+    } else {
+        EditState::NoChange
+    }
+}
+
+/// Edit, insert and remove polygon vertices. At least 3 vertices are kept.
+fn editable_polygon_vertices_ui(ui: &mut egui::Ui, vertices: &mut Vec<Vec2>) {
+    let mut remove_index: Option<usize> = None;
+    let mut insert_index: Option<usize> = None;
+    let can_remove = vertices.len() > 3;
+
+    for (index, vertex) in vertices.iter_mut().enumerate() {
+        ui.horizontal(|ui_h| {
+            ui_h.label(format!("{}:", index));
+            ui_h.add(DragValue::new(&mut vertex.x).speed(DRAG_INC));
+            ui_h.add(DragValue::new(&mut vertex.y).speed(DRAG_INC));
+            if ui_h
+                .small_button("+")
+                .on_hover_text("Insert vertex after")
+                .clicked()
+            {
+                insert_index = Some(index + 1);
+            }
+            if ui_h
+                .add_enabled(can_remove, egui::Button::new("-").small())
+                .on_hover_text("Remove vertex")
+                .clicked()
+            {
+                remove_index = Some(index);
+            }
+        });
+    }
+
+    if let Some(index) = insert_index {
+        // place the new vertex halfway along the edge to the next vertex
+        let next = vertices[index % vertices.len()];
+        let new_vertex = (vertices[index - 1] + next) / 2.;
+        vertices.insert(index, new_vertex);
+    } else if let Some(index) = remove_index {
+        vertices.remove(index);
+    }
+}
+
 pub fn uber_primitive_editor_ui(
     ui: &mut egui::Ui,
     uber_primitive: &mut UberPrimitive,
@@ -84,6 +353,44 @@ pub fn blend_editor_ui(ui: &mut egui::Ui, blend: &mut f32) -> EditState {
     }
 }
 
+/// Parameters specific to the op e.g. number of stairs
+pub fn op_params_editor_ui(ui: &mut egui::Ui, op: &mut Operation) -> EditState {
+    let original_op = *op;
+    match op {
+        Operation::StairsUnion { steps }
+        | Operation::StairsSubtraction { steps }
+        | Operation::ColumnsUnion { steps }
+        | Operation::ColumnsSubtraction { steps } => {
+            ui.horizontal(|ui_h| {
+                ui_h.label("Steps:");
+                ui_h.add(DragValue::new(steps).clamp_range(1..=32));
+            });
+        }
+        Operation::Groove { width } | Operation::Tongue { width } => {
+            ui.horizontal(|ui_h| {
+                ui_h.label("Width:");
+                ui_h.add(
+                    DragValue::new(width)
+                        .speed(DRAG_INC / 10.)
+                        .clamp_range(0.0..=f32::MAX),
+                );
+            });
+        }
+        Operation::Morph { factor } => {
+            ui.horizontal(|ui_h| {
+                ui_h.label("Factor:");
+                ui_h.add(egui::Slider::new(factor, 0.0..=1.0));
+            });
+        }
+        _ => (),
+    }
+    if original_op != *op {
+        EditState::Modified
+    } else {
+        EditState::NoChange
+    }
+}
+
 pub fn color_specular_editor_ui(
     ui: &mut egui::Ui,
     color: &mut Vec3,
@@ -135,7 +442,309 @@ pub fn primitive_transform_editor_ui(
         edit_state = EditState::Modified;
     }
 
+    let scale_edit_state = editable_scale_ui(ui, &mut primitive_transform.scale);
+    let mirror_edit_state = editable_mirror_ui(ui, &mut primitive_transform.mirror);
+
     edit_state
+        .combine(scale_edit_state)
+        .combine(mirror_edit_state)
+}
+
+/// List of domain modifiers with buttons to reorder, remove and add modifiers
+pub fn domain_modifiers_editor_ui(
+    ui: &mut egui::Ui,
+    modifiers: &mut Vec<DomainModifier>,
+) -> EditState {
+    let original_modifiers = modifiers.clone();
+    let mut remove_index: Option<usize> = None;
+    let mut move_up_index: Option<usize> = None;
+    let modifier_count = modifiers.len();
+
+    ui.label("Domain modifiers:");
+    for (index, modifier) in modifiers.iter_mut().enumerate() {
+        ui.horizontal(|ui_h| {
+            ui_h.label(format!("{}. {}", index, modifier.name()));
+            if ui_h
+                .add_enabled(index > 0, egui::Button::new("Up").small())
+                .on_hover_text("Apply earlier")
+                .clicked()
+            {
+                move_up_index = Some(index);
+            }
+            if ui_h
+                .add_enabled(
+                    index + 1 < modifier_count,
+                    egui::Button::new("Down").small(),
+                )
+                .on_hover_text("Apply later")
+                .clicked()
+            {
+                move_up_index = Some(index + 1);
+            }
+            if ui_h.small_button("Remove").clicked() {
+                remove_index = Some(index);
+            }
+        });
+        ui.indent(format!("domain modifier {}", index), |ui_i| {
+            domain_modifier_params_ui(ui_i, modifier);
+        });
+    }
+
+    if let Some(index) = move_up_index {
+        modifiers.swap(index - 1, index);
+    } else if let Some(index) = remove_index {
+        modifiers.remove(index);
+    }
+
+    ui.menu_button("Add modifier", |ui_m| {
+        for (modifier, name) in DomainModifier::variants_with_names() {
+            if ui_m.button(name).clicked() {
+                modifiers.push(modifier);
+                ui_m.close_menu();
+            }
+        }
+    });
+
+    if *modifiers != original_modifiers {
+        EditState::Modified
+    } else {
+        EditState::NoChange
+    }
+}
+
+fn domain_modifier_params_ui(ui: &mut egui::Ui, modifier: &mut DomainModifier) {
+    match modifier {
+        DomainModifier::Repeat { spacing, limit } => {
+            ui.horizontal(|ui_h| {
+                ui_h.label("Spacing:");
+                ui_h.add(
+                    DragValue::new(&mut spacing.x)
+                        .speed(DRAG_INC)
+                        .clamp_range(0.0..=f32::MAX),
+                );
+                ui_h.add(
+                    DragValue::new(&mut spacing.y)
+                        .speed(DRAG_INC)
+                        .clamp_range(0.0..=f32::MAX),
+                );
+                ui_h.add(
+                    DragValue::new(&mut spacing.z)
+                        .speed(DRAG_INC)
+                        .clamp_range(0.0..=f32::MAX),
+                );
+            });
+            ui.horizontal(|ui_h| {
+                let mut is_limited = limit.is_some();
+                ui_h.checkbox(&mut is_limited, "Limit copies:");
+                match (is_limited, &mut *limit) {
+                    (true, None) => *limit = Some(UVec3::ONE),
+                    (false, Some(_)) => *limit = None,
+                    _ => (),
+                }
+                if let Some(limit) = limit {
+                    ui_h.add(DragValue::new(&mut limit.x));
+                    ui_h.add(DragValue::new(&mut limit.y));
+                    ui_h.add(DragValue::new(&mut limit.z));
+                }
+            });
+        }
+        DomainModifier::Twist { rate } | DomainModifier::Bend { rate } => {
+            ui.horizontal(|ui_h| {
+                ui_h.label("Rate (radians/unit):");
+                ui_h.add(DragValue::new(rate).speed(DRAG_INC));
+            });
+        }
+        DomainModifier::Elongate { amount } => {
+            ui.horizontal(|ui_h| {
+                ui_h.label("Amount:");
+                ui_h.add(
+                    DragValue::new(&mut amount.x)
+                        .speed(DRAG_INC)
+                        .clamp_range(0..=100),
+                );
+                ui_h.add(
+                    DragValue::new(&mut amount.y)
+                        .speed(DRAG_INC)
+                        .clamp_range(0..=100),
+                );
+                ui_h.add(
+                    DragValue::new(&mut amount.z)
+                        .speed(DRAG_INC)
+                        .clamp_range(0..=100),
+                );
+            });
+        }
+        DomainModifier::Symmetry { axes } => {
+            ui.horizontal(|ui_h| {
+                ui_h.label("Axes:");
+                ui_h.checkbox(&mut axes.x, "X");
+                ui_h.checkbox(&mut axes.y, "Y");
+                ui_h.checkbox(&mut axes.z, "Z");
+            });
+        }
+    }
+}
+
+/// List of surface modifiers with buttons to reorder, remove and add modifiers
+pub fn surface_modifiers_editor_ui(
+    ui: &mut egui::Ui,
+    modifiers: &mut Vec<SurfaceModifier>,
+) -> EditState {
+    let original_modifiers = modifiers.clone();
+    let mut remove_index: Option<usize> = None;
+    let mut move_up_index: Option<usize> = None;
+    let modifier_count = modifiers.len();
+
+    ui.label("Surface modifiers:");
+    for (index, modifier) in modifiers.iter_mut().enumerate() {
+        ui.horizontal(|ui_h| {
+            ui_h.label(format!("{}. {}", index, modifier.name()));
+            if ui_h
+                .add_enabled(index > 0, egui::Button::new("Up").small())
+                .on_hover_text("Apply earlier")
+                .clicked()
+            {
+                move_up_index = Some(index);
+            }
+            if ui_h
+                .add_enabled(
+                    index + 1 < modifier_count,
+                    egui::Button::new("Down").small(),
+                )
+                .on_hover_text("Apply later")
+                .clicked()
+            {
+                move_up_index = Some(index + 1);
+            }
+            if ui_h.small_button("Remove").clicked() {
+                remove_index = Some(index);
+            }
+        });
+        ui.indent(format!("surface modifier {}", index), |ui_i| {
+            surface_modifier_params_ui(ui_i, modifier, index);
+        });
+    }
+
+    if let Some(index) = move_up_index {
+        modifiers.swap(index - 1, index);
+    } else if let Some(index) = remove_index {
+        modifiers.remove(index);
+    }
+
+    ui.menu_button("Add surface modifier", |ui_m| {
+        for (modifier, name) in SurfaceModifier::variants_with_names() {
+            if ui_m.button(name).clicked() {
+                modifiers.push(modifier);
+                ui_m.close_menu();
+            }
+        }
+    });
+
+    if *modifiers != original_modifiers {
+        EditState::Modified
+    } else {
+        EditState::NoChange
+    }
+}
+
+fn surface_modifier_params_ui(ui: &mut egui::Ui, modifier: &mut SurfaceModifier, index: usize) {
+    match modifier {
+        SurfaceModifier::Displace {
+            pattern,
+            amplitude,
+            frequency,
+            seed,
+        } => {
+            ComboBox::from_id_source(format!("noise pattern {}", index))
+                .selected_text(pattern.name())
+                .show_ui(ui, |ui_p| {
+                    for variant in NoisePattern::VARIANTS {
+                        ui_p.selectable_value(pattern, *variant, variant.name());
+                    }
+                });
+            ui.horizontal(|ui_h| {
+                ui_h.label("Amplitude:");
+                ui_h.add(DragValue::new(amplitude).speed(DRAG_INC / 10.));
+                ui_h.label("Frequency:");
+                ui_h.add(
+                    DragValue::new(frequency)
+                        .speed(DRAG_INC)
+                        .clamp_range(0..=100),
+                );
+                ui_h.label("Seed:");
+                ui_h.add(DragValue::new(seed));
+            });
+        }
+        SurfaceModifier::Round { radius } => {
+            ui.horizontal(|ui_h| {
+                ui_h.label("Radius:");
+                ui_h.add(DragValue::new(radius).speed(DRAG_INC / 10.));
+            });
+        }
+        SurfaceModifier::Shell { thickness } => {
+            ui.horizontal(|ui_h| {
+                ui_h.label("Thickness:");
+                ui_h.add(
+                    DragValue::new(thickness)
+                        .speed(DRAG_INC / 10.)
+                        .clamp_range(0.0..=f32::MAX),
+                );
+            });
+        }
+        SurfaceModifier::Onion { thickness, layers } => {
+            ui.horizontal(|ui_h| {
+                ui_h.label("Thickness:");
+                ui_h.add(
+                    DragValue::new(thickness)
+                        .speed(DRAG_INC / 10.)
+                        .clamp_range(0.0..=f32::MAX),
+                );
+                ui_h.label("Layers:");
+                ui_h.add(DragValue::new(layers).clamp_range(1..=8));
+            });
+        }
+        SurfaceModifier::SurfaceOnly => (),
+    }
+}
+
+pub fn editable_scale_ui(ui: &mut egui::Ui, scale: &mut Vec3) -> EditState {
+    let mut something_changed: bool = false;
+
+    ui.horizontal(|ui| {
+        ui.label("Scale:");
+        for value in [&mut scale.x, &mut scale.y, &mut scale.z] {
+            something_changed |= ui
+                .add(
+                    DragValue::new(value)
+                        .speed(DRAG_INC)
+                        .clamp_range(MIN_SCALE..=f32::MAX),
+                )
+                .changed();
+        }
+    });
+
+    if something_changed {
+        EditState::Modified
+    } else {
+        EditState::NoChange
+    }
+}
+
+pub fn editable_mirror_ui(ui: &mut egui::Ui, mirror: &mut BVec3) -> EditState {
+    let mut something_changed: bool = false;
+
+    ui.horizontal(|ui| {
+        ui.label("Mirror:");
+        something_changed |= ui.checkbox(&mut mirror.x, "X").changed();
+        something_changed |= ui.checkbox(&mut mirror.y, "Y").changed();
+        something_changed |= ui.checkbox(&mut mirror.z, "Z").changed();
+    });
+
+    if something_changed {
+        EditState::Modified
+    } else {
+        EditState::NoChange
+    }
 }
 
 /// Returns `Some` new center if the value was modified by the gui
@@ -248,6 +857,26 @@ pub fn editable_radius_ui(ui: &mut egui::Ui, original_radius: f32) -> Option<f32
     }
 }
 
+/// Drag value for a non-negative length e.g. a radius or thickness
+pub fn editable_length_ui(ui: &mut egui::Ui, label: &str, length: &mut f32) -> EditState {
+    let original_length = *length;
+
+    ui.horizontal(|ui| {
+        ui.label(label);
+        ui.add(
+            DragValue::new(length)
+                .speed(DRAG_INC)
+                .clamp_range(0..=config::MAX_SPHERE_RADIUS),
+        );
+    });
+
+    if *length != original_length {
+        EditState::Modified
+    } else {
+        EditState::NoChange
+    }
+}
+
 pub fn editable_dimensions_ui(ui: &mut egui::Ui, dimensions: &mut Vec3) -> EditState {
     let mut something_changed: bool = false;
 
@@ -310,9 +939,3 @@ pub fn editable_uber_parameters_ui(
         EditState::NoChange
     }
 }
-
-impl DragableItem for PrimitiveOp {
-    fn drag_id(&self) -> egui::Id {
-        egui::Id::new(format!("p-op-drag{}", self.id()))
-    }
-}
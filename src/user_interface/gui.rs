@@ -9,11 +9,8 @@ use super::{
 use crate::{
     engine::{
         commands::{Command, CommandWithSource},
-        object::{
-            object::ObjectId,
-            object_collection::ObjectCollection,
-            primitive_op::{PrimitiveOp, PrimitiveOpId},
-        },
+        object::{object_collection::ObjectCollection, primitive_op::PrimitiveOp},
+        selection::Selection,
     },
     renderer::config_renderer::RenderOptions,
 };
@@ -28,6 +25,9 @@ mod bottom_panel;
 mod camera_control;
 mod command_palette;
 mod debug_options;
+mod group_transform_window;
+mod layer_panel;
+mod materials_window;
 mod object_editor;
 mod object_list;
 
@@ -116,11 +116,6 @@ impl Gui {
         self.egui_context.set_pixels_per_point(scale_factor);
     }
 
-    /// Call this when the selected object is changed
-    pub fn selected_object_changed(&mut self) {
-        self.gui_state.primitive_op_list_drag = Default::default();
-    }
-
     /// Call this when a primitive op is selected
     pub fn primitive_op_selected(&mut self, selected_primitive_op: &PrimitiveOp) {
         self.gui_state
@@ -132,8 +127,7 @@ impl Gui {
         object_collection: &ObjectCollection,
         window: &Window,
         camera: Camera,
-        selected_object_id: Option<ObjectId>,
-        selected_primitive_op_id: Option<PrimitiveOpId>,
+        selection: &Selection,
         render_options: RenderOptions,
     ) -> anyhow::Result<Vec<CommandWithSource>> {
         let mut commands = Vec::<Command>::new();
@@ -147,17 +141,27 @@ impl Gui {
         self.draw_bottom_panel();
 
         if self.sub_window_states.object_list {
-            let mut new_commands =
-                self.draw_object_list_window(object_collection, selected_object_id);
+            let mut new_commands = self.draw_object_list_window(object_collection, selection);
             commands.append(&mut new_commands);
         }
 
         if self.sub_window_states.object_editor {
-            let mut new_commands = self.draw_object_editor_window(
-                object_collection,
-                selected_object_id,
-                selected_primitive_op_id,
-            );
+            let mut new_commands = self.draw_object_editor_window(object_collection, selection);
+            commands.append(&mut new_commands);
+        }
+
+        if self.sub_window_states.layer_panel {
+            let mut new_commands = self.draw_layer_panel_window(object_collection, selection);
+            commands.append(&mut new_commands);
+        }
+
+        if self.sub_window_states.materials {
+            let mut new_commands = self.draw_materials_window(object_collection);
+            commands.append(&mut new_commands);
+        }
+
+        if self.sub_window_states.group_transform {
+            let mut new_commands = self.draw_group_transform_window(selection);
             commands.append(&mut new_commands);
         }
 
@@ -226,6 +230,14 @@ impl Gui {
         self.egui_context.set_visuals(theme);
     }
 
+    pub fn clipboard_text(&mut self) -> Option<String> {
+        self.winit_state.clipboard_text()
+    }
+
+    pub fn set_clipboard_text(&mut self, text: String) {
+        self.winit_state.set_clipboard_text(text);
+    }
+
     pub fn set_command_palette_visability(&mut self, is_open: bool) {
         self.sub_window_states.command_palette = is_open;
     }
@@ -22,6 +22,9 @@ fn bottom_panel_layout(ui: &mut Ui, window_states: &mut SubWindowStates) {
         // window toggles
         ui.toggle_value(&mut window_states.object_list, "Object List");
         ui.toggle_value(&mut window_states.object_editor, "Object Editor");
+        ui.toggle_value(&mut window_states.layer_panel, "Layers");
+        ui.toggle_value(&mut window_states.materials, "Materials");
+        ui.toggle_value(&mut window_states.group_transform, "Transform Selection");
         ui.toggle_value(&mut window_states.camera_control, "Camera Control");
 
         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
@@ -13,7 +13,7 @@ struct CommandPaletteEntry {
     command: Command,
 }
 
-const AVAILABLE_PALETTE_COMMANDS: [CommandPaletteEntry; 4] = [
+const AVAILABLE_PALETTE_COMMANDS: [CommandPaletteEntry; 10] = [
     CommandPaletteEntry {
         name: "Save Camera State",
         command: Command::SaveStateCamera,
@@ -30,6 +30,30 @@ const AVAILABLE_PALETTE_COMMANDS: [CommandPaletteEntry; 4] = [
         name: "Load Objects",
         command: Command::LoadObjects,
     },
+    CommandPaletteEntry {
+        name: "Copy",
+        command: Command::CopySelected(),
+    },
+    CommandPaletteEntry {
+        name: "Paste",
+        command: Command::Paste(),
+    },
+    CommandPaletteEntry {
+        name: "Duplicate",
+        command: Command::DuplicateSelected(),
+    },
+    CommandPaletteEntry {
+        name: "Delete",
+        command: Command::RemoveSelected(),
+    },
+    CommandPaletteEntry {
+        name: "Select All",
+        command: Command::SelectAll(),
+    },
+    CommandPaletteEntry {
+        name: "Invert Selection",
+        command: Command::InvertSelection(),
+    },
 ];
 
 // ~~ Drawing fns ~~
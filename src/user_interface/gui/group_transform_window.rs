@@ -0,0 +1,117 @@
+use super::Gui;
+use crate::{
+    engine::{
+        commands::Command,
+        group_transform::{GroupTransform, Pivot},
+        primitives::primitive_transform::MIN_SCALE,
+        selection::Selection,
+    },
+    user_interface::gui_state::{GuiState, DRAG_INC},
+};
+use egui::{DragValue, RichText};
+use glam::{EulerRot, Quat, Vec3};
+#[allow(unused_imports)]
+use log::{debug, error, info, trace, warn};
+use std::f32::consts::PI;
+
+impl Gui {
+    pub(super) fn draw_group_transform_window(&mut self, selection: &Selection) -> Vec<Command> {
+        let mut commands = Vec::<Command>::new();
+
+        let add_contents = |ui: &mut egui::Ui| {
+            commands = layout_group_transform(ui, &mut self.gui_state, selection);
+        };
+        egui::Window::new("Transform Selection")
+            .open(&mut self.sub_window_states.group_transform)
+            .resizable(true)
+            .vscroll(true)
+            .show(&self.egui_context, add_contents);
+
+        commands
+    }
+}
+
+fn layout_group_transform(
+    ui: &mut egui::Ui,
+    gui_state: &mut GuiState,
+    selection: &Selection,
+) -> Vec<Command> {
+    let mut commands = Vec::<Command>::new();
+
+    let primitive_op_count = selection.primitive_ops().len();
+    if selection.is_empty() {
+        ui.label(RichText::new("Nothing selected...").italics());
+    } else if primitive_op_count > 0 {
+        ui.label(format!("{} primitive ops", primitive_op_count));
+    } else {
+        ui.label(format!("{} objects", selection.objects().len()));
+    }
+
+    let group_transform = &mut gui_state.group_transform_edit;
+
+    let pivot = &mut group_transform.pivot;
+    ui.horizontal(|ui_h| {
+        ui_h.label("Pivot:");
+        ui_h.radio_value(pivot, Pivot::BoundsCenter, "Bounds center");
+        ui_h.radio_value(pivot, Pivot::ObjectOrigin, "Object origin");
+        let is_point = matches!(pivot, Pivot::Point(_));
+        if ui_h.radio(is_point, "Point").clicked() && !is_point {
+            *pivot = Pivot::Point(Vec3::ZERO);
+        }
+    });
+    if let Pivot::Point(point) = pivot {
+        ui.horizontal(|ui_h| {
+            ui_h.label("Pivot point:");
+            ui_h.add(DragValue::new(&mut point.x).speed(DRAG_INC));
+            ui_h.add(DragValue::new(&mut point.y).speed(DRAG_INC));
+            ui_h.add(DragValue::new(&mut point.z).speed(DRAG_INC));
+        });
+    }
+
+    let translation = &mut group_transform.translation;
+    ui.horizontal(|ui_h| {
+        ui_h.label("Translation:");
+        ui_h.add(DragValue::new(&mut translation.x).speed(DRAG_INC));
+        ui_h.add(DragValue::new(&mut translation.y).speed(DRAG_INC));
+        ui_h.add(DragValue::new(&mut translation.z).speed(DRAG_INC));
+    });
+
+    // rotation as euler angles in degrees
+    let angles = &mut gui_state.group_rotation_edit;
+    ui.horizontal(|ui_h| {
+        ui_h.label("Rotation:");
+        ui_h.add(DragValue::new(&mut angles.x).suffix("°"));
+        ui_h.add(DragValue::new(&mut angles.y).suffix("°"));
+        ui_h.add(DragValue::new(&mut angles.z).suffix("°"));
+    });
+
+    ui.horizontal(|ui_h| {
+        ui_h.label("Scale:");
+        ui_h.add(
+            DragValue::new(&mut group_transform.scale)
+                .speed(DRAG_INC)
+                .clamp_range(MIN_SCALE..=f32::MAX),
+        );
+    });
+
+    ui.horizontal(|ui_h| {
+        let apply_button = ui_h.add_enabled(!selection.is_empty(), egui::Button::new("Apply"));
+        if apply_button.clicked() {
+            let radians = *angles * (PI / 180.);
+            commands.push(Command::TransformSelected(GroupTransform {
+                rotation: Quat::from_euler(EulerRot::XYZ, radians.x, radians.y, radians.z),
+                ..*group_transform
+            }));
+        }
+        if ui_h.button("Reset").clicked() {
+            // keep the pivot so that several transforms can be applied around it
+            *group_transform = GroupTransform {
+                pivot: group_transform.pivot,
+                ..GroupTransform::IDENTITY
+            };
+            *angles = Vec3::ZERO;
+        }
+    });
+
+    commands
+}
@@ -0,0 +1,118 @@
+use super::Gui;
+use crate::engine::{
+    commands::Command,
+    object::{
+        layer::{Layer, LayerId},
+        object_collection::ObjectCollection,
+    },
+    selection::Selection,
+};
+#[allow(unused_imports)]
+use log::{debug, error, info, trace, warn};
+
+impl Gui {
+    pub(super) fn draw_layer_panel_window(
+        &mut self,
+        object_collection: &ObjectCollection,
+        selection: &Selection,
+    ) -> Vec<Command> {
+        let mut commands = Vec::<Command>::new();
+
+        let add_contents = |ui: &mut egui::Ui| {
+            commands = layout_layer_panel(ui, object_collection, selection);
+        };
+        egui::Window::new("Layers")
+            .open(&mut self.sub_window_states.layer_panel)
+            .resizable(true)
+            .vscroll(true)
+            .show(&self.egui_context, add_contents);
+
+        commands
+    }
+}
+
+fn layout_layer_panel(
+    ui: &mut egui::Ui,
+    object_collection: &ObjectCollection,
+    selection: &Selection,
+) -> Vec<Command> {
+    let mut commands = Vec::<Command>::new();
+
+    if ui.button("Add layer").clicked() {
+        commands.push(Command::CreateLayer());
+    }
+
+    let no_layer_count = object_collection.objects_in_layer(None).len();
+    ui.label(format!("No layer: {} objects", no_layer_count));
+
+    for (&layer_id, layer) in object_collection.layers().iter() {
+        ui.separator();
+        layer_item(
+            ui,
+            &mut commands,
+            object_collection,
+            layer_id,
+            layer,
+            selection,
+        );
+    }
+
+    commands
+}
+
+fn layer_item(
+    ui: &mut egui::Ui,
+    commands: &mut Vec<Command>,
+    object_collection: &ObjectCollection,
+    layer_id: LayerId,
+    layer: &Layer,
+    selection: &Selection,
+) {
+    ui.horizontal(|ui_h| {
+        if ui_h
+            .selectable_label(layer.visible, "👁")
+            .on_hover_text("Visible")
+            .clicked()
+        {
+            commands.push(Command::SetLayerVisible {
+                layer_id,
+                visible: !layer.visible,
+            });
+        }
+
+        if ui_h
+            .selectable_label(layer.selectable, "🖱")
+            .on_hover_text("Selectable in the viewport")
+            .clicked()
+        {
+            commands.push(Command::SetLayerSelectable {
+                layer_id,
+                selectable: !layer.selectable,
+            });
+        }
+
+        let mut new_name = layer.name.clone();
+        ui_h.text_edit_singleline(&mut new_name);
+        if new_name != layer.name {
+            commands.push(Command::SetLayerName { layer_id, new_name });
+        }
+
+        let object_count = object_collection.objects_in_layer(Some(layer_id)).len();
+        ui_h.label(format!("{} objects", object_count));
+    });
+
+    ui.horizontal(|ui_h| {
+        if !selection.is_empty() && ui_h.button("Move selected here").clicked() {
+            for &object_id in selection.objects() {
+                commands.push(Command::SetObjectLayer {
+                    object_id,
+                    layer_id: Some(layer_id),
+                });
+            }
+        }
+
+        if ui_h.button("Delete").clicked() {
+            commands.push(Command::RemoveLayer(layer_id));
+        }
+    });
+}
@@ -0,0 +1,90 @@
+use super::Gui;
+use crate::{
+    engine::{
+        commands::Command,
+        object::{
+            material::{Material, MaterialId},
+            object_collection::ObjectCollection,
+        },
+    },
+    user_interface::{editable_fields::color_specular_editor_ui, gui::EditState},
+};
+#[allow(unused_imports)]
+use log::{debug, error, info, trace, warn};
+
+impl Gui {
+    pub(super) fn draw_materials_window(
+        &mut self,
+        object_collection: &ObjectCollection,
+    ) -> Vec<Command> {
+        let mut commands = Vec::<Command>::new();
+
+        let add_contents = |ui: &mut egui::Ui| {
+            commands = layout_materials_window(ui, object_collection);
+        };
+        egui::Window::new("Materials")
+            .open(&mut self.sub_window_states.materials)
+            .resizable(true)
+            .vscroll(true)
+            .show(&self.egui_context, add_contents);
+
+        commands
+    }
+}
+
+fn layout_materials_window(
+    ui: &mut egui::Ui,
+    object_collection: &ObjectCollection,
+) -> Vec<Command> {
+    let mut commands = Vec::<Command>::new();
+
+    if ui.button("Add material").clicked() {
+        commands.push(Command::CreateMaterial());
+    }
+
+    for (&material_id, material) in object_collection.materials().iter() {
+        ui.separator();
+        ui.push_id(material_id, |ui| {
+            material_item(ui, &mut commands, object_collection, material_id, material);
+        });
+    }
+
+    commands
+}
+
+fn material_item(
+    ui: &mut egui::Ui,
+    commands: &mut Vec<Command>,
+    object_collection: &ObjectCollection,
+    material_id: MaterialId,
+    material: &Material,
+) {
+    let mut new_material = material.clone();
+    let mut edit_state = EditState::NoChange;
+
+    ui.horizontal(|ui_h| {
+        ui_h.text_edit_singleline(&mut new_material.name);
+
+        let object_count = object_collection.objects_using_material(material_id).len();
+        ui_h.label(format!("{} objects", object_count));
+    });
+    if new_material.name != material.name {
+        edit_state = EditState::Modified;
+    }
+
+    let color_specular_edit_state =
+        color_specular_editor_ui(ui, &mut new_material.albedo, &mut new_material.specular);
+    edit_state = edit_state.combine(color_specular_edit_state);
+
+    if ui.button("Delete").clicked() {
+        commands.push(Command::RemoveMaterial(material_id));
+        return;
+    }
+
+    if edit_state == EditState::Modified {
+        commands.push(Command::SetMaterial {
+            material_id,
+            new_material,
+        });
+    }
+}
@@ -3,46 +3,48 @@ use crate::{
     engine::{
         commands::{Command, TargetPrimitiveOp, ValidationCommand},
         object::{
+            layer::LayerId,
+            material::{Material, MaterialId},
             object::{Object, ObjectId},
             object_collection::ObjectCollection,
             primitive_op::{PrimitiveOp, PrimitiveOpId},
+            primitive_op_group::{group_ancestors, PrimitiveOpGroup, PrimitiveOpGroupId},
         },
-        primitives::primitive::{EncodablePrimitive, Primitive},
+        primitives::{
+            primitive::{EncodablePrimitive, Primitive},
+            primitive_transform::MIN_SCALE,
+        },
+        selection::Selection,
     },
     user_interface::{
         config_ui,
         editable_fields::{
-            blend_editor_ui, color_specular_editor_ui, cube_editor_ui, op_drop_down,
-            primitive_transform_editor_ui, sphere_editor_ui, uber_primitive_editor_ui,
+            blend_editor_ui, box_frame_editor_ui, capped_torus_editor_ui, capsule_editor_ui,
+            color_specular_editor_ui, cone_editor_ui, cube_editor_ui, cylinder_editor_ui,
+            domain_modifiers_editor_ui, extrusion_editor_ui, op_drop_down, op_params_editor_ui,
+            primitive_transform_editor_ui, revolution_editor_ui, sphere_editor_ui,
+            surface_modifiers_editor_ui, torus_editor_ui, tube_editor_ui, uber_primitive_editor_ui,
         },
         gui::EditState,
         gui_state::{GuiState, DRAG_INC},
     },
 };
 use egui::{ComboBox, DragValue, RichText, TextStyle};
-use egui_dnd::DragDropResponse;
+use glam::{EulerRot, Quat, Vec3};
 #[allow(unused_imports)]
 use log::{debug, error, info, trace, warn};
-use std::mem::discriminant;
+use std::{collections::BTreeMap, f32::consts::PI, mem::discriminant};
 
 impl Gui {
     pub(super) fn draw_object_editor_window(
         &mut self,
         object_collection: &ObjectCollection,
-        selected_object_id: Option<ObjectId>,
-        selected_primitive_op_id: Option<PrimitiveOpId>,
+        selection: &Selection,
     ) -> Vec<Command> {
         let mut commands = Vec::<Command>::new();
 
         let add_contents = |ui: &mut egui::Ui| {
-            commands = layout_object_editor(
-                ui,
-                &mut self.gui_state,
-                &self.egui_context,
-                object_collection,
-                selected_object_id,
-                selected_primitive_op_id,
-            );
+            commands = layout_object_editor(ui, &mut self.gui_state, object_collection, selection);
         };
         egui::Window::new("Object Editor")
             .open(&mut self.sub_window_states.object_editor)
@@ -58,12 +60,14 @@ impl Gui {
 fn layout_object_editor(
     ui: &mut egui::Ui,
     gui_state: &mut GuiState,
-    context: &egui::Context,
     object_collection: &ObjectCollection,
-    selected_object_id: Option<ObjectId>,
-    selected_primitive_op_id: Option<PrimitiveOpId>,
+    selection: &Selection,
 ) -> Vec<Command> {
     let mut commands = Vec::<Command>::new();
+    let selected_object_id = selection.active_object_id();
+    let selected_primitive_op_id = selection.active_primitive_op_id();
+
+    selection_editor(ui, &mut commands, selection);
 
     // selected object name
     let (selected_object, some_selected_object_id) = match label_and_get_selected_object(
@@ -76,30 +80,84 @@ fn layout_object_editor(
         None => return commands,
     };
 
-    object_properties_editor(ui, &mut commands, selected_object, some_selected_object_id);
+    if selected_object.locked {
+        ui.label(RichText::new("Locked").italics());
+    }
+    ui.add_enabled_ui(!selected_object.locked, |ui| {
+        object_properties_editor(ui, &mut commands, selected_object, some_selected_object_id);
+        layer_and_tags_editor(
+            ui,
+            &mut commands,
+            gui_state,
+            object_collection,
+            selected_object,
+            some_selected_object_id,
+        );
+    });
 
-    primitive_op_editor(
+    // instances are edited via the primitive ops of their definition
+    let selected_object = match instance_editor(
         ui,
         &mut commands,
-        gui_state,
+        object_collection,
         selected_object,
         some_selected_object_id,
-        selected_primitive_op_id,
-    );
+    ) {
+        Some(value) => value,
+        None => return commands,
+    };
 
-    primitive_op_list(
-        ui,
-        &mut commands,
-        gui_state,
-        context,
-        selected_object,
-        some_selected_object_id,
-        selected_primitive_op_id,
-    );
+    ui.add_enabled_ui(!selected_object.locked, |ui| {
+        primitive_op_editor(
+            ui,
+            &mut commands,
+            gui_state,
+            object_collection.materials(),
+            selected_object,
+            some_selected_object_id,
+            selected_primitive_op_id,
+        );
+
+        primitive_op_list(
+            ui,
+            &mut commands,
+            selection,
+            selected_object,
+            some_selected_object_id,
+            selected_primitive_op_id,
+        );
+    });
 
     commands
 }
 
+/// Shown when more than one object or primitive op is selected. The object and primitive op
+/// editors below edit the active ones, the "Transform Selection" window moves them together.
+fn selection_editor(ui: &mut egui::Ui, commands: &mut Vec<Command>, selection: &Selection) {
+    let object_count = selection.objects().len();
+    let primitive_op_count = selection.primitive_ops().len();
+    if object_count <= 1 && primitive_op_count <= 1 {
+        return;
+    }
+
+    if primitive_op_count > 0 {
+        ui.label(format!("{} primitive ops selected", primitive_op_count));
+    } else {
+        ui.label(format!("{} objects selected", object_count));
+    }
+
+    ui.horizontal(|ui_h| {
+        if ui_h.button("Delete selected").clicked() {
+            commands.push(Command::RemoveSelected());
+        }
+        if ui_h.button("Duplicate selected").clicked() {
+            commands.push(Command::DuplicateSelected());
+        }
+    });
+
+    ui.separator();
+}
+
 fn label_and_get_selected_object<'a>(
     ui: &mut egui::Ui,
     commands: &mut Vec<Command>,
@@ -121,7 +179,7 @@ fn label_and_get_selected_object<'a>(
         None => {
             // invalid object id
             debug!("selected object {} dropped", some_selected_object_id);
-            commands.push(ValidationCommand::SelectedObject().into());
+            commands.push(ValidationCommand::Selection().into());
 
             ui.label(no_object_text);
             return None;
@@ -170,12 +228,140 @@ fn object_properties_editor(
             origin: new_origin,
         });
     }
+
+    // rotation as euler angles in degrees
+    let (x, y, z) = object.rotation.to_euler(EulerRot::XYZ);
+    let original_angles = Vec3::new(x, y, z) * (180. / PI);
+    let mut new_angles = original_angles;
+
+    ui.horizontal(|ui| {
+        ui.label("Rotation:");
+        ui.add(DragValue::new(&mut new_angles.x).suffix("°"));
+        ui.add(DragValue::new(&mut new_angles.y).suffix("°"));
+        ui.add(DragValue::new(&mut new_angles.z).suffix("°"));
+    });
+
+    if original_angles != new_angles {
+        let radians = new_angles * (PI / 180.);
+        commands.push(Command::SetObjectRotation {
+            object_id,
+            rotation: Quat::from_euler(EulerRot::XYZ, radians.x, radians.y, radians.z),
+        });
+    }
+
+    let mut new_scale = object.scale;
+    ui.horizontal(|ui| {
+        ui.label("Scale:");
+        ui.add(
+            DragValue::new(&mut new_scale)
+                .speed(DRAG_INC)
+                .clamp_range(MIN_SCALE..=f32::MAX),
+        );
+    });
+
+    if object.scale != new_scale {
+        commands.push(Command::SetObjectScale {
+            object_id,
+            scale: new_scale,
+        });
+    }
+}
+
+fn layer_and_tags_editor(
+    ui: &mut egui::Ui,
+    commands: &mut Vec<Command>,
+    gui_state: &mut GuiState,
+    object_collection: &ObjectCollection,
+    object: &Object,
+    object_id: ObjectId,
+) {
+    let layer_name = |layer_id: Option<LayerId>| match layer_id
+        .and_then(|some_layer_id| object_collection.get_layer(some_layer_id))
+    {
+        Some(layer) => layer.name.clone(),
+        None => "None".to_string(),
+    };
+
+    let mut new_layer_id = object.layer();
+    ui.horizontal(|ui_h| {
+        ui_h.label("Layer:");
+        ComboBox::from_id_source("object layer")
+            .selected_text(layer_name(new_layer_id))
+            .show_ui(ui_h, |ui_combo| {
+                ui_combo.selectable_value(&mut new_layer_id, None, layer_name(None));
+                for (&layer_id, layer) in object_collection.layers().iter() {
+                    ui_combo.selectable_value(&mut new_layer_id, Some(layer_id), &layer.name);
+                }
+            });
+    });
+    if new_layer_id != object.layer() {
+        commands.push(Command::SetObjectLayer {
+            object_id,
+            layer_id: new_layer_id,
+        });
+    }
+
+    // click a tag to remove it
+    let mut new_tags = object.tags.clone();
+    ui.horizontal_wrapped(|ui_h| {
+        ui_h.label("Tags:");
+        for tag in &object.tags {
+            if ui_h.small_button(format!("{} ×", tag)).clicked() {
+                new_tags.retain(|new_tag| new_tag != tag);
+            }
+        }
+        let tag_response = ui_h.text_edit_singleline(&mut gui_state.tag_edit);
+        let submitted =
+            tag_response.lost_focus() && ui_h.input(|i| i.key_pressed(egui::Key::Enter));
+        if submitted || ui_h.button("Add tag").clicked() {
+            let new_tag = gui_state.tag_edit.trim().to_string();
+            if !new_tag.is_empty() && !new_tags.contains(&new_tag) {
+                new_tags.push(new_tag);
+            }
+            gui_state.tag_edit.clear();
+        }
+    });
+    if new_tags != object.tags {
+        commands.push(Command::SetObjectTags {
+            object_id,
+            tags: new_tags,
+        });
+    }
+}
+
+/// Shows which object an instance shares its primitive ops with. Returns the definition object
+/// if the object is an instance, otherwise `object`.
+fn instance_editor<'a>(
+    ui: &mut egui::Ui,
+    commands: &mut Vec<Command>,
+    object_collection: &'a ObjectCollection,
+    object: &'a Object,
+    object_id: ObjectId,
+) -> Option<&'a Object> {
+    let Some(definition_id) = object.instance_of() else {
+        return Some(object);
+    };
+    let definition = object_collection.get_object(definition_id)?;
+
+    ui.separator();
+    ui.horizontal(|ui_h| {
+        ui_h.label(format!(
+            "Instance of: {} - {}",
+            definition_id, definition.name
+        ));
+        if ui_h.button("Make unique").clicked() {
+            commands.push(Command::MakeInstanceUnique(object_id));
+        }
+    });
+
+    Some(definition)
 }
 
 fn primitive_op_editor(
     ui: &mut egui::Ui,
     commands: &mut Vec<Command>,
     gui_state: &mut GuiState,
+    materials: &BTreeMap<MaterialId, Material>,
     selected_object: &Object,
     selected_object_id: ObjectId,
     selected_primitive_op_id: Option<PrimitiveOpId>,
@@ -185,6 +371,7 @@ fn primitive_op_editor(
             ui,
             commands,
             gui_state,
+            materials,
             selected_object,
             selected_object_id,
             selected_prim_op_id,
@@ -198,6 +385,7 @@ fn existing_primitive_op_editor(
     ui: &mut egui::Ui,
     commands: &mut Vec<Command>,
     gui_state: &mut GuiState,
+    materials: &BTreeMap<MaterialId, Material>,
     selected_object: &Object,
     selected_object_id: ObjectId,
     selected_prim_op_id: PrimitiveOpId,
@@ -211,7 +399,7 @@ fn existing_primitive_op_editor(
             None => {
                 // selected_prim_op_id not in selected_obejct -> invalid id
                 debug!("selected object {} dropped", selected_object_id);
-                commands.push(ValidationCommand::SelectedObject().into());
+                commands.push(ValidationCommand::Selection().into());
 
                 new_primitive_op_editor(ui, commands, gui_state, selected_object_id);
                 return;
@@ -245,14 +433,49 @@ fn existing_primitive_op_editor(
     let primitive_edit_state = primitive_editor_ui(ui, gui_state);
     primitive_op_edit_state = primitive_op_edit_state.combine(primitive_edit_state);
 
-    // delete button
+    // domain modifiers
 
-    let delete_clicked = ui.button("Delete").clicked();
+    let modifiers_edit_state = domain_modifiers_editor_ui(ui, &mut gui_state.modifiers_edit);
+    primitive_op_edit_state = primitive_op_edit_state.combine(modifiers_edit_state);
+
+    // surface modifiers
+
+    let surface_modifiers_edit_state =
+        surface_modifiers_editor_ui(ui, &mut gui_state.surface_modifiers_edit);
+    primitive_op_edit_state = primitive_op_edit_state.combine(surface_modifiers_edit_state);
+
+    let target_primitive_op = TargetPrimitiveOp::Id(selected_object_id, selected_prim_op_id);
+
+    material_picker(
+        ui,
+        commands,
+        materials,
+        selected_primitive_op,
+        target_primitive_op,
+    );
+
+    // delete, duplicate and copy buttons
+
+    let (delete_clicked, duplicate_clicked, copy_clicked) = ui
+        .horizontal(|ui_h| {
+            (
+                ui_h.button("Delete").clicked(),
+                ui_h.button("Duplicate").clicked(),
+                ui_h.button("Copy").clicked(),
+            )
+        })
+        .inner;
     if delete_clicked {
-        let target_primitive_op = TargetPrimitiveOp::Id(selected_object_id, selected_prim_op_id);
         commands.push(Command::RemovePrimitiveOp(target_primitive_op));
         return;
     }
+    if duplicate_clicked {
+        commands.push(Command::DuplicatePrimitiveOp(target_primitive_op));
+        return;
+    }
+    if copy_clicked {
+        commands.push(Command::CopyPrimitiveOp(target_primitive_op));
+    }
 
     match primitive_op_edit_state {
         EditState::Modified => {
@@ -261,18 +484,67 @@ fn existing_primitive_op_editor(
                 TargetPrimitiveOp::Id(selected_object_id, selected_prim_op_id);
             commands.push(Command::SetPrimitiveOp {
                 target_primitive_op,
-                new_primitive: gui_state.primitive_edit,
+                new_primitive: gui_state.primitive_edit.clone(),
                 new_transform: gui_state.transform_edit,
                 new_operation: gui_state.op_edit,
                 new_blend: gui_state.blend_edit,
                 new_albedo: gui_state.albedo_edit,
                 new_specular: gui_state.specular_edit,
+                new_modifiers: gui_state.modifiers_edit.clone(),
+                new_surface_modifiers: gui_state.surface_modifiers_edit.clone(),
             });
         }
         EditState::NoChange => (),
     }
 }
 
+fn material_picker(
+    ui: &mut egui::Ui,
+    commands: &mut Vec<Command>,
+    materials: &BTreeMap<MaterialId, Material>,
+    primitive_op: &PrimitiveOp,
+    target_primitive_op: TargetPrimitiveOp,
+) {
+    let material_name = |material_id: Option<MaterialId>| match material_id
+        .and_then(|some_material_id| materials.get(&some_material_id))
+    {
+        Some(material) => material.name.clone(),
+        None => "None".to_string(),
+    };
+
+    let mut new_material_id = primitive_op.material;
+    let mut new_material_override = primitive_op.material_override;
+    ui.horizontal(|ui_h| {
+        ui_h.label("Material:");
+        ComboBox::from_id_source("primitive op material")
+            .selected_text(material_name(new_material_id))
+            .show_ui(ui_h, |ui_combo| {
+                ui_combo.selectable_value(&mut new_material_id, None, material_name(None));
+                for (&material_id, material) in materials.iter() {
+                    ui_combo.selectable_value(
+                        &mut new_material_id,
+                        Some(material_id),
+                        &material.name,
+                    );
+                }
+            });
+        ui_h.add_enabled_ui(new_material_id.is_some(), |ui_h| {
+            ui_h.checkbox(&mut new_material_override, "Override")
+                .on_hover_text("Use the color and specular above instead of the material");
+        });
+    });
+
+    if new_material_id != primitive_op.material
+        || new_material_override != primitive_op.material_override
+    {
+        commands.push(Command::SetPrimitiveOpMaterial {
+            target_primitive_op,
+            material_id: new_material_id,
+            material_override: new_material_override,
+        });
+    }
+}
+
 /// Returns wherever the object has been edited
 fn new_primitive_op_editor(
     ui: &mut egui::Ui,
@@ -368,79 +640,320 @@ fn primitive_type_drop_down(
     type_has_changed
 }
 
-/// Draw the primitive op list. each list element can be dragged/dropped elsewhere in the list,
-/// or selected with a button for editing.
+/// Draw the primitive op tree. primitive ops and groups can be dragged onto another primitive op
+/// to move before it, or onto a group to move to the end of that group. Primitive ops can be
+/// selected with a button for editing.
+/// Ctrl-click primitive ops to add them to the selection
 fn primitive_op_list(
     ui: &mut egui::Ui,
     commands: &mut Vec<Command>,
-    gui_state: &mut GuiState,
-    context: &egui::Context,
+    selection: &Selection,
     selected_object: &Object,
     selected_object_id: ObjectId,
     selected_primitive_op_id: Option<PrimitiveOpId>,
 ) {
     ui.separator();
 
-    // new primitive op button
-    let new_op_response =
-        ui.selectable_label(selected_primitive_op_id.is_none(), "New primitive op");
-    if new_op_response.clicked() {
-        commands.push(Command::DeselectPrimtiveOp());
-    }
+    ui.horizontal(|ui_h| {
+        // new primitive op button
+        let new_op_response =
+            ui_h.selectable_label(selected_primitive_op_id.is_none(), "New primitive op");
+        if new_op_response.clicked() {
+            commands.push(Command::DeselectPrimtiveOp());
+        }
 
-    let selected_prim_op = match selected_primitive_op_id {
-        Some(selected_prim_op_id) => {
-            match selected_object.get_primitive_op(selected_prim_op_id) {
-                Some(found_prim_op) => Some(found_prim_op),
-                None => {
-                    // selected_prim_op_id not in selected_obejct! invalid id so we should deselect
-                    debug!("primitive op id not found in selected object!");
-                    commands.push(Command::DeselectPrimtiveOp());
-                    None
-                }
-            }
+        // new group button
+        if ui_h.button("New group").clicked() {
+            commands.push(Command::PushPrimitiveOpGroup {
+                object_id: selected_object_id,
+                parent_group_id: None,
+            });
         }
-        None => None,
-    };
+    });
 
-    // draw each item in the primitive op list
-    let mut primitive_op_list_drag_state = gui_state.primitive_op_list_drag.clone();
-    let drag_drop_response = primitive_op_list_drag_state.list_ui::<PrimitiveOp>(
-        context,
-        ui,
-        selected_object.primitive_ops.iter(),
-        // function to draw a single primitive op entry in the list
-        |ui, drag_handle, index, primitive_op| {
-            primitive_op_list_item(
-                ui,
+    if let Some(selected_prim_op_id) = selected_primitive_op_id {
+        if selected_object
+            .get_primitive_op(selected_prim_op_id)
+            .is_none()
+        {
+            // selected_prim_op_id not in selected_obejct! invalid id so we should deselect
+            debug!("primitive op id not found in selected object!");
+            commands.push(ValidationCommand::Selection().into());
+        }
+    }
+
+    // the innermost element that something was dropped on sets this first
+    let mut drop_command: Option<Command> = None;
+
+    // draw the tree. dropping below the items moves to the end of the object
+    let (_, dropped_payload) =
+        ui.dnd_drop_zone::<PrimitiveOpTreeItem, _>(egui::Frame::none(), |ui_zone| {
+            primitive_op_tree_children(
+                ui_zone,
                 commands,
-                primitive_op,
-                index,
-                selected_prim_op,
-                drag_handle,
+                &mut drop_command,
+                selected_object,
                 selected_object_id,
+                None,
+                selection,
             );
-        },
+        });
+    if drop_command.is_none() {
+        if let Some(dropped_item) = dropped_payload {
+            drop_command = Some(move_tree_item_command(
+                *dropped_item,
+                selected_object_id,
+                None,
+                None,
+            ));
+        }
+    }
+
+    if let Some(some_drop_command) = drop_command {
+        commands.push(some_drop_command);
+    }
+}
+
+/// An element of the primitive op tree. Also used as the drag and drop payload.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum PrimitiveOpTreeItem {
+    PrimitiveOp(PrimitiveOpId),
+    Group(PrimitiveOpGroupId),
+}
+
+/// Returns the primitive ops and groups directly inside `parent` (`None` for the object root) in
+/// evaluation order. Groups without any primitive ops go at the end.
+fn tree_children(object: &Object, parent: Option<PrimitiveOpGroupId>) -> Vec<PrimitiveOpTreeItem> {
+    let mut children = Vec::new();
+    for primitive_op in &object.primitive_ops {
+        let ancestors = group_ancestors(primitive_op.group(), object.groups());
+        let parent_depth = match parent {
+            Some(parent_id) => match ancestors.iter().position(|group| group.id() == parent_id) {
+                Some(parent_index) => parent_index + 1,
+                None => continue,
+            },
+            None => 0,
+        };
+
+        let child = match ancestors.get(parent_depth) {
+            Some(child_group) => PrimitiveOpTreeItem::Group(child_group.id()),
+            None => PrimitiveOpTreeItem::PrimitiveOp(primitive_op.id()),
+        };
+        if !children.contains(&child) {
+            children.push(child);
+        }
+    }
+
+    for group in object.groups() {
+        let child = PrimitiveOpTreeItem::Group(group.id());
+        if group.parent() == parent && !children.contains(&child) {
+            children.push(child);
+        }
+    }
+    children
+}
+
+fn primitive_op_tree_children(
+    ui: &mut egui::Ui,
+    commands: &mut Vec<Command>,
+    drop_command: &mut Option<Command>,
+    object: &Object,
+    object_id: ObjectId,
+    parent: Option<PrimitiveOpGroupId>,
+    selection: &Selection,
+) {
+    for child in tree_children(object, parent) {
+        match child {
+            PrimitiveOpTreeItem::PrimitiveOp(primitive_op_id) => {
+                let Some((primitive_op, index)) =
+                    object.get_primitive_op_and_index(primitive_op_id)
+                else {
+                    continue;
+                };
+                primitive_op_list_item(
+                    ui,
+                    commands,
+                    drop_command,
+                    primitive_op,
+                    index,
+                    selection,
+                    object_id,
+                );
+            }
+            PrimitiveOpTreeItem::Group(group_id) => {
+                let Some(group) = object.get_group(group_id) else {
+                    continue;
+                };
+                primitive_op_group_item(
+                    ui,
+                    commands,
+                    drop_command,
+                    object,
+                    object_id,
+                    group,
+                    selection,
+                );
+            }
+        }
+    }
+}
+
+fn primitive_op_group_item(
+    ui: &mut egui::Ui,
+    commands: &mut Vec<Command>,
+    drop_command: &mut Option<Command>,
+    object: &Object,
+    object_id: ObjectId,
+    group: &PrimitiveOpGroup,
+    selection: &Selection,
+) {
+    let group_id = group.id();
+    let header_text = RichText::new(format!("{} {}", group.op.name(), group.name))
+        .text_style(TextStyle::Monospace);
+
+    let collapsing_id = ui.make_persistent_id(("primitive op group", object_id, group_id));
+    let collapsing_state = egui::collapsing_header::CollapsingState::load_with_default_open(
+        ui.ctx(),
+        collapsing_id,
+        true,
     );
-    gui_state.primitive_op_list_drag = primitive_op_list_drag_state;
-
-    // if an item has been dropped after being dragged, re-arrange the primtive ops list
-    if let DragDropResponse::Completed(drag_indices) = drag_drop_response {
-        commands.push(Command::ShiftPrimitiveOps {
-            object_id: selected_object_id,
-            source_index: drag_indices.source,
-            target_index: drag_indices.target,
+    let (_, header_response, _) = collapsing_state
+        .show_header(ui, |ui_header| {
+            // drag the header to move the whole group
+            let drag_id = egui::Id::new(("primitive op group drag", object_id, group_id));
+            ui_header
+                .dnd_drag_source(drag_id, PrimitiveOpTreeItem::Group(group_id), |ui_drag| {
+                    ui_drag.label(header_text);
+                })
+                .response
+        })
+        .body(|ui_body| {
+            primitive_op_group_editor(ui_body, commands, object_id, group);
+
+            // dropping below the items moves to the end of the group
+            let (_, dropped_payload) =
+                ui_body.dnd_drop_zone::<PrimitiveOpTreeItem, _>(egui::Frame::none(), |ui_zone| {
+                    primitive_op_tree_children(
+                        ui_zone,
+                        commands,
+                        drop_command,
+                        object,
+                        object_id,
+                        Some(group_id),
+                        selection,
+                    );
+                });
+            if drop_command.is_none() {
+                if let Some(dropped_item) = dropped_payload {
+                    *drop_command = Some(move_tree_item_command(
+                        *dropped_item,
+                        object_id,
+                        Some(group_id),
+                        None,
+                    ));
+                }
+            }
         });
+
+    // dropping on the header also moves to the end of the group
+    if drop_command.is_none() {
+        if let Some(dropped_item) = header_response
+            .inner
+            .dnd_release_payload::<PrimitiveOpTreeItem>()
+        {
+            *drop_command = Some(move_tree_item_command(
+                *dropped_item,
+                object_id,
+                Some(group_id),
+                None,
+            ));
+        }
+    }
+}
+
+fn primitive_op_group_editor(
+    ui: &mut egui::Ui,
+    commands: &mut Vec<Command>,
+    object_id: ObjectId,
+    group: &PrimitiveOpGroup,
+) {
+    let group_id = group.id();
+
+    let mut name = group.name.clone();
+    let mut op = group.op;
+    let mut blend = group.blend;
+    let mut new_op = None;
+
+    ui.horizontal(|ui_h| {
+        ui_h.label("Name:");
+        ui_h.text_edit_singleline(&mut name);
+    });
+    ui.horizontal(|ui_h| {
+        ui_h.label("Op:");
+        new_op = op_drop_down(ui_h, op, (object_id, group_id));
+    });
+    if let Some(some_new_op) = new_op {
+        op = some_new_op;
+    }
+    let group_edit_state =
+        blend_editor_ui(ui, &mut blend).combine(op_params_editor_ui(ui, &mut op));
+
+    if name != group.name || new_op.is_some() || group_edit_state == EditState::Modified {
+        commands.push(Command::SetPrimitiveOpGroup {
+            object_id,
+            group_id,
+            new_name: Some(name),
+            new_operation: Some(op),
+            new_blend: Some(blend),
+        });
+    }
+
+    ui.horizontal(|ui_h| {
+        if ui_h.button("Add sub-group").clicked() {
+            commands.push(Command::PushPrimitiveOpGroup {
+                object_id,
+                parent_group_id: Some(group_id),
+            });
+        }
+        if ui_h.button("Remove group").clicked() {
+            commands.push(Command::RemovePrimitiveOpGroup {
+                object_id,
+                group_id,
+            });
+        }
+    });
+}
+
+/// Command to move `item` into `group_id` before `before_primitive_op_id`
+fn move_tree_item_command(
+    item: PrimitiveOpTreeItem,
+    object_id: ObjectId,
+    group_id: Option<PrimitiveOpGroupId>,
+    before_primitive_op_id: Option<PrimitiveOpId>,
+) -> Command {
+    match item {
+        PrimitiveOpTreeItem::PrimitiveOp(primitive_op_id) => Command::MovePrimitiveOp {
+            object_id,
+            primitive_op_id,
+            group_id,
+            before_primitive_op_id,
+        },
+        PrimitiveOpTreeItem::Group(moved_group_id) => Command::MovePrimitiveOpGroup {
+            object_id,
+            group_id: moved_group_id,
+            parent_group_id: group_id,
+            before_primitive_op_id,
+        },
     }
 }
 
 fn primitive_op_list_item(
     ui: &mut egui::Ui,
     commands: &mut Vec<Command>,
+    drop_command: &mut Option<Command>,
     primitive_op: &PrimitiveOp,
     index: usize,
-    selected_prim_op: Option<&PrimitiveOp>,
-    drag_handle: egui_dnd::handle::DragHandle<'_>,
+    selection: &Selection,
     selected_object_id: ObjectId,
 ) {
     let draggable_text = RichText::new(format!("{}", index)).text_style(TextStyle::Monospace);
@@ -454,17 +967,19 @@ fn primitive_op_list_item(
     .text_style(TextStyle::Monospace);
 
     // check if this primitive op is selected
-    let is_selected = match selected_prim_op {
-        Some(some_selected_prim_op) => some_selected_prim_op.id() == primitive_op.id(),
-        None => false,
-    };
+    let is_selected = selection.contains_primitive_op(selected_object_id, primitive_op.id());
 
     // draw ui for this primitive op
-    ui.horizontal(|ui_h| {
-        // anything inside the handle can be used to drag the item
-        drag_handle.ui(ui_h, primitive_op, |handle_ui| {
-            handle_ui.label(draggable_text);
-        });
+    let item_response = ui.horizontal(|ui_h| {
+        // the index label can be used to drag the item
+        let drag_id = egui::Id::new(("primitive op drag", selected_object_id, primitive_op.id()));
+        ui_h.dnd_drag_source(
+            drag_id,
+            PrimitiveOpTreeItem::PrimitiveOp(primitive_op.id()),
+            |handle_ui| {
+                handle_ui.label(draggable_text);
+            },
+        );
 
         // label to select this primitive op
         let prim_op_res = ui_h.selectable_label(is_selected, primitive_op_text);
@@ -472,24 +987,56 @@ fn primitive_op_list_item(
         // primitive op selected
         if prim_op_res.clicked() {
             let target_primitive_op = TargetPrimitiveOp::Id(selected_object_id, primitive_op.id());
-            commands.push(Command::SelectPrimitiveOp(target_primitive_op))
+            if ui_h.input(|i| i.modifiers.ctrl) {
+                commands.push(Command::TogglePrimitiveOpSelection(target_primitive_op))
+            } else {
+                commands.push(Command::SelectPrimitiveOp(target_primitive_op))
+            }
         }
     });
+
+    // dropping on a primitive op moves the dragged item in front of it
+    if drop_command.is_none() {
+        if let Some(dropped_item) = item_response
+            .response
+            .dnd_release_payload::<PrimitiveOpTreeItem>()
+        {
+            if *dropped_item != PrimitiveOpTreeItem::PrimitiveOp(primitive_op.id()) {
+                *drop_command = Some(move_tree_item_command(
+                    *dropped_item,
+                    selected_object_id,
+                    primitive_op.group(),
+                    Some(primitive_op.id()),
+                ));
+            }
+        }
+    }
 }
 
 fn primitive_editor_ui(ui: &mut egui::Ui, gui_state: &mut GuiState) -> EditState {
     let primitive_edit_state = match &mut gui_state.primitive_edit {
         Primitive::Sphere(p) => sphere_editor_ui(ui, p),
         Primitive::Cube(p) => cube_editor_ui(ui, p),
+        Primitive::Torus(p) => torus_editor_ui(ui, p),
+        Primitive::CappedTorus(p) => capped_torus_editor_ui(ui, p),
+        Primitive::BoxFrame(p) => box_frame_editor_ui(ui, p),
+        Primitive::Cylinder(p) => cylinder_editor_ui(ui, p),
+        Primitive::Cone(p) => cone_editor_ui(ui, p),
+        Primitive::Capsule(p) => capsule_editor_ui(ui, p),
+        Primitive::Extrusion(p) => extrusion_editor_ui(ui, p),
+        Primitive::Revolution(p) => revolution_editor_ui(ui, p),
+        Primitive::Tube(p) => tube_editor_ui(ui, p),
         Primitive::UberPrimitive(p) => uber_primitive_editor_ui(ui, p),
     };
     let transform_edit_state = primitive_transform_editor_ui(ui, &mut gui_state.transform_edit);
     let blend_edit_state = blend_editor_ui(ui, &mut gui_state.blend_edit);
+    let op_params_edit_state = op_params_editor_ui(ui, &mut gui_state.op_edit);
     let color_specular_edit_state =
         color_specular_editor_ui(ui, &mut gui_state.albedo_edit, &mut gui_state.specular_edit);
 
     transform_edit_state
         .combine(primitive_edit_state)
         .combine(blend_edit_state)
+        .combine(op_params_edit_state)
         .combine(color_specular_edit_state)
 }
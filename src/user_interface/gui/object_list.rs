@@ -2,7 +2,11 @@ use super::Gui;
 use crate::{
     engine::{
         commands::Command,
-        object::{object::ObjectId, object_collection::ObjectCollection},
+        object::{
+            object::{Object, ObjectId},
+            object_collection::ObjectCollection,
+        },
+        selection::Selection,
     },
     helper::unique_id_gen::UniqueIdType,
 };
@@ -14,12 +18,12 @@ impl Gui {
     pub(super) fn draw_object_list_window(
         &mut self,
         object_collection: &ObjectCollection,
-        selected_object_id: Option<ObjectId>,
+        selection: &Selection,
     ) -> Vec<Command> {
         let mut commands = Vec::<Command>::new();
 
         let add_contents = |ui: &mut egui::Ui| {
-            commands = layout_object_list(ui, selected_object_id, object_collection);
+            commands = layout_object_list(ui, selection, object_collection);
         };
         egui::Window::new("Objects")
             .open(&mut self.sub_window_states.object_list)
@@ -34,10 +38,11 @@ impl Gui {
 
 fn layout_object_list(
     ui: &mut egui::Ui,
-    selected_object_id: Option<ObjectId>,
+    selection: &Selection,
     object_collection: &ObjectCollection,
 ) -> Vec<Command> {
     let mut commands = Vec::<Command>::new();
+    let selected_object_id = selection.active_object_id();
 
     ui.horizontal(|ui_h| {
         // add object button
@@ -46,7 +51,12 @@ fn layout_object_list(
             commands.push(Command::CreateAndSelectNewDefaultObject());
         }
 
-        // delete object button
+        // pastes objects to the root, or primitive ops into the selected object
+        if ui_h.button("Paste").clicked() {
+            commands.push(Command::Paste());
+        }
+
+        // delete object buttons
         if let Some(selected_object_id) = selected_object_id {
             if let Some(selected_object) = object_collection.get_object(selected_object_id) {
                 let delete_clicked = ui_h
@@ -56,30 +66,214 @@ fn layout_object_list(
                 if delete_clicked {
                     commands.push(Command::RemoveObject(selected_object_id));
                 }
+
+                if ui_h.button("Duplicate").clicked() {
+                    commands.push(Command::DuplicateObject(selected_object_id));
+                }
+
+                if ui_h.button("Copy").clicked() {
+                    commands.push(Command::CopyObject(selected_object_id));
+                }
+
+                if ui_h.button("Instance").clicked() {
+                    commands.push(Command::CreateInstance(selected_object_id));
+                }
+
+                if !object_collection.children(selected_object_id).is_empty() {
+                    let delete_children_clicked = ui_h.button("Delete with children").clicked();
+                    if delete_children_clicked {
+                        commands.push(Command::RemoveObjectAndChildren(selected_object_id));
+                    }
+                }
             } else {
                 debug!("selected object dropped. deselecting object...");
             }
         }
     });
 
-    // object list
-    for (&current_id, current_object) in object_collection.objects().iter() {
-        let label_text =
-            RichText::new(format!("{} - {}", current_id.raw_id(), current_object.name))
-                .text_style(TextStyle::Monospace);
+    // ctrl-click objects to add them to the selection
+    ui.horizontal(|ui_h| {
+        if ui_h.button("Select all").clicked() {
+            commands.push(Command::SelectAll());
+        }
+        if ui_h.button("Invert selection").clicked() {
+            commands.push(Command::InvertSelection());
+        }
 
-        let is_selected = if let Some(some_selected_object_id) = selected_object_id {
-            some_selected_object_id == current_id
-        } else {
-            false
-        };
+        let selected_count = selection.objects().len();
+        if selected_count > 1 {
+            let delete_selected_clicked = ui_h
+                .button(format!("Delete {} selected", selected_count))
+                .clicked();
+            if delete_selected_clicked {
+                commands.push(Command::RemoveSelectedObject());
+            }
+        }
+    });
 
-        let object_label_res = ui.selectable_label(is_selected, label_text);
-        if object_label_res.clicked() {
-            // select object in the object editor
-            commands.push(Command::SelectObject(current_id));
+    // object tree. dropping an object below the tree moves it to the root
+    let mut drop_command: Option<Command> = None;
+    let (_, dropped_payload) = ui.dnd_drop_zone::<ObjectId, _>(egui::Frame::none(), |ui_zone| {
+        for (&current_id, current_object) in object_collection.objects().iter() {
+            if current_object.parent().is_none() {
+                object_tree_item(
+                    ui_zone,
+                    &mut commands,
+                    &mut drop_command,
+                    object_collection,
+                    current_id,
+                    current_object,
+                    selection,
+                );
+            }
+        }
+    });
+    if drop_command.is_none() {
+        if let Some(dropped_object_id) = dropped_payload {
+            drop_command = Some(Command::SetObjectParent {
+                object_id: *dropped_object_id,
+                parent_id: None,
+            });
         }
     }
+    commands.extend(drop_command);
 
     commands
 }
+
+/// Draws an object and its children. Objects dropped on the item become its children.
+fn object_tree_item(
+    ui: &mut egui::Ui,
+    commands: &mut Vec<Command>,
+    drop_command: &mut Option<Command>,
+    object_collection: &ObjectCollection,
+    object_id: ObjectId,
+    object: &Object,
+    selection: &Selection,
+) {
+    let child_ids = object_collection.children(object_id);
+
+    let item_response = if child_ids.is_empty() {
+        object_list_item(ui, commands, object_id, object, selection)
+    } else {
+        let collapsing_id = ui.make_persistent_id(("object tree", object_id));
+        let collapsing_state = egui::collapsing_header::CollapsingState::load_with_default_open(
+            ui.ctx(),
+            collapsing_id,
+            true,
+        );
+        let (_, header_response, _) = collapsing_state
+            .show_header(ui, |ui_header| {
+                object_list_item(ui_header, commands, object_id, object, selection)
+            })
+            .body(|ui_body| {
+                for child_id in child_ids {
+                    if let Some(child) = object_collection.get_object(child_id) {
+                        object_tree_item(
+                            ui_body,
+                            commands,
+                            drop_command,
+                            object_collection,
+                            child_id,
+                            child,
+                            selection,
+                        );
+                    }
+                }
+            });
+        header_response.inner
+    };
+
+    if drop_command.is_none() {
+        if let Some(dropped_object_id) = item_response.dnd_release_payload::<ObjectId>() {
+            if *dropped_object_id != object_id {
+                *drop_command = Some(Command::SetObjectParent {
+                    object_id: *dropped_object_id,
+                    parent_id: Some(object_id),
+                });
+            }
+        }
+    }
+}
+
+fn object_list_item(
+    ui: &mut egui::Ui,
+    commands: &mut Vec<Command>,
+    object_id: ObjectId,
+    object: &Object,
+    selection: &Selection,
+) -> egui::Response {
+    let draggable_text =
+        RichText::new(format!("{}", object_id.raw_id())).text_style(TextStyle::Monospace);
+    let mut label_text = RichText::new(object.name.clone()).text_style(TextStyle::Monospace);
+    if object.instance_of().is_some() {
+        label_text = label_text.italics();
+    }
+    if !object.visible {
+        label_text = label_text.weak();
+    }
+
+    let is_selected = selection.contains_object(object_id);
+
+    ui.horizontal(|ui_h| {
+        // the id label can be used to drag the object to a new parent
+        let drag_id = egui::Id::new(("object drag", object_id));
+        ui_h.dnd_drag_source(drag_id, object_id, |handle_ui| {
+            handle_ui.label(draggable_text);
+        });
+
+        object_flag_toggles(ui_h, commands, object_id, object);
+
+        let object_label_res = ui_h.selectable_label(is_selected, label_text);
+        if object_label_res.clicked() {
+            if ui_h.input(|i| i.modifiers.ctrl) {
+                commands.push(Command::ToggleObjectSelection(object_id));
+            } else {
+                // select object in the object editor
+                commands.push(Command::SelectObject(object_id));
+            }
+        }
+    })
+    .response
+}
+
+/// Visible, locked and selectable toggles. Highlighted icons mean the flag is set.
+fn object_flag_toggles(
+    ui: &mut egui::Ui,
+    commands: &mut Vec<Command>,
+    object_id: ObjectId,
+    object: &Object,
+) {
+    if ui
+        .selectable_label(object.visible, "👁")
+        .on_hover_text("Visible")
+        .clicked()
+    {
+        commands.push(Command::SetObjectVisible {
+            object_id,
+            visible: !object.visible,
+        });
+    }
+
+    if ui
+        .selectable_label(object.locked, "🔒")
+        .on_hover_text("Locked")
+        .clicked()
+    {
+        commands.push(Command::SetObjectLocked {
+            object_id,
+            locked: !object.locked,
+        });
+    }
+
+    if ui
+        .selectable_label(object.selectable, "🖱")
+        .on_hover_text("Selectable in the viewport")
+        .clicked()
+    {
+        commands.push(Command::SetObjectSelectable {
+            object_id,
+            selectable: !object.selectable,
+        });
+    }
+}
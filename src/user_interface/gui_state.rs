@@ -1,9 +1,12 @@
 use crate::engine::{
     config_engine::{DEFAULT_ALBEDO, DEFAULT_SPECULAR},
-    object::{operation::Operation, primitive_op::PrimitiveOp},
+    group_transform::GroupTransform,
+    object::{
+        domain_modifier::DomainModifier, operation::Operation, primitive_op::PrimitiveOp,
+        surface_modifier::SurfaceModifier,
+    },
     primitives::{primitive::Primitive, primitive_transform::PrimitiveTransform},
 };
-use egui_dnd::DragDropUi;
 use glam::Vec3;
 
 /// Wherver or not different windows are open
@@ -11,6 +14,9 @@ use glam::Vec3;
 pub struct SubWindowStates {
     pub object_list: bool,
     pub object_editor: bool,
+    pub layer_panel: bool,
+    pub materials: bool,
+    pub group_transform: bool,
     pub camera_control: bool,
     pub command_palette: bool,
     pub debug_options: bool,
@@ -20,6 +26,9 @@ impl Default for SubWindowStates {
         Self {
             object_list: true,
             object_editor: true,
+            layer_panel: false,
+            materials: false,
+            group_transform: false,
             camera_control: false,
             command_palette: false,
             debug_options: false,
@@ -40,38 +49,47 @@ pub struct GuiState {
     pub op_edit: Operation,
     /// Stores the state of the blend field in the gui editor
     pub blend_edit: f32,
-    /// Stores the drag and drop state of the primitive op list of the selected object
-    pub primitive_op_list_drag: DragDropUi,
 
     pub albedo_edit: Vec3,
     pub specular_edit: f32,
+    /// Stores the state of the domain modifier list in the gui editor
+    pub modifiers_edit: Vec<DomainModifier>,
+    /// Stores the state of the surface modifier list in the gui editor
+    pub surface_modifiers_edit: Vec<SurfaceModifier>,
+    /// Stores the tag being typed in the object editor
+    pub tag_edit: String,
+    /// Stores the translation, scale and pivot fields of the selection transform window
+    pub group_transform_edit: GroupTransform,
+    /// Stores the rotation field of the selection transform window as euler angles in degrees
+    pub group_rotation_edit: Vec3,
 }
 
 // Setters
 impl GuiState {
     pub fn set_selected_primitive_op(&mut self, selected_primitive_op: &PrimitiveOp) {
-        self.primitive_edit = selected_primitive_op.primitive;
+        self.primitive_edit = selected_primitive_op.primitive.clone();
         self.op_edit = selected_primitive_op.op;
         self.albedo_edit = selected_primitive_op.albedo;
         self.specular_edit = selected_primitive_op.specular;
-    }
-
-    /// Call this if no object is selected
-    pub fn reset_primitive_op_list_drag_state(&mut self) {
-        self.primitive_op_list_drag = Default::default();
+        self.modifiers_edit = selected_primitive_op.modifiers.clone();
+        self.surface_modifiers_edit = selected_primitive_op.surface_modifiers.clone();
     }
 
     pub fn reset_primitive_op_fields(&mut self) {
         self.op_edit = Default::default();
         self.transform_edit = Default::default();
         self.primitive_edit = Default::default();
+        self.modifiers_edit = Vec::new();
+        self.surface_modifiers_edit = Vec::new();
     }
 
     pub fn set_primitive_op_edit_state(&mut self, primitive_op: &PrimitiveOp) {
-        self.primitive_edit = primitive_op.primitive;
+        self.primitive_edit = primitive_op.primitive.clone();
         self.transform_edit = primitive_op.transform;
         self.op_edit = primitive_op.op;
         self.blend_edit = primitive_op.blend;
+        self.modifiers_edit = primitive_op.modifiers.clone();
+        self.surface_modifiers_edit = primitive_op.surface_modifiers.clone();
     }
 }
 
@@ -84,7 +102,11 @@ impl Default for GuiState {
             primitive_edit: Default::default(),
             albedo_edit: DEFAULT_ALBEDO,
             specular_edit: DEFAULT_SPECULAR,
-            primitive_op_list_drag: Default::default(),
+            modifiers_edit: Vec::new(),
+            surface_modifiers_edit: Vec::new(),
+            tag_edit: String::new(),
+            group_transform_edit: GroupTransform::IDENTITY,
+            group_rotation_edit: Vec3::ZERO,
         }
     }
 }